@@ -1,4 +1,4 @@
-use clap::Parser;
+use clap::{Args, Parser, Subcommand};
 use std::path::PathBuf;
 
 #[derive(Parser, Debug)]
@@ -6,8 +6,11 @@ use std::path::PathBuf;
 #[command(about = "Speedy GraphQL Codegen - A fast GraphQL code generator")]
 #[command(version)]
 pub(crate) struct CliArgs {
+    #[command(subcommand)]
+    pub command: Option<Command>,
+
     /// Path to the config file (JSON)
-    #[arg(short, long, default_value = "codegen.json")]
+    #[arg(short, long, default_value = "codegen.json", global = true)]
     pub config: PathBuf,
 
     /// Check mode - validate without writing files
@@ -42,3 +45,45 @@ pub(crate) struct CliArgs {
     #[arg(long)]
     pub max_diagnostics: Option<usize>,
 }
+
+#[derive(Subcommand, Debug)]
+pub(crate) enum Command {
+    /// Compare two schemas and classify changes as breaking, dangerous or
+    /// safe. Exits with an error when any change is breaking.
+    Diff(DiffArgs),
+
+    /// Report which schema fields the configured documents select: unused
+    /// fields and deprecated fields still in use
+    Coverage(CoverageArgs),
+
+    /// Report the depth, aliased fields and cost of each operation. Exits
+    /// with an error when an operation exceeds a configured limit.
+    Complexity(ComplexityArgs),
+}
+
+#[derive(Args, Debug)]
+pub(crate) struct DiffArgs {
+    /// Previous schema (SDL or introspection JSON, globs allowed)
+    pub old: String,
+
+    /// New schema (SDL or introspection JSON, globs allowed)
+    pub new: String,
+
+    /// Print changes as JSON
+    #[arg(long)]
+    pub json: bool,
+}
+
+#[derive(Args, Debug)]
+pub(crate) struct CoverageArgs {
+    /// Print usage of every field as JSON
+    #[arg(long)]
+    pub json: bool,
+}
+
+#[derive(Args, Debug)]
+pub(crate) struct ComplexityArgs {
+    /// Print the complexity of every operation as JSON
+    #[arg(long)]
+    pub json: bool,
+}
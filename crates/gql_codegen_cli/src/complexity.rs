@@ -0,0 +1,76 @@
+//! `complexity` subcommand: depth, aliases and cost of each operation
+
+use std::path::Path;
+use std::process::ExitCode;
+
+use anyhow::Result;
+use console::style;
+use gql_codegen_core::{ComplexityReport, operation_complexity};
+
+use crate::args::ComplexityArgs;
+use crate::logger::Logger;
+use crate::project::{print_diagnostics, with_documents};
+use crate::read_config;
+
+pub(crate) fn run_complexity(
+    config_path: &Path,
+    args: &ComplexityArgs,
+    logger: &Logger,
+) -> Result<ExitCode> {
+    let config = read_config(config_path)?;
+    let limits = config.complexity.clone().unwrap_or_default();
+    let report = with_documents(&config, |schema, documents, cache| {
+        Ok(operation_complexity(schema, documents, cache, &limits))
+    })?;
+
+    if args.json {
+        println!("{}", serde_json::to_string_pretty(&report)?);
+    } else {
+        print_report(&report);
+    }
+
+    let violations = report.violations(&limits);
+    if !violations.is_empty() {
+        print_diagnostics(&violations);
+        logger.error(&format!(
+            "{} operation limits exceeded",
+            violations.errors().count()
+        ));
+        return Ok(ExitCode::FAILURE);
+    }
+
+    logger.success(&format!(
+        "{} operations within limits",
+        report.operations.len()
+    ));
+    Ok(ExitCode::SUCCESS)
+}
+
+fn print_report(report: &ComplexityReport) {
+    if report.operations.is_empty() {
+        return;
+    }
+
+    let width = report
+        .operations
+        .keys()
+        .map(String::len)
+        .chain(["Operation".len()])
+        .max()
+        .unwrap_or_default();
+    println!(
+        "{}",
+        style(format!(
+            "{:width$}  {:>5}  {:>7}  {:>8}",
+            "Operation", "Depth", "Aliases", "Cost"
+        ))
+        .bold()
+    );
+    for (name, operation) in &report.operations {
+        println!(
+            "{name:width$}  {:>5}  {:>7}  {:>8}",
+            operation.depth, operation.aliases, operation.cost
+        );
+    }
+    println!();
+}
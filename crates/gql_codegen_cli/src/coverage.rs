@@ -0,0 +1,82 @@
+//! `coverage` subcommand: which schema fields client operations select
+
+use std::path::Path;
+use std::process::ExitCode;
+
+use anyhow::Result;
+use console::style;
+use gql_codegen_core::{FieldCoverage, field_coverage};
+
+use crate::args::CoverageArgs;
+use crate::logger::Logger;
+use crate::project::with_documents;
+use crate::read_config;
+
+pub(crate) fn run_coverage(
+    config_path: &Path,
+    args: &CoverageArgs,
+    logger: &Logger,
+) -> Result<ExitCode> {
+    let config = read_config(config_path)?;
+    let (coverage, operations) = with_documents(&config, |schema, documents, cache| {
+        Ok((
+            field_coverage(schema, documents, cache, config.federation),
+            documents.operations.len(),
+        ))
+    })?;
+
+    if args.json {
+        println!("{}", serde_json::to_string_pretty(&coverage)?);
+    } else {
+        print_report(&coverage);
+    }
+
+    let used = coverage.fields.values().filter(|f| f.is_used()).count();
+    logger.success(&format!(
+        "{used} of {} fields used by {} operations",
+        coverage.fields.len(),
+        operations
+    ));
+    Ok(ExitCode::SUCCESS)
+}
+
+fn print_report(coverage: &FieldCoverage) {
+    let unused: Vec<_> = coverage.unused().collect();
+    if !unused.is_empty() {
+        println!("{} ({})", style("Unused fields").bold(), unused.len());
+        for (name, stats) in unused {
+            match &stats.deprecation_reason {
+                Some(reason) => {
+                    println!("  {name}  {}", style(format!("deprecated: {reason}")).dim())
+                }
+                None => println!("  {name}"),
+            }
+        }
+        println!();
+    }
+
+    let deprecated: Vec<_> = coverage.deprecated_used().collect();
+    if !deprecated.is_empty() {
+        println!(
+            "{} ({})",
+            style("Deprecated fields in use").bold(),
+            deprecated.len()
+        );
+        for (name, stats) in deprecated {
+            let reason = stats.deprecation_reason.as_deref().unwrap_or_default();
+            println!(
+                "  {} {name}  {}",
+                style("⚠").yellow().bold(),
+                style(reason).dim()
+            );
+            for usage in &stats.usages {
+                let location = usage.location.as_ref().map_or_else(
+                    || "<unknown>".to_string(),
+                    |l| format!("{}:{}:{}", l.file.display(), l.line, l.column),
+                );
+                println!("      {location}  {}", style(&usage.operation).dim());
+            }
+        }
+        println!();
+    }
+}
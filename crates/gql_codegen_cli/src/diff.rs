@@ -0,0 +1,65 @@
+//! `diff` subcommand: report schema changes for CI
+
+use std::process::ExitCode;
+
+use anyhow::Result;
+use console::style;
+use gql_codegen_core::{ChangeSeverity, SchemaDiff, schema_diff};
+
+use crate::args::DiffArgs;
+use crate::load_schema::load_schema;
+use crate::logger::Logger;
+
+pub(crate) fn run_diff(args: &DiffArgs, logger: &Logger) -> Result<ExitCode> {
+    let old = load_schema(&args.old)?;
+    let new = load_schema(&args.new)?;
+    let diff = schema_diff(&old, &new);
+
+    if args.json {
+        println!("{}", serde_json::to_string_pretty(&diff)?);
+    } else {
+        print_changes(&diff);
+    }
+
+    let breaking = diff.with_severity(ChangeSeverity::Breaking).count();
+    if breaking > 0 {
+        let plural = if breaking == 1 { "" } else { "s" };
+        logger.error(&format!("Found {breaking} breaking change{plural}"));
+        return Ok(ExitCode::FAILURE);
+    }
+
+    if diff.is_empty() {
+        logger.success("No changes");
+    } else {
+        logger.success("No breaking changes");
+    }
+    Ok(ExitCode::SUCCESS)
+}
+
+fn print_changes(diff: &SchemaDiff) {
+    for severity in [
+        ChangeSeverity::Breaking,
+        ChangeSeverity::Dangerous,
+        ChangeSeverity::Safe,
+    ] {
+        let changes: Vec<_> = diff.with_severity(severity).collect();
+        if changes.is_empty() {
+            continue;
+        }
+
+        let (heading, marker) = match severity {
+            ChangeSeverity::Breaking => ("Breaking changes", style("✗").red().bold()),
+            ChangeSeverity::Dangerous => ("Dangerous changes", style("⚠").yellow().bold()),
+            ChangeSeverity::Safe => ("Safe changes", style("✓").green().bold()),
+        };
+        println!("{} ({})", style(heading).bold(), changes.len());
+        for change in changes {
+            println!(
+                "  {marker} {}  {}",
+                change.message,
+                style(&change.path).dim()
+            );
+        }
+        println!();
+    }
+}
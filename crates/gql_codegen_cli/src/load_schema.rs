@@ -1,3 +1,10 @@
-pub(crate) fn load_schema() {
+use anyhow::Result;
+use apollo_compiler::Schema;
+use apollo_compiler::validation::Valid;
+use gql_codegen_core::{load_schema as load_schema_paths, resolve_schema_paths};
 
+/// Load a schema from a path or glob, e.g. `schema/*.graphql`
+pub(crate) fn load_schema(pattern: &str) -> Result<Valid<Schema>> {
+    let paths = resolve_schema_paths(&[pattern], None);
+    Ok(load_schema_paths(&paths)?)
 }
@@ -39,5 +39,4 @@ impl Logger {
             eprintln!("  {} {}", style("→").dim(), style(path).dim());
         }
     }
-
 }
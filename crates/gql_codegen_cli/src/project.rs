@@ -0,0 +1,103 @@
+//! Schema and documents of the configured project, for analysis commands
+
+use std::collections::HashSet;
+use std::path::{Path, PathBuf};
+
+use anyhow::Result;
+use apollo_compiler::Schema;
+use apollo_compiler::validation::Valid;
+use gql_codegen_core::diagnostic::{self, Color};
+use gql_codegen_core::{
+    CodegenConfig, CollectedDocuments, Diagnostics, SourceCache, collect_documents_in,
+    expand_document_globs, expand_output_documents, extend_schema, load_config_schema,
+    load_sources_from_paths,
+};
+
+/// Load the schema and documents of `config` and call `f` with them.
+/// Operations and fragments only found in the `documents` of an output are
+/// included too. Schema and document warnings are printed to stderr.
+pub(crate) fn with_documents<T>(
+    config: &CodegenConfig,
+    f: impl FnOnce(&Valid<Schema>, &CollectedDocuments, &SourceCache) -> Result<T>,
+) -> Result<T> {
+    let mut warnings = Diagnostics::new();
+    let schema = load_config_schema(config, &mut warnings)?;
+
+    let base_dir = Path::new(".");
+    let ignore = config.ignore_list();
+    let document_paths = expand_document_globs(&config.documents, base_dir, &ignore)?;
+    let output_paths = expand_output_documents(config, base_dir, &ignore)?;
+
+    let mut load_paths = document_paths.clone();
+    let mut seen: HashSet<&PathBuf> = document_paths.iter().collect();
+    let mut outputs: Vec<_> = output_paths.iter().collect();
+    outputs.sort();
+    for (_, paths) in &outputs {
+        load_paths.extend(paths.iter().filter(|path| seen.insert(path)).cloned());
+    }
+    let mut cache = SourceCache::with_capacity(load_paths.len());
+    load_sources_from_paths(&load_paths, &mut cache)?;
+
+    let mut documents = collect_documents_in(
+        &cache,
+        &config.extract_config(),
+        config.anonymous_operations,
+        &document_paths.into_iter().collect(),
+    );
+    for (name, paths) in outputs {
+        let extract_config = config.output_extract_config(&config.outputs[name]);
+        let output_documents = collect_documents_in(
+            &cache,
+            &extract_config,
+            config.anonymous_operations,
+            &paths.iter().cloned().collect(),
+        );
+        merge_documents(&mut documents, output_documents);
+    }
+
+    let schema = extend_schema(
+        schema,
+        &documents,
+        &cache,
+        config.schema_options(),
+        &mut warnings,
+    )?;
+
+    warnings.extend(documents.diagnostics.clone());
+    print_diagnostics(&warnings);
+
+    f(&schema, &documents, &cache)
+}
+
+/// Add the definitions of `other` that `documents` doesn't have yet. Files
+/// read by several outputs yield the same definitions, kept once.
+fn merge_documents<'a>(documents: &mut CollectedDocuments<'a>, other: CollectedDocuments<'a>) {
+    let mut seen: HashSet<_> = documents
+        .schema_extensions
+        .iter()
+        .map(|extension| (extension.source_idx, extension.text.as_ptr()))
+        .collect();
+    for (name, operation) in other.operations {
+        documents.operations.entry(name).or_insert(operation);
+    }
+    for (name, fragment) in other.fragments {
+        documents.fragments.entry(name).or_insert(fragment);
+    }
+    for extension in other.schema_extensions {
+        if seen.insert((extension.source_idx, extension.text.as_ptr())) {
+            documents.schema_extensions.push(extension);
+        }
+    }
+    documents.diagnostics.extend(other.diagnostics);
+}
+
+pub(crate) fn print_diagnostics(diagnostics: &Diagnostics) {
+    for diagnostic in diagnostics.iter() {
+        let _ = diagnostic::render_diagnostic(
+            diagnostic,
+            None,
+            Color::StderrIsTerminal,
+            &mut std::io::stderr(),
+        );
+    }
+}
@@ -0,0 +1,66 @@
+//! Tests for the `complexity` subcommand
+
+use std::fs;
+use std::path::PathBuf;
+use std::process::{Command, Output};
+
+const SCHEMA: &str = r#"
+type Query { users(first: Int): [User!]! }
+type User { id: ID!, friends(first: Int): [User!]! }
+"#;
+
+fn complexity(name: &str, complexity: &str, extra: &[&str]) -> Output {
+    let dir = PathBuf::from(env!("CARGO_TARGET_TMPDIR")).join(name);
+    fs::create_dir_all(dir.join("src")).unwrap();
+    fs::write(dir.join("schema.graphql"), SCHEMA).unwrap();
+    fs::write(
+        dir.join("src/users.graphql"),
+        "query GetUsers {\n  users(first: 10) {\n    ...UserFields\n  }\n}\n\nfragment UserFields on User {\n  id\n  friends(first: 5) { id }\n}\n",
+    )
+    .unwrap();
+    fs::write(
+        dir.join("codegen.json"),
+        format!(
+            r#"{{ "schema": "schema.graphql", "documents": "src/**/*.graphql", "outputs": {{}}, "complexity": {complexity} }}"#
+        ),
+    )
+    .unwrap();
+
+    Command::new(env!("CARGO_BIN_EXE_gql-codegen"))
+        .current_dir(&dir)
+        .arg("complexity")
+        .args(extra)
+        .output()
+        .unwrap()
+}
+
+#[test]
+fn test_report_within_limits() {
+    let output = complexity("complexity_report", r#"{ "maxDepth": 3 }"#, &[]);
+    let stdout = String::from_utf8_lossy(&output.stdout);
+
+    assert!(output.status.success(), "{output:?}");
+    // 1 + 10 * (1 + (1 + 5 * 1))
+    assert!(
+        stdout.contains("GetUsers       3        0        71"),
+        "{stdout}"
+    );
+}
+
+#[test]
+fn test_json_with_violation() {
+    let output = complexity("complexity_json", r#"{ "maxCost": 50 }"#, &["--json"]);
+    let stderr = String::from_utf8_lossy(&output.stderr);
+
+    assert!(!output.status.success(), "{output:?}");
+    assert!(
+        stderr.contains("Operation 'GetUsers' has cost 71, exceeding the limit of 50"),
+        "{stderr}"
+    );
+
+    let json: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    let operation = &json["operations"]["GetUsers"];
+    assert_eq!(operation["depth"], 3);
+    assert_eq!(operation["cost"], 71);
+    assert_eq!(operation["location"]["line"], 1);
+}
@@ -0,0 +1,89 @@
+//! Tests for the `coverage` subcommand
+
+use std::fs;
+use std::path::PathBuf;
+use std::process::{Command, Output};
+
+const SCHEMA: &str = r#"
+type Query { user(id: ID!): User }
+type User {
+  id: ID!
+  name: String!
+  username: String @deprecated(reason: "Use `name`")
+  avatar: String
+}
+"#;
+
+const CONFIG: &str =
+    r#"{ "schema": "schema.graphql", "documents": "src/**/*.graphql", "outputs": {} }"#;
+
+fn coverage(name: &str, extra: &[&str]) -> Output {
+    coverage_with(name, CONFIG, extra)
+}
+
+fn coverage_with(name: &str, config: &str, extra: &[&str]) -> Output {
+    let dir = PathBuf::from(env!("CARGO_TARGET_TMPDIR")).join(name);
+    fs::create_dir_all(dir.join("src")).unwrap();
+    fs::write(dir.join("schema.graphql"), SCHEMA).unwrap();
+    fs::write(
+        dir.join("src/user.graphql"),
+        "query GetUser {\n  user(id: 1) {\n    ...UserFields\n  }\n}\n\nfragment UserFields on User {\n  id\n  username\n}\n",
+    )
+    .unwrap();
+    fs::write(
+        dir.join("admin.graphql"),
+        "query GetUserName {\n  user(id: 1) {\n    name\n  }\n}\n",
+    )
+    .unwrap();
+    fs::write(dir.join("codegen.json"), config).unwrap();
+
+    Command::new(env!("CARGO_BIN_EXE_gql-codegen"))
+        .current_dir(&dir)
+        .arg("coverage")
+        .args(extra)
+        .output()
+        .unwrap()
+}
+
+#[test]
+fn test_report() {
+    let output = coverage("coverage_report", &[]);
+    let stdout = String::from_utf8_lossy(&output.stdout);
+
+    assert!(output.status.success(), "{output:?}");
+    assert!(stdout.contains("Unused fields (2)"), "{stdout}");
+    assert!(stdout.contains("  User.name\n"), "{stdout}");
+    assert!(stdout.contains("  User.avatar\n"), "{stdout}");
+    assert!(stdout.contains("Deprecated fields in use (1)"), "{stdout}");
+    assert!(stdout.contains("User.username  Use `name`"), "{stdout}");
+    assert!(stdout.contains("src/user.graphql:9:3  GetUser"), "{stdout}");
+}
+
+#[test]
+fn test_json() {
+    let output = coverage("coverage_json", &["--json"]);
+    assert!(output.status.success(), "{output:?}");
+
+    let json: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    let usages = json["fields"]["User.id"]["usages"].as_array().unwrap();
+    assert_eq!(usages.len(), 1);
+    assert_eq!(usages[0]["operation"], "GetUser");
+    assert_eq!(usages[0]["location"]["line"], 8);
+}
+
+#[test]
+fn test_output_documents() {
+    let config = r#"{
+        "schema": "schema.graphql",
+        "documents": "src/**/*.graphql",
+        "outputs": { "admin.ts": { "documents": "admin.graphql" } }
+    }"#;
+    let output = coverage_with("coverage_output_documents", config, &[]);
+    let stdout = String::from_utf8_lossy(&output.stdout);
+
+    assert!(output.status.success(), "{output:?}");
+    assert!(stdout.contains("Unused fields (1)"), "{stdout}");
+    assert!(!stdout.contains("  User.name\n"), "{stdout}");
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(stderr.contains("used by 2 operations"), "{stderr}");
+}
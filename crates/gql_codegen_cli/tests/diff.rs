@@ -0,0 +1,61 @@
+//! Tests for the `diff` subcommand
+
+use std::fs;
+use std::path::PathBuf;
+use std::process::{Command, Output};
+
+const OLD: &str = "type Query { user: User }\ntype User { id: ID!, email: String }\n";
+
+fn diff(name: &str, new: &str, extra: &[&str]) -> Output {
+    let dir = PathBuf::from(env!("CARGO_TARGET_TMPDIR")).join(name);
+    fs::create_dir_all(&dir).unwrap();
+    fs::write(dir.join("old.graphql"), OLD).unwrap();
+    fs::write(dir.join("new.graphql"), new).unwrap();
+
+    Command::new(env!("CARGO_BIN_EXE_gql-codegen"))
+        .current_dir(&dir)
+        .args(["diff", "old.graphql", "new.graphql"])
+        .args(extra)
+        .output()
+        .unwrap()
+}
+
+#[test]
+fn test_breaking_change_fails() {
+    let output = diff(
+        "diff_breaking",
+        "type Query { user: User }\ntype User { id: ID! }\n",
+        &[],
+    );
+    let stdout = String::from_utf8_lossy(&output.stdout);
+
+    assert!(!output.status.success());
+    assert!(stdout.contains("Breaking changes (1)"), "{stdout}");
+    assert!(
+        stdout.contains("Field 'email' was removed from 'User'"),
+        "{stdout}"
+    );
+}
+
+#[test]
+fn test_safe_change_as_json() {
+    let output = diff(
+        "diff_safe",
+        "type Query { user: User }\ntype User { id: ID!, email: String, name: String }\n",
+        &["--json"],
+    );
+    assert!(output.status.success());
+
+    let json: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(
+        json,
+        serde_json::json!({
+            "changes": [{
+                "severity": "safe",
+                "kind": "FIELD_ADDED",
+                "path": "User.name",
+                "message": "Field 'name' was added to 'User'"
+            }]
+        })
+    );
+}
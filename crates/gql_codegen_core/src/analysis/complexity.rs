@@ -0,0 +1,200 @@
+use std::collections::HashSet;
+
+use apollo_compiler::ast::{Field, OperationDefinition, Selection, Value};
+use apollo_compiler::{Name, Schema};
+use indexmap::IndexMap;
+use serde::Serialize;
+
+use crate::config::ComplexityConfig;
+use crate::diagnostic::{Diagnostic, DiagnosticCategory, DiagnosticLocation, Diagnostics};
+use crate::documents::CollectedDocuments;
+use crate::source_cache::SourceCache;
+
+/// Depth, aliases and cost of every operation
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct ComplexityReport {
+    /// Keyed by operation name, in document order
+    pub operations: IndexMap<String, OperationComplexity>,
+}
+
+/// Complexity of one operation, with fragments expanded
+#[derive(Debug, Clone, Serialize)]
+pub struct OperationComplexity {
+    /// Deepest field nesting, root fields being at depth 1
+    pub depth: usize,
+    /// Number of aliased fields
+    pub aliases: usize,
+    pub cost: u64,
+    pub location: Option<DiagnosticLocation>,
+}
+
+impl ComplexityReport {
+    /// An error for every limit of `config` an operation exceeds
+    pub fn violations(&self, config: &ComplexityConfig) -> Diagnostics {
+        let mut diagnostics = Diagnostics::new();
+        for (name, operation) in &self.operations {
+            let limits = [
+                (
+                    "depth",
+                    operation.depth as u64,
+                    config.max_depth.map(|max| max as u64),
+                ),
+                (
+                    "aliased fields",
+                    operation.aliases as u64,
+                    config.max_aliases.map(|max| max as u64),
+                ),
+                ("cost", operation.cost, config.max_cost),
+            ];
+            for (metric, value, max) in limits {
+                let Some(max) = max.filter(|max| value > *max) else {
+                    continue;
+                };
+                let mut diagnostic = Diagnostic::error(
+                    DiagnosticCategory::Document,
+                    format!(
+                        "Operation '{name}' has {metric} {value}, exceeding the limit of {max}"
+                    ),
+                );
+                if let Some(location) = &operation.location {
+                    diagnostic = diagnostic.with_location(location.clone());
+                }
+                diagnostics.push(diagnostic);
+            }
+        }
+        diagnostics
+    }
+}
+
+/// Compute the complexity of each operation.
+///
+/// Selecting a field costs its weight plus the cost of its selections times
+/// the size of the list it returns: the `first` or `last` argument when
+/// given, otherwise `defaultListSize` for list types and 1 for others. All
+/// branches of inline fragments and spreads count, so the cost of a
+/// selection on an abstract type is an upper bound. Meta fields such as
+/// `__typename` are free and don't add depth.
+pub fn operation_complexity(
+    schema: &Schema,
+    documents: &CollectedDocuments,
+    cache: &SourceCache,
+    config: &ComplexityConfig,
+) -> ComplexityReport {
+    let mut report = ComplexityReport::default();
+    for (name, operation) in &documents.operations {
+        let definition = &operation.definition;
+        let Some(root) = schema.root_operation(definition.operation_type) else {
+            continue;
+        };
+
+        let mut walker = Walker {
+            schema,
+            documents,
+            config,
+            operation: definition,
+            expanding: HashSet::new(),
+            aliases: 0,
+        };
+        let (depth, cost) = walker.walk(root, &definition.selection_set);
+        report.operations.insert(
+            name.to_string(),
+            OperationComplexity {
+                depth,
+                aliases: walker.aliases,
+                cost,
+                location: operation.name_location(cache),
+            },
+        );
+    }
+    report
+}
+
+struct Walker<'s, 'd, 'a> {
+    schema: &'s Schema,
+    documents: &'d CollectedDocuments<'a>,
+    config: &'s ComplexityConfig,
+    operation: &'d OperationDefinition,
+    /// Fragments being expanded, guarding against fragment cycles
+    expanding: HashSet<&'d Name>,
+    aliases: usize,
+}
+
+impl<'d> Walker<'_, 'd, '_> {
+    /// Depth and cost of `selections` on `parent`
+    fn walk(&mut self, parent: &Name, selections: &'d [Selection]) -> (usize, u64) {
+        let mut depth = 0;
+        let mut cost: u64 = 0;
+        for selection in selections {
+            let (selection_depth, selection_cost) = match selection {
+                Selection::Field(field) => self.field(parent, field),
+                Selection::InlineFragment(inline) => {
+                    let parent = inline.type_condition.as_ref().unwrap_or(parent);
+                    self.walk(parent, &inline.selection_set)
+                }
+                Selection::FragmentSpread(spread) => {
+                    let Some(fragment) = self.documents.fragments.get(&spread.fragment_name) else {
+                        continue;
+                    };
+                    if !self.expanding.insert(&spread.fragment_name) {
+                        continue;
+                    }
+                    let definition = &fragment.definition;
+                    let result = self.walk(&definition.type_condition, &definition.selection_set);
+                    self.expanding.remove(&spread.fragment_name);
+                    result
+                }
+            };
+            depth = depth.max(selection_depth);
+            cost = cost.saturating_add(selection_cost);
+        }
+        (depth, cost)
+    }
+
+    fn field(&mut self, parent: &Name, field: &'d Field) -> (usize, u64) {
+        if field.alias.is_some() {
+            self.aliases += 1;
+        }
+        if field.name.starts_with("__") {
+            return (0, 0);
+        }
+        let Ok(definition) = self.schema.type_field(parent, &field.name) else {
+            return (0, 0);
+        };
+
+        let (depth, cost) = self.walk(definition.ty.inner_named_type(), &field.selection_set);
+        let weight = self
+            .config
+            .field_weights
+            .get(&format!("{parent}.{}", field.name))
+            .copied()
+            .unwrap_or(1);
+        let size = self.list_size(field).unwrap_or(if definition.ty.is_list() {
+            self.config.default_list_size.unwrap_or(1)
+        } else {
+            1
+        });
+        (depth + 1, weight.saturating_add(size.saturating_mul(cost)))
+    }
+
+    /// Value of the `first` or `last` argument of `field`, when given
+    fn list_size(&self, field: &Field) -> Option<u64> {
+        let argument = field
+            .arguments
+            .iter()
+            .find(|argument| argument.name == "first" || argument.name == "last")?;
+        let value = match argument.value.as_ref() {
+            Value::Variable(variable) => self
+                .operation
+                .variables
+                .iter()
+                .find(|definition| definition.name == *variable)
+                .and_then(|definition| definition.default_value.as_ref())
+                .map(|value| value.as_ref()),
+            value => Some(value),
+        };
+        match value {
+            Some(Value::Int(int)) => int.try_to_i32().ok().map(|n| n.max(0) as u64),
+            _ => Some(self.config.default_list_size.unwrap_or(1)),
+        }
+    }
+}
@@ -0,0 +1,151 @@
+use apollo_compiler::ast::{Selection, Value};
+use apollo_compiler::schema::ExtendedType;
+use apollo_compiler::{Name, Node, Schema};
+
+use super::{Owner, deprecation_reason};
+use crate::diagnostic::{
+    Diagnostic, DiagnosticCategory, DiagnosticLocation, Diagnostics, Severity,
+};
+use crate::documents::CollectedDocuments;
+use crate::source_cache::SourceCache;
+
+/// Report deprecated fields, arguments, input fields and enum values used in
+/// operations and fragments, with `severity`.
+pub fn check_deprecated_usage(
+    schema: &Schema,
+    documents: &CollectedDocuments,
+    cache: &SourceCache,
+    severity: Severity,
+) -> Diagnostics {
+    let mut lint = Lint {
+        schema,
+        cache,
+        severity,
+        diagnostics: Diagnostics::new(),
+    };
+
+    for operation in documents.operations.values() {
+        let definition = &operation.definition;
+        if let Some(root) = schema.root_operation(definition.operation_type) {
+            lint.selections(root, &definition.selection_set, Owner::Operation(operation));
+        }
+    }
+    for fragment in documents.fragments.values() {
+        let definition = &fragment.definition;
+        lint.selections(
+            &definition.type_condition,
+            &definition.selection_set,
+            Owner::Fragment(fragment),
+        );
+    }
+
+    lint.diagnostics
+}
+
+struct Lint<'s> {
+    schema: &'s Schema,
+    cache: &'s SourceCache,
+    severity: Severity,
+    diagnostics: Diagnostics,
+}
+
+impl Lint<'_> {
+    fn selections(&mut self, parent: &Name, selections: &[Selection], owner: Owner) {
+        for selection in selections {
+            match selection {
+                Selection::Field(field) => {
+                    let Ok(definition) = self.schema.type_field(parent, &field.name) else {
+                        continue;
+                    };
+                    if let Some(reason) = deprecation_reason(&definition.directives) {
+                        self.report(
+                            format!("Field '{parent}.{}' is deprecated: {reason}", field.name),
+                            owner.location_of(field.name.location(), self.cache),
+                        );
+                    }
+
+                    let coordinate = format!("{parent}.{}", field.name);
+                    for argument in &field.arguments {
+                        let Some(argument_definition) = definition.argument_by_name(&argument.name)
+                        else {
+                            continue;
+                        };
+                        if let Some(reason) = deprecation_reason(&argument_definition.directives) {
+                            self.report(
+                                format!(
+                                    "Argument '{}' of '{coordinate}' is deprecated: {reason}",
+                                    argument.name
+                                ),
+                                owner.location_of(argument.name.location(), self.cache),
+                            );
+                        }
+                        self.input_value(
+                            argument_definition.ty.inner_named_type(),
+                            &argument.value,
+                            owner,
+                        );
+                    }
+
+                    self.selections(
+                        definition.ty.inner_named_type(),
+                        &field.selection_set,
+                        owner,
+                    );
+                }
+                Selection::InlineFragment(inline) => {
+                    let parent = inline.type_condition.as_ref().unwrap_or(parent);
+                    self.selections(parent, &inline.selection_set, owner);
+                }
+                // Fragments are checked on their own
+                Selection::FragmentSpread(_) => {}
+            }
+        }
+    }
+
+    /// Enum values and input object fields of a literal of type `ty`
+    fn input_value(&mut self, ty: &Name, value: &Node<Value>, owner: Owner) {
+        match (value.as_ref(), self.schema.types.get(ty)) {
+            (Value::List(items), _) => {
+                for item in items {
+                    self.input_value(ty, item, owner);
+                }
+            }
+            (Value::Enum(name), Some(ExtendedType::Enum(enum_type))) => {
+                if let Some(reason) = enum_type
+                    .values
+                    .get(name)
+                    .and_then(|v| deprecation_reason(&v.directives))
+                {
+                    self.report(
+                        format!("Enum value '{ty}.{name}' is deprecated: {reason}"),
+                        owner.location_of(value.location(), self.cache),
+                    );
+                }
+            }
+            (Value::Object(fields), Some(ExtendedType::InputObject(input))) => {
+                for (name, field_value) in fields {
+                    let Some(field) = input.fields.get(name) else {
+                        continue;
+                    };
+                    if let Some(reason) = deprecation_reason(&field.directives) {
+                        self.report(
+                            format!("Input field '{ty}.{name}' is deprecated: {reason}"),
+                            owner.location_of(name.location(), self.cache),
+                        );
+                    }
+                    self.input_value(field.ty.inner_named_type(), field_value, owner);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    fn report(&mut self, message: String, location: Option<DiagnosticLocation>) {
+        let mut diagnostic = Diagnostic::warning(DiagnosticCategory::Document, message);
+        diagnostic.severity = self.severity;
+        if let Some(location) = location {
+            diagnostic = diagnostic.with_location(location);
+        }
+        self.diagnostics.push(diagnostic);
+    }
+}
@@ -0,0 +1,265 @@
+use std::collections::{BTreeMap, HashMap, HashSet};
+
+use apollo_compiler::Name;
+use apollo_compiler::ast::Selection;
+use indexmap::IndexMap;
+
+use crate::diagnostic::{
+    Diagnostic, DiagnosticCategory, DiagnosticLocation, Diagnostics, RelatedLocation, Severity,
+};
+use crate::documents::CollectedDocuments;
+use crate::source_cache::SourceCache;
+
+/// A `...Name` spread inside an operation or fragment
+struct Spread<'d> {
+    /// Fragment being spread
+    target: &'d Name,
+    /// Human readable owner, e.g. "operation 'GetUser'"
+    owner: String,
+    location: Option<DiagnosticLocation>,
+}
+
+/// Report unknown spreads, unused fragments and fragment cycles.
+pub(super) fn check_fragments(
+    documents: &CollectedDocuments,
+    cache: &SourceCache,
+    severity: Severity,
+    diagnostics: &mut Diagnostics,
+) {
+    let mut operation_spreads: Vec<Spread> = Vec::new();
+    for (name, operation) in &documents.operations {
+        let owner = format!("operation '{name}'");
+        collect_spreads(&operation.definition.selection_set, &mut |spread| {
+            operation_spreads.push(Spread {
+                target: &spread.fragment_name,
+                owner: owner.clone(),
+                location: operation.location_of(spread.location(), cache),
+            });
+        });
+    }
+
+    let mut fragment_spreads: IndexMap<&Name, Vec<Spread>> = IndexMap::new();
+    for (name, fragment) in &documents.fragments {
+        let owner = format!("fragment '{name}'");
+        let spreads = fragment_spreads.entry(name).or_default();
+        collect_spreads(&fragment.definition.selection_set, &mut |spread| {
+            spreads.push(Spread {
+                target: &spread.fragment_name,
+                owner: owner.clone(),
+                location: fragment.location_of(spread.location(), cache),
+            });
+        });
+    }
+
+    let all_spreads = || {
+        operation_spreads
+            .iter()
+            .chain(fragment_spreads.values().flatten())
+    };
+
+    // Every spread of a fragment, keyed by fragment name (sorted for stable output)
+    let mut usages: BTreeMap<&Name, Vec<&Spread>> = BTreeMap::new();
+    for spread in all_spreads() {
+        usages.entry(spread.target).or_default().push(spread);
+    }
+
+    let definition_location = |name: &Name| documents.fragments.get(name)?.name_location(cache);
+
+    // Unknown spreads: reported once per name, at the first usage
+    for (name, spreads) in &usages {
+        if documents.fragments.contains_key(*name) {
+            continue;
+        }
+
+        let mut diagnostic = Diagnostic::error(
+            DiagnosticCategory::Document,
+            format!("Unknown fragment '{name}'"),
+        );
+        diagnostic.severity = severity;
+
+        let mut spreads = spreads.iter();
+        if let Some(first) = spreads.next() {
+            diagnostic.message = format!("Unknown fragment '{name}' spread in {}", first.owner);
+            if let Some(location) = &first.location {
+                diagnostic = diagnostic.with_location(location.clone());
+            }
+        }
+
+        diagnostic.related = spreads
+            .filter_map(|spread| {
+                related(format!("also spread in {}", spread.owner), &spread.location)
+            })
+            .collect();
+
+        diagnostics.push(diagnostic);
+    }
+
+    // Unused fragments: not reachable from any operation
+    let mut reachable: HashSet<&Name> = HashSet::new();
+    let mut queue: Vec<&Name> = operation_spreads.iter().map(|s| s.target).collect();
+    while let Some(name) = queue.pop() {
+        if reachable.insert(name)
+            && let Some(spreads) = fragment_spreads.get(name)
+        {
+            queue.extend(spreads.iter().map(|s| s.target));
+        }
+    }
+
+    for name in documents.fragments.keys() {
+        if reachable.contains(name) {
+            continue;
+        }
+
+        let mut diagnostic = Diagnostic::warning(
+            DiagnosticCategory::Document,
+            format!("Fragment '{name}' is not used by any operation"),
+        );
+        if let Some(location) = definition_location(name) {
+            diagnostic = diagnostic.with_location(location);
+        }
+
+        diagnostic.related = usages
+            .get(name)
+            .into_iter()
+            .flatten()
+            .filter_map(|spread| {
+                related(
+                    format!("spread in unused {}", spread.owner),
+                    &spread.location,
+                )
+            })
+            .collect();
+
+        diagnostics.push(diagnostic);
+    }
+
+    // Fragment cycles
+    let graph: IndexMap<&Name, Vec<&Name>> = fragment_spreads
+        .iter()
+        .map(|(name, spreads)| (*name, spreads.iter().map(|s| s.target).collect()))
+        .collect();
+
+    for cycle in find_cycles(&graph) {
+        let path = cycle
+            .iter()
+            .chain(cycle.first())
+            .map(|name| name.as_str())
+            .collect::<Vec<_>>()
+            .join(" -> ");
+
+        let mut diagnostic = Diagnostic::error(
+            DiagnosticCategory::Document,
+            format!("Fragment cycle: {path}"),
+        );
+        diagnostic.severity = severity;
+
+        if let Some(location) = definition_location(cycle[0]) {
+            diagnostic = diagnostic.with_location(location);
+        }
+
+        for (i, from) in cycle.iter().enumerate() {
+            let to = cycle[(i + 1) % cycle.len()];
+
+            if i > 0
+                && let Some(location) = definition_location(from)
+            {
+                diagnostic.related.push(RelatedLocation {
+                    message: format!("fragment '{from}' defined here"),
+                    location,
+                    inline_source: None,
+                });
+            }
+
+            let spread = fragment_spreads[from].iter().find(|s| s.target == to);
+            if let Some(related) =
+                spread.and_then(|s| related(format!("'{from}' spreads '{to}' here"), &s.location))
+            {
+                diagnostic.related.push(related);
+            }
+        }
+
+        diagnostics.push(diagnostic);
+    }
+}
+
+fn related(message: String, location: &Option<DiagnosticLocation>) -> Option<RelatedLocation> {
+    Some(RelatedLocation {
+        message,
+        location: location.clone()?,
+        inline_source: None,
+    })
+}
+
+/// Call `f` for every fragment spread in a selection set, including nested
+/// selections and inline fragments.
+pub(crate) fn collect_spreads<'d>(
+    selections: &'d [Selection],
+    f: &mut impl FnMut(&'d apollo_compiler::Node<apollo_compiler::ast::FragmentSpread>),
+) {
+    for selection in selections {
+        match selection {
+            Selection::Field(field) => collect_spreads(&field.selection_set, f),
+            Selection::InlineFragment(inline) => collect_spreads(&inline.selection_set, f),
+            Selection::FragmentSpread(spread) => f(spread),
+        }
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum Visit {
+    InProgress,
+    Done,
+}
+
+/// Find cycles in the fragment spread graph. Each cycle is reported once,
+/// starting at the fragment where it was first entered.
+fn find_cycles<'d>(graph: &IndexMap<&'d Name, Vec<&'d Name>>) -> Vec<Vec<&'d Name>> {
+    fn visit<'d>(
+        name: &'d Name,
+        graph: &IndexMap<&'d Name, Vec<&'d Name>>,
+        state: &mut HashMap<&'d Name, Visit>,
+        stack: &mut Vec<&'d Name>,
+        seen: &mut HashSet<Vec<&'d Name>>,
+        cycles: &mut Vec<Vec<&'d Name>>,
+    ) {
+        state.insert(name, Visit::InProgress);
+        stack.push(name);
+
+        for &next in graph.get(name).into_iter().flatten() {
+            match state.get(next) {
+                Some(Visit::InProgress) => {
+                    let start = stack.iter().position(|n| *n == next).unwrap_or(0);
+                    let cycle = stack[start..].to_vec();
+
+                    let mut key = cycle.clone();
+                    key.sort();
+                    if seen.insert(key) {
+                        cycles.push(cycle);
+                    }
+                }
+                Some(Visit::Done) => {}
+                // Unknown fragments have no outgoing edges
+                None if graph.contains_key(next) => {
+                    visit(next, graph, state, stack, seen, cycles);
+                }
+                None => {}
+            }
+        }
+
+        stack.pop();
+        state.insert(name, Visit::Done);
+    }
+
+    let mut state = HashMap::new();
+    let mut stack = Vec::new();
+    let mut seen = HashSet::new();
+    let mut cycles = Vec::new();
+
+    for &name in graph.keys() {
+        if !state.contains_key(name) {
+            visit(name, graph, &mut state, &mut stack, &mut seen, &mut cycles);
+        }
+    }
+
+    cycles
+}
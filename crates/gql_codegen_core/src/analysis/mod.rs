@@ -0,0 +1,71 @@
+//! Analysis of collected documents
+//!
+//! Checks that need every operation and fragment at once, as opposed to
+//! per-definition validation against the schema, and reports over all of
+//! them such as field usage and operation complexity, as well as lints such as usage of deprecated
+//! schema members.
+
+mod complexity;
+mod deprecated;
+mod fragments;
+mod usage;
+
+pub use complexity::{ComplexityReport, OperationComplexity, operation_complexity};
+pub use deprecated::check_deprecated_usage;
+pub(crate) use fragments::collect_spreads;
+pub use usage::{FieldCoverage, FieldStats, FieldUsage, field_coverage};
+
+use apollo_compiler::ast::DirectiveList;
+use apollo_compiler::parser::SourceSpan;
+
+use crate::diagnostic::{DiagnosticLocation, Diagnostics, Severity};
+use crate::documents::{CollectedDocuments, ParsedFragment, ParsedOperation};
+use crate::source_cache::SourceCache;
+
+/// Analyze collected documents.
+///
+/// Unknown fragment spreads and fragment cycles are reported with `severity`,
+/// since the generated output would be broken. Unused fragments are always
+/// warnings.
+pub fn analyze_documents(
+    documents: &CollectedDocuments,
+    cache: &SourceCache,
+    severity: Severity,
+) -> Diagnostics {
+    let mut diagnostics = Diagnostics::new();
+    fragments::check_fragments(documents, cache, severity, &mut diagnostics);
+    diagnostics
+}
+
+/// Operation or fragment whose text the spans of its selections are
+/// relative to
+#[derive(Clone, Copy)]
+enum Owner<'d, 'a> {
+    Operation(&'d ParsedOperation<'a>),
+    Fragment(&'d ParsedFragment<'a>),
+}
+
+impl Owner<'_, '_> {
+    fn location_of(
+        &self,
+        span: Option<SourceSpan>,
+        cache: &SourceCache,
+    ) -> Option<DiagnosticLocation> {
+        match self {
+            Owner::Operation(operation) => operation.location_of(span, cache),
+            Owner::Fragment(fragment) => fragment.location_of(span, cache),
+        }
+    }
+}
+
+/// Reason of a `@deprecated` schema member, with the spec default when none
+/// is given
+fn deprecation_reason(directives: &DirectiveList) -> Option<&str> {
+    let deprecated = directives.get("deprecated")?;
+    Some(
+        deprecated
+            .specified_argument_by_name("reason")
+            .and_then(|reason| reason.as_str())
+            .unwrap_or("No longer supported"),
+    )
+}
@@ -0,0 +1,208 @@
+use std::collections::HashSet;
+use std::path::PathBuf;
+
+use apollo_compiler::ast::Selection;
+use apollo_compiler::collections::HashMap;
+use apollo_compiler::schema::{ExtendedType, Implementers};
+use apollo_compiler::{Name, Schema};
+use indexmap::IndexMap;
+use serde::Serialize;
+
+use super::{Owner, deprecation_reason};
+use crate::config::Federation;
+use crate::diagnostic::DiagnosticLocation;
+use crate::documents::CollectedDocuments;
+use crate::federation;
+use crate::source_cache::SourceCache;
+
+/// Which schema fields client operations select
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct FieldCoverage {
+    /// Every object and interface field of the schema, keyed by coordinate
+    /// (`User.email`), in schema order
+    pub fields: IndexMap<String, FieldStats>,
+}
+
+/// Usage of one schema field
+#[derive(Debug, Clone, Default, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FieldStats {
+    /// Reason of a `@deprecated` field
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub deprecation_reason: Option<String>,
+    /// Every selection of the field, once per operation reaching it
+    pub usages: Vec<FieldUsage>,
+}
+
+/// A selection of a field by an operation, directly or through a fragment
+#[derive(Debug, Clone, Serialize)]
+pub struct FieldUsage {
+    pub operation: String,
+    pub location: Option<DiagnosticLocation>,
+}
+
+impl FieldStats {
+    pub fn is_deprecated(&self) -> bool {
+        self.deprecation_reason.is_some()
+    }
+
+    pub fn is_used(&self) -> bool {
+        !self.usages.is_empty()
+    }
+}
+
+impl FieldCoverage {
+    /// Fields no operation selects
+    pub fn unused(&self) -> impl Iterator<Item = (&str, &FieldStats)> {
+        self.iter().filter(|(_, stats)| !stats.is_used())
+    }
+
+    /// Deprecated fields that operations still select
+    pub fn deprecated_used(&self) -> impl Iterator<Item = (&str, &FieldStats)> {
+        self.iter()
+            .filter(|(_, stats)| stats.is_deprecated() && stats.is_used())
+    }
+
+    fn iter(&self) -> impl Iterator<Item = (&str, &FieldStats)> {
+        self.fields
+            .iter()
+            .map(|(name, stats)| (name.as_str(), stats))
+    }
+}
+
+/// Record the schema fields selected by each operation. Fragments are
+/// followed from the operations that spread them, so fragments no operation
+/// uses don't count. A field selected on an interface also counts as a
+/// usage of that field on every implementation. Federation types of a
+/// subgraph schema are left out when `federation` is given.
+pub fn field_coverage(
+    schema: &Schema,
+    documents: &CollectedDocuments,
+    cache: &SourceCache,
+    federation: Option<Federation>,
+) -> FieldCoverage {
+    let mut coverage = FieldCoverage::default();
+    for (type_name, ty) in &schema.types {
+        if ty.is_built_in() || federation::is_internal_type(federation, type_name) {
+            continue;
+        }
+        let fields = match ty {
+            ExtendedType::Object(object) => &object.fields,
+            ExtendedType::Interface(interface) => &interface.fields,
+            _ => continue,
+        };
+        for (field_name, field) in fields {
+            let deprecation_reason = deprecation_reason(&field.directives).map(str::to_string);
+            coverage.fields.insert(
+                format!("{type_name}.{field_name}"),
+                FieldStats {
+                    deprecation_reason,
+                    usages: Vec::new(),
+                },
+            );
+        }
+    }
+
+    let implementers = schema.implementers_map();
+    for (name, operation) in &documents.operations {
+        let Some(root) = schema.root_operation(operation.definition.operation_type) else {
+            continue;
+        };
+
+        let mut walker = Walker {
+            schema,
+            documents,
+            cache,
+            implementers: &implementers,
+            operation: name.as_str(),
+            visited: HashSet::new(),
+            seen: HashSet::new(),
+            coverage: &mut coverage,
+        };
+        walker.walk(
+            root,
+            &operation.definition.selection_set,
+            Owner::Operation(operation),
+        );
+    }
+
+    coverage
+}
+
+/// Field coordinate and the file, line and column of its selection
+type UsageKey = (String, Option<(PathBuf, usize, usize)>);
+
+struct Walker<'s, 'd, 'a> {
+    schema: &'s Schema,
+    documents: &'d CollectedDocuments<'a>,
+    cache: &'s SourceCache,
+    implementers: &'s HashMap<Name, Implementers>,
+    operation: &'s str,
+    /// Fragments already walked for this operation
+    visited: HashSet<&'d Name>,
+    /// Usages already recorded for this operation
+    seen: HashSet<UsageKey>,
+    coverage: &'s mut FieldCoverage,
+}
+
+impl<'d> Walker<'_, 'd, '_> {
+    fn walk(&mut self, parent: &Name, selections: &'d [Selection], owner: Owner<'d, '_>) {
+        for selection in selections {
+            match selection {
+                Selection::Field(field) => {
+                    let location = owner.location_of(field.location(), self.cache);
+                    self.record(parent, &field.name, location);
+
+                    if let Ok(definition) = self.schema.type_field(parent, &field.name) {
+                        let child = definition.ty.inner_named_type();
+                        self.walk(child, &field.selection_set, owner);
+                    }
+                }
+                Selection::InlineFragment(inline) => {
+                    let parent = inline.type_condition.as_ref().unwrap_or(parent);
+                    self.walk(parent, &inline.selection_set, owner);
+                }
+                Selection::FragmentSpread(spread) => {
+                    let Some(fragment) = self.documents.fragments.get(&spread.fragment_name) else {
+                        continue;
+                    };
+                    // Also guards against fragment cycles
+                    if !self.visited.insert(&spread.fragment_name) {
+                        continue;
+                    }
+                    let definition = &fragment.definition;
+                    self.walk(
+                        &definition.type_condition,
+                        &definition.selection_set,
+                        Owner::Fragment(fragment),
+                    );
+                }
+            }
+        }
+    }
+
+    /// Record `parent.field`, and the same field on implementations when
+    /// `parent` is an interface
+    fn record(&mut self, parent: &Name, field: &Name, location: Option<DiagnosticLocation>) {
+        let mut types = vec![parent.clone()];
+        if let Some(implementers) = self.implementers.get(parent) {
+            types.extend(implementers.iter().cloned());
+        }
+
+        for ty in types {
+            let coordinate = format!("{ty}.{field}");
+            let Some(stats) = self.coverage.fields.get_mut(&coordinate) else {
+                continue;
+            };
+            let key = location
+                .as_ref()
+                .map(|l| (l.file.clone(), l.line, l.column));
+            if self.seen.insert((coordinate, key)) {
+                stats.usages.push(FieldUsage {
+                    operation: self.operation.to_string(),
+                    location: location.clone(),
+                });
+            }
+        }
+    }
+}
@@ -63,6 +63,7 @@ mod tests {
             config_hash: 456,
             file_meta: HashMap::new(),
             glob_cache: None,
+            remote_schemas: HashMap::new(),
         };
         assert!(!cache.is_fresh(&data));
     }
@@ -75,6 +76,7 @@ mod tests {
             config_hash: 456,
             file_meta: HashMap::new(),
             glob_cache: None,
+            remote_schemas: HashMap::new(),
         };
         cache.store(data.clone()).unwrap();
         assert!(cache.is_fresh(&data));
@@ -88,6 +90,7 @@ mod tests {
             config_hash: 456,
             file_meta: HashMap::new(),
             glob_cache: None,
+            remote_schemas: HashMap::new(),
         };
         cache.store(data1).unwrap();
 
@@ -96,6 +99,7 @@ mod tests {
             config_hash: 456,
             file_meta: HashMap::new(),
             glob_cache: None,
+            remote_schemas: HashMap::new(),
         };
         assert!(!cache.is_fresh(&data2));
     }
@@ -43,6 +43,7 @@ mod tests {
             config_hash: 456,
             file_meta: HashMap::new(),
             glob_cache: None,
+            remote_schemas: HashMap::new(),
         };
         assert!(!cache.is_fresh(&data));
     }
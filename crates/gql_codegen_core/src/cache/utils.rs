@@ -49,6 +49,9 @@ pub struct CacheData {
     /// Cached glob results for fast file discovery
     #[serde(default)]
     pub glob_cache: Option<GlobCache>,
+    /// SDL of schemas fetched from URLs, used when the server can't be reached
+    #[serde(default)]
+    pub remote_schemas: HashMap<String, String>,
 }
 
 /// Cached glob expansion results
@@ -134,7 +137,8 @@ pub fn compute_hashes_from_cache(
         inputs_hash,
         config_hash,
         file_meta,
-        glob_cache: None, // Set by caller if glob caching is used
+        glob_cache: None,               // Set by caller if glob caching is used
+        remote_schemas: HashMap::new(), // Set by caller when schemas are fetched
     }
 }
 
@@ -152,6 +156,15 @@ pub fn normalize_path(path: &str, base_dir: &Path) -> PathBuf {
 /// Hash config options that affect output (excludes paths)
 pub fn hash_config_options(config: &CodegenConfig) -> u64 {
     let mut hasher = DefaultHasher::new();
+    config.anonymous_operations.hash(&mut hasher);
+    config.federation.hash(&mut hasher);
+    config.schema_validation.hash(&mut hasher);
+    config.document_validation.hash(&mut hasher);
+    config.deprecated_usage.hash(&mut hasher);
+    config.complexity.hash(&mut hasher);
+    config.ignore.hash(&mut hasher);
+    config.document_extensions.hash(&mut hasher);
+    config.pluck_config.hash(&mut hasher);
 
     let mut outputs: Vec<_> = config.outputs.iter().collect();
     outputs.sort_by_key(|(k, _)| *k);
@@ -175,6 +188,11 @@ pub fn hash_config_options(config: &CodegenConfig) -> u64 {
         }
 
         output_config.prelude.hash(&mut hasher);
+        output_config.import_types_from.hash(&mut hasher);
+        output_config.documents.hash(&mut hasher);
+        output_config.include.hash(&mut hasher);
+        output_config.exclude.hash(&mut hasher);
+        output_config.pluck_config.hash(&mut hasher);
     }
 
     hasher.finish()
@@ -5,26 +5,30 @@
 //! - `generate`: Convenience wrapper that handles file I/O
 //! - `generate_cached`: Full caching support with two-phase optimization
 
-use std::collections::{BTreeMap, HashMap};
+use std::collections::{BTreeMap, HashMap, HashSet};
 use std::fs;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 
 use apollo_compiler::Schema;
 use apollo_compiler::validation::Valid;
 
+use crate::analysis::{analyze_documents, check_deprecated_usage, operation_complexity};
 use crate::cache::{
     Cache, MetadataCheckResult, compute_hashes_from_cache, create_glob_cache, is_glob_cache_valid,
 };
-use crate::config::{GeneratorConfig, GeneratorOptions, OutputConfig};
+use crate::config::{Federation, GeneratorConfig, GeneratorOptions, OutputConfig, PluckConfig};
 use crate::diagnostic::{Diagnostic, DiagnosticCategory, Diagnostics};
 use crate::documents::{
-    CollectedDocuments, collect_documents, expand_document_globs, load_sources_from_paths,
+    CollectedDocuments, collect_documents_in, collect_documents_with, expand_document_globs,
+    load_sources_from_paths,
 };
-use crate::extract::ExtractConfig;
-use crate::generators::{GeneratorContext, run_generator};
-use crate::schema::{load_schema_from_contents, resolve_schema_paths};
+use crate::generators::{GeneratorContext, TypeImports, TypesModule, run_generator};
+use crate::remote::{fetch_remote_schema, fetch_remote_schemas, is_url};
+use crate::schema::{expand_schema_paths, extend_schema, load_schema_from_contents_with};
+use crate::selection::select_documents;
 use crate::source_cache::SourceCache;
-use crate::validation::validate_options;
+use crate::validation::{validate_documents, validate_options};
+use crate::walk::normalize_path;
 use crate::{CodegenConfig, Result};
 
 /// Default generators when none are specified in config
@@ -67,6 +71,14 @@ pub struct GenerateInput<'a> {
     pub documents: &'a CollectedDocuments<'a>,
     /// Output configurations
     pub outputs: &'a HashMap<String, OutputConfig>,
+    /// Per-output subsets of `documents`, see [`select_documents`].
+    /// Outputs without an entry use all of `documents`.
+    ///
+    /// [`select_documents`]: crate::select_documents
+    pub output_documents: Option<&'a HashMap<String, CollectedDocuments<'a>>>,
+    /// Federation version the schema was loaded with. Federation types are
+    /// left out of the output.
+    pub federation: Option<Federation>,
 }
 
 /// Pure generation function - NO filesystem access
@@ -83,7 +95,14 @@ pub fn generate_from_input(input: &GenerateInput) -> Result<GenerateResult> {
 
     // Generate each output file
     for (output_path, output_config) in input.outputs {
+        let documents = input
+            .output_documents
+            .and_then(|subsets| subsets.get(output_path))
+            .unwrap_or(input.documents);
+
         let mut content = String::new();
+        let mut body = String::new();
+        let mut imports = TypeImports::new();
 
         // Add prelude if configured
         if let Some(prelude) = &output_config.prelude {
@@ -97,6 +116,26 @@ pub fn generate_from_input(input: &GenerateInput) -> Result<GenerateResult> {
             base_options = merge_options(&base_options, Some(config_options));
         }
 
+        let import_types_from = output_config.import_types_from.as_ref().map(|target| {
+            // Imported names must match how the target output names them
+            let options = match input.outputs.get(target) {
+                Some(target_config) => schema_types_options(target_config),
+                None => {
+                    diagnostics.push(Diagnostic::warning(
+                        DiagnosticCategory::Config,
+                        format!(
+                            "'importTypesFrom' of output '{output_path}' refers to '{target}', which is not a configured output"
+                        ),
+                    ));
+                    base_options.clone()
+                }
+            };
+            (
+                relative_import_path(Path::new(output_path), Path::new(target)),
+                options,
+            )
+        });
+
         // Validate resolved options and collect config warnings
         validate_options(&base_options, &mut diagnostics);
 
@@ -118,12 +157,17 @@ pub fn generate_from_input(input: &GenerateInput) -> Result<GenerateResult> {
 
             let mut ctx = GeneratorContext {
                 schema: input.schema,
-                operations: &input.documents.operations,
-                fragments: &input.documents.fragments,
+                operations: &documents.operations,
+                fragments: &documents.fragments,
                 options: &options,
                 writer: &mut buffer,
                 diagnostics: &mut diagnostics,
                 generators,
+                imports: &mut imports,
+                import_types_from: import_types_from
+                    .as_ref()
+                    .map(|(module, options)| TypesModule { module, options }),
+                federation: input.federation,
             };
 
             let t0 = web_time::Instant::now();
@@ -131,11 +175,19 @@ pub fn generate_from_input(input: &GenerateInput) -> Result<GenerateResult> {
             crate::timing!(format!("  Generator '{}'", generator_name), t0.elapsed());
 
             // Safe: our generators only output valid UTF-8
-            content.push_str(
+            body.push_str(
                 &String::from_utf8(buffer).expect("generator output should be valid UTF-8"),
             );
         }
 
+        // Imports go after the prelude but before any generated code
+        let mut import_buffer = Vec::new();
+        imports.render(&mut import_buffer)?;
+        content.push_str(
+            &String::from_utf8(import_buffer).expect("import output should be valid UTF-8"),
+        );
+        content.push_str(&body);
+
         result.files.push(GeneratedFile {
             path: output_path.clone(),
             content,
@@ -146,6 +198,62 @@ pub fn generate_from_input(input: &GenerateInput) -> Result<GenerateResult> {
     Ok(result)
 }
 
+/// Options the `schema-types` generator of an output runs with
+fn schema_types_options(output_config: &OutputConfig) -> GeneratorOptions {
+    let mut options = GeneratorOptions::default();
+    if let Some(config_options) = &output_config.config {
+        options = merge_options(&options, Some(config_options));
+    }
+    let generator = output_config
+        .generators
+        .as_deref()
+        .and_then(|generators| generators.iter().find(|g| g.is_schema_types()));
+    merge_options(&options, generator.and_then(GeneratorConfig::options))
+}
+
+/// Module specifier for importing `to` from a file at `from`, e.g.
+/// `src/ops.ts` + `src/schema.ts` -> `./schema`.
+///
+/// Both paths are output paths from config, so they are compared lexically
+/// (no filesystem access). The `.ts`/`.tsx`/`.d.ts` extension is dropped as
+/// TypeScript module resolution expects.
+fn relative_import_path(from: &Path, to: &Path) -> String {
+    fn normalized(path: &Path) -> Vec<String> {
+        normalize_path(path)
+            .components()
+            .map(|component| component.as_os_str().to_string_lossy().into_owned())
+            .collect()
+    }
+
+    let from_dir = from.parent().map(normalized).unwrap_or_default();
+    let mut target = normalized(to);
+
+    if let Some(file) = target.last_mut() {
+        for ext in [".d.ts", ".ts", ".tsx", ".mts", ".cts"] {
+            if let Some(stem) = file.strip_suffix(ext) {
+                *file = stem.to_string();
+                break;
+            }
+        }
+    }
+
+    let common = from_dir
+        .iter()
+        .zip(&target)
+        .take_while(|(a, b)| a == b)
+        .count();
+
+    let mut parts: Vec<&str> = vec![".."; from_dir.len() - common];
+    parts.extend(target[common..].iter().map(String::as_str));
+
+    let joined = parts.join("/");
+    if joined.starts_with("..") {
+        joined
+    } else {
+        format!("./{joined}")
+    }
+}
+
 /// Convenience function that handles file I/O
 ///
 /// Reads schema and document files from disk based on config paths.
@@ -153,9 +261,35 @@ pub fn generate_from_input(input: &GenerateInput) -> Result<GenerateResult> {
 pub fn generate(config: &CodegenConfig) -> Result<GenerateResult> {
     // TODO: simplify this
     let base_dir = PathBuf::from(".");
+    let ignore = config.ignore_list();
+
+    let mut schema_diagnostics = Diagnostics::new();
+    let schema = load_config_schema(config, &mut schema_diagnostics)?;
+
+    let document_paths = expand_document_globs(&config.documents, &base_dir, &ignore)?;
+    let output_paths = expand_output_documents(config, &base_dir, &ignore)?;
+    let load_paths = with_output_paths(&document_paths, &output_paths);
+    let mut source_cache = SourceCache::with_capacity(load_paths.len());
+    load_sources_from_paths(&load_paths, &mut source_cache)?;
+
+    let sets = DocumentSets::collect(config, &source_cache, &document_paths, &output_paths);
+    let mut result = sets.generate(config, schema, &source_cache, &mut schema_diagnostics)?;
+    schema_diagnostics.extend(result.diagnostics);
+    result.diagnostics = schema_diagnostics;
+    Ok(result)
+}
 
-    // Build schema from both file paths and pre-resolved SDL content
-    let schema_paths = resolve_schema_paths(&config.schema.as_vec(), Some(&base_dir));
+/// Load the schema of a config: files and globs, URLs and pre-resolved
+/// SDL content. Warnings such as errors downgraded by lenient validation are
+/// added to `warnings`.
+pub fn load_config_schema(
+    config: &CodegenConfig,
+    warnings: &mut Diagnostics,
+) -> Result<Valid<Schema>> {
+    let base_dir = PathBuf::from(".");
+    let ignore = config.ignore_list();
+    let (schema_urls, schema_patterns) = schema_sources(config);
+    let schema_paths = expand_schema_paths(&schema_patterns, &base_dir, &ignore)?;
     let mut schema_files: Vec<(PathBuf, String)> = Vec::new();
 
     for path in &schema_paths {
@@ -168,30 +302,245 @@ pub fn generate(config: &CodegenConfig) -> Result<GenerateResult> {
         schema_files.push((path.clone(), content));
     }
 
+    let remote_config = config.remote_schema.clone().unwrap_or_default();
+    for url in &schema_urls {
+        let sdl = fetch_remote_schema(url, &remote_config)?;
+        schema_files.push((PathBuf::from(url), sdl));
+    }
+
     if let Some(contents) = &config.schema_content {
         for (i, sdl) in contents.iter().enumerate() {
             schema_files.push((PathBuf::from(format!("<schema:{i}>")), sdl.clone()));
         }
     }
 
-    let schema = load_schema_from_contents(&schema_files)?;
+    load_schema_from_contents_with(
+        &schema_files,
+        &config.extract_config(),
+        config.schema_options(),
+        warnings,
+    )
+}
+
+/// Split `schema` entries into URLs and path patterns
+fn schema_sources(config: &CodegenConfig) -> (Vec<&str>, Vec<&str>) {
+    config.schema.as_vec().into_iter().partition(|s| is_url(s))
+}
 
-    let document_paths = expand_document_globs(&config.documents, &base_dir)?;
-    let mut source_cache = SourceCache::with_capacity(document_paths.len());
-    load_sources_from_paths(&document_paths, &mut source_cache)?;
+/// Expand the `documents` globs of outputs that override them, keyed by
+/// output path
+pub fn expand_output_documents(
+    config: &CodegenConfig,
+    base_dir: &Path,
+    ignore: &[String],
+) -> Result<HashMap<String, Vec<PathBuf>>> {
+    config
+        .outputs
+        .iter()
+        .filter_map(|(name, output)| output.documents.as_ref().map(|docs| (name, docs)))
+        .map(|(name, docs)| {
+            expand_document_globs(docs, base_dir, ignore).map(|paths| (name.clone(), paths))
+        })
+        .collect()
+}
 
-    // TODO: Make this configurable per-output
-    let extract_config = ExtractConfig::default();
+/// Top-level document paths followed by any extra paths from outputs
+fn with_output_paths(
+    document_paths: &[PathBuf],
+    output_paths: &HashMap<String, Vec<PathBuf>>,
+) -> Vec<PathBuf> {
+    let mut seen: HashSet<&PathBuf> = document_paths.iter().collect();
+    let mut paths = document_paths.to_vec();
+    let mut extra: Vec<&PathBuf> = output_paths
+        .values()
+        .flatten()
+        .filter(|path| seen.insert(path))
+        .collect();
+    extra.sort();
+    paths.extend(extra.into_iter().cloned());
+    paths
+}
 
-    let documents = collect_documents(&source_cache, &extract_config);
+/// Documents of each group of outputs reading the same files the same way:
+/// the top-level `documents`, and the files of outputs with their own
+/// `documents` or `pluckConfig`. Outputs share a set only when they match
+/// the same files with the same `pluckConfig`, so operations in different
+/// sets may share names, and each set is checked on its own.
+struct DocumentSets<'a> {
+    /// The top-level documents first
+    sets: Vec<CollectedDocuments<'a>>,
+    /// Index into `sets` of outputs with their own `documents` or `pluckConfig`
+    output_sets: HashMap<&'a str, usize>,
+}
 
-    let input = GenerateInput {
-        schema: &schema,
-        documents: &documents,
-        outputs: &config.outputs,
-    };
+impl<'a> DocumentSets<'a> {
+    fn collect(
+        config: &'a CodegenConfig,
+        source_cache: &'a SourceCache,
+        document_paths: &[PathBuf],
+        output_paths: &HashMap<String, Vec<PathBuf>>,
+    ) -> Self {
+        let anonymous = config.anonymous_operations;
+        let extract_config = config.extract_config();
+        // Without per-output documents the cache holds just the top-level ones
+        let top_level = if output_paths.is_empty() {
+            collect_documents_with(source_cache, &extract_config, anonymous)
+        } else {
+            let paths = document_paths.iter().cloned().collect();
+            collect_documents_in(source_cache, &extract_config, anonymous, &paths)
+        };
+
+        let mut sets = vec![top_level];
+        let mut keys: Vec<(Vec<&PathBuf>, Option<&PluckConfig>)> =
+            vec![(sorted_paths(document_paths), None)];
+        let mut output_sets = HashMap::new();
 
-    generate_from_input(&input)
+        let mut outputs: Vec<_> = config
+            .outputs
+            .iter()
+            .filter(|(name, output)| {
+                output_paths.contains_key(*name) || output.pluck_config.is_some()
+            })
+            .collect();
+        outputs.sort_by_key(|(name, _)| *name);
+        for (name, output) in outputs {
+            let paths = output_paths.get(name).map_or(document_paths, Vec::as_slice);
+            let key = (sorted_paths(paths), output.pluck_config.as_ref());
+            let index = match keys.iter().position(|other| *other == key) {
+                Some(i) => i,
+                None => {
+                    let files = paths.iter().cloned().collect();
+                    sets.push(collect_documents_in(
+                        source_cache,
+                        &config.output_extract_config(output),
+                        anonymous,
+                        &files,
+                    ));
+                    keys.push(key);
+                    sets.len() - 1
+                }
+            };
+            output_sets.insert(name.as_str(), index);
+        }
+
+        Self { sets, output_sets }
+    }
+
+    /// Extend the schema with the type-system definitions of all sets, check
+    /// each set and generate every output from its own.
+    fn generate(
+        mut self,
+        config: &CodegenConfig,
+        schema: Valid<Schema>,
+        source_cache: &SourceCache,
+        schema_diagnostics: &mut Diagnostics,
+    ) -> Result<GenerateResult> {
+        // Sets may share files; each definition extends the schema once
+        let mut extensions = CollectedDocuments::default();
+        let mut seen = HashSet::new();
+        for set in &self.sets {
+            for extension in &set.schema_extensions {
+                if seen.insert((extension.source_idx, extension.text.as_ptr())) {
+                    extensions.schema_extensions.push(extension.clone());
+                }
+            }
+        }
+        let schema = extend_schema(
+            schema,
+            &extensions,
+            source_cache,
+            config.schema_options(),
+            schema_diagnostics,
+        )?;
+
+        for set in &mut self.sets {
+            check_documents(config, &schema, set, source_cache)?;
+        }
+
+        let mut output_documents = HashMap::new();
+        for (name, output) in &config.outputs {
+            let index = self.output_sets.get(name.as_str()).copied().unwrap_or(0);
+            if index == 0 && output.include.is_none() && output.exclude.is_none() {
+                continue;
+            }
+
+            let subset = select_documents(
+                &self.sets[index],
+                source_cache,
+                None,
+                output.include.as_ref(),
+                output.exclude.as_ref(),
+            )?;
+            output_documents.insert(name.clone(), subset);
+        }
+
+        let input = GenerateInput {
+            schema: &schema,
+            documents: &self.sets[0],
+            outputs: &config.outputs,
+            output_documents: Some(&output_documents),
+            federation: config.federation,
+        };
+        let mut result = generate_from_input(&input)?;
+        for set in &self.sets[1..] {
+            result.diagnostics.extend(set.diagnostics.clone());
+        }
+        Ok(result)
+    }
+}
+
+fn sorted_paths(paths: &[PathBuf]) -> Vec<&PathBuf> {
+    let mut sorted: Vec<&PathBuf> = paths.iter().collect();
+    sorted.sort();
+    sorted.dedup();
+    sorted
+}
+
+/// Analyze collected documents, validate them against the schema per
+/// `documentValidation` and check them for deprecated usage per
+/// `deprecatedUsage` and against the `complexity` limits.
+///
+/// Fails on collection errors (e.g. disallowed anonymous operations) or when
+/// analysis or validation reports errors; warnings are added to the
+/// collection diagnostics and end up in the generation result.
+fn check_documents(
+    config: &CodegenConfig,
+    schema: &Valid<Schema>,
+    documents: &mut CollectedDocuments,
+    source_cache: &SourceCache,
+) -> Result<()> {
+    if documents.diagnostics.has_errors() {
+        return Err(std::mem::take(&mut documents.diagnostics));
+    }
+
+    let mut diagnostics = Diagnostics::new();
+    if let Some(severity) = config.document_validation.severity() {
+        diagnostics.extend(analyze_documents(documents, source_cache, severity));
+        diagnostics.extend(validate_documents(
+            schema,
+            documents,
+            source_cache,
+            severity,
+        ));
+    }
+    if let Some(severity) = config.deprecated_usage.severity() {
+        diagnostics.extend(check_deprecated_usage(
+            schema,
+            documents,
+            source_cache,
+            severity,
+        ));
+    }
+    if let Some(complexity) = &config.complexity {
+        let report = operation_complexity(schema, documents, source_cache, complexity);
+        diagnostics.extend(report.violations(complexity));
+    }
+    if diagnostics.has_errors() {
+        return Err(diagnostics);
+    }
+
+    documents.diagnostics.extend(diagnostics);
+    Ok(())
 }
 
 /// What `#[serde(skip)]` and `#[serde(default)]` produce for each field —
@@ -216,6 +565,8 @@ fn serde_field_defaults() -> GeneratorOptions {
         naming_convention: None,
         typename_policy: None,
         only_referenced_types: false,
+        registry_documents: false,
+        omit_deprecated: false,
     }
 }
 
@@ -263,6 +614,8 @@ fn merge_options(
         type_name_suffix,
         naming_convention,
         typename_policy,
+        registry_documents,
+        omit_deprecated,
     );
     result
 }
@@ -285,11 +638,23 @@ pub fn generate_cached(
     // TODO: simplify this
     let base_dir = PathBuf::from(".");
 
-    let schema_paths = resolve_schema_paths(&config.schema.as_vec(), Some(&base_dir));
+    let ignore = config.ignore_list();
+    let (schema_urls, schema_patterns) = schema_sources(config);
+    let schema_paths = expand_schema_paths(&schema_patterns, &base_dir, &ignore)?;
 
     // Try to use cached glob results
     let t0 = web_time::Instant::now();
-    let patterns: Vec<&str> = config.documents.as_vec();
+    // The ignore list changes which files match, so it's part of the key
+    let ignore_keys: Vec<String> = ignore
+        .iter()
+        .map(|name| format!("!ignore:{name}"))
+        .collect();
+    let patterns: Vec<&str> = config
+        .documents
+        .as_vec()
+        .into_iter()
+        .chain(ignore_keys.iter().map(String::as_str))
+        .collect();
     let glob_cache = cache.stored().and_then(|c| c.glob_cache.as_ref());
 
     let (document_paths, glob_cache_hit) = match glob_cache {
@@ -303,32 +668,53 @@ pub fn generate_cached(
             (cached.files.clone(), true)
         }
         _ => {
-            let paths = expand_document_globs(&config.documents, &base_dir)?;
+            let paths = expand_document_globs(&config.documents, &base_dir, &ignore)?;
             crate::timing!("Glob expansion", t0.elapsed(), "{} files", paths.len());
             (paths, false)
         }
     };
 
+    // Per-output globs aren't part of the glob cache
+    let output_paths = expand_output_documents(config, &base_dir, &ignore)?;
+    let load_paths = with_output_paths(&document_paths, &output_paths);
+
     let all_paths: Vec<PathBuf> = schema_paths
         .iter()
-        .chain(document_paths.iter())
+        .chain(load_paths.iter())
         // TODO: can we avoid cloning here?
         .cloned()
         .collect();
 
-    // Phase 1: Quick metadata check (no file reads, just stat)
+    // Phase 1: Quick metadata check (no file reads, just stat). Remote
+    // schemas can change without any local file changing, so they always
+    // go through the content check.
+    if schema_urls.is_empty() {
+        let t0 = web_time::Instant::now();
+        let metadata_result = cache.check_metadata(&all_paths);
+        crate::timing!("Cache metadata check", t0.elapsed());
+        if matches!(metadata_result, MetadataCheckResult::AllMatch) {
+            crate::timing!("Total (cache hit - metadata)", start.elapsed());
+            return Ok(GenerateCachedResult::Fresh);
+        }
+    }
+
     let t0 = web_time::Instant::now();
-    let metadata_result = cache.check_metadata(&all_paths);
-    crate::timing!("Cache metadata check", t0.elapsed());
-    if matches!(metadata_result, MetadataCheckResult::AllMatch) {
-        crate::timing!("Total (cache hit - metadata)", start.elapsed());
-        return Ok(GenerateCachedResult::Fresh);
+    let mut remote_diagnostics = Diagnostics::new();
+    let remote_schemas = fetch_remote_schemas(
+        &schema_urls,
+        &config.remote_schema.clone().unwrap_or_default(),
+        cache.stored().map(|c| &c.remote_schemas),
+        &mut remote_diagnostics,
+    )?;
+    if !schema_urls.is_empty() {
+        crate::timing!("Remote schema fetch", t0.elapsed());
     }
 
     // Load schema and documents in parallel
     let t0 = web_time::Instant::now();
-    let doc_paths_len = document_paths.len();
+    let doc_paths_len = load_paths.len();
     let inline_content = config.schema_content.clone();
+    let extract_config = config.extract_config();
 
     let load_schema = || {
         let mut schema_files: Vec<(PathBuf, String)> = schema_paths
@@ -336,6 +722,11 @@ pub fn generate_cached(
             .filter_map(|p| fs::read_to_string(&p).ok().map(|c| (p, c)))
             .collect();
 
+        // Keep the configured order so merged schemas stay stable
+        for url in &schema_urls {
+            schema_files.push((PathBuf::from(url), remote_schemas[*url].clone()));
+        }
+
         // Append pre-resolved SDL content from Node CLI (.ts/.js schemas)
         if let Some(contents) = inline_content {
             for (i, sdl) in contents.into_iter().enumerate() {
@@ -343,16 +734,23 @@ pub fn generate_cached(
             }
         }
 
-        load_schema_from_contents(&schema_files).map(|s| (s, schema_files))
+        let mut diagnostics = Diagnostics::new();
+        load_schema_from_contents_with(
+            &schema_files,
+            &extract_config,
+            config.schema_options(),
+            &mut diagnostics,
+        )
+        .map(|s| (s, schema_files, diagnostics))
     };
 
     let load_sources = || {
         let mut source_cache = SourceCache::with_capacity(doc_paths_len);
-        load_sources_from_paths(&document_paths, &mut source_cache).map(|_| source_cache)
+        load_sources_from_paths(&load_paths, &mut source_cache).map(|_| source_cache)
     };
 
     let (schema_result, docs_result) = rayon::join(load_schema, load_sources);
-    let (schema, schema_files) = schema_result?;
+    let (schema, schema_files, mut schema_diagnostics) = schema_result?;
     let source_cache = docs_result?;
     crate::timing!("Schema + docs parallel load", t0.elapsed());
 
@@ -367,6 +765,7 @@ pub fn generate_cached(
         // Preserve existing glob cache
         computed.glob_cache = cache.stored().and_then(|c| c.glob_cache.clone());
     }
+    computed.remote_schemas = remote_schemas.clone();
     crate::timing!("Hash computation", t0.elapsed());
 
     if cache.is_fresh(&computed) {
@@ -376,25 +775,20 @@ pub fn generate_cached(
         return Ok(GenerateCachedResult::Fresh);
     }
 
-    // TODO: actually use real passed config
     let t0 = web_time::Instant::now();
-    let extract_config = ExtractConfig::default();
-    let documents = collect_documents(&source_cache, &extract_config);
+    let sets = DocumentSets::collect(config, &source_cache, &document_paths, &output_paths);
     crate::timing!(
         "GraphQL extraction",
         t0.elapsed(),
-        "{} ops, {} frags",
-        documents.operations.len(),
-        documents.fragments.len()
+        "{} document sets",
+        sets.sets.len()
     );
 
     let t0 = web_time::Instant::now();
-    let input = GenerateInput {
-        schema: &schema,
-        documents: &documents,
-        outputs: &config.outputs,
-    };
-    let result = generate_from_input(&input)?;
+    let mut result = sets.generate(config, schema, &source_cache, &mut schema_diagnostics)?;
+    remote_diagnostics.extend(schema_diagnostics);
+    remote_diagnostics.extend(result.diagnostics);
+    result.diagnostics = remote_diagnostics;
     crate::timing!("Code generation", t0.elapsed());
 
     // Store cache after successful generation
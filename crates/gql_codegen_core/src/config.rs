@@ -6,12 +6,18 @@ use std::collections::{BTreeMap, HashMap};
 
 // Re-export casing types for convenience
 pub use crate::casing::{NamingCase, NamingConvention, NamingConventionConfig};
+use crate::diagnostic::Severity;
+use crate::extract::{ExtractConfig, ExtractStrategy};
+use crate::schema::SchemaOptions;
+use crate::walk::default_ignore;
 
 /// Main configuration — matches TypeScript `CodegenConfig`
 #[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct CodegenConfig {
-    /// Path to GraphQL schema file(s)
+    /// Paths or globs of GraphQL schema file(s), `!` to exclude. `.json`
+    /// files are read as introspection results, SDL is extracted from
+    /// tagged templates in code files, and `http(s)://` URLs are introspected.
     pub schema: StringOrArray,
 
     /// Glob patterns for documents
@@ -26,13 +32,295 @@ pub struct CodegenConfig {
     #[serde(default)]
     pub schema_content: Option<Vec<String>>,
 
+    /// Request options for `schema` URLs
+    #[serde(default)]
+    pub remote_schema: Option<RemoteSchemaConfig>,
+
+    /// Apollo Federation version of a subgraph schema. Adds the federation
+    /// directive and scalar definitions, allows `extend type` of types
+    /// defined in other subgraphs, and hides federation types from output.
+    #[serde(default)]
+    pub federation: Option<Federation>,
+
+    /// How schema validation errors are handled (default: `strict`)
+    #[serde(default)]
+    pub schema_validation: SchemaValidation,
+
     /// Lifecycle hooks — shell commands run after generation
     #[serde(default)]
     pub hooks: Option<HooksConfig>,
+
+    /// How operations and fragments that don't validate against the schema
+    /// are reported (default: `warning`)
+    #[serde(default)]
+    pub document_validation: DocumentValidation,
+
+    /// How selections of deprecated fields, arguments, input fields and
+    /// enum values in documents are reported (default: `warning`)
+    #[serde(default)]
+    pub deprecated_usage: DeprecatedUsage,
+
+    /// Depth, alias and cost limits for operations. Operations exceeding a
+    /// limit fail generation.
+    #[serde(default)]
+    pub complexity: Option<ComplexityConfig>,
+
+    /// How operations without a name are handled (default: `allow`)
+    #[serde(default)]
+    pub anonymous_operations: AnonymousOperations,
+
+    /// Extraction strategy per document file extension (without the dot),
+    /// merged over the built-in mapping, e.g. `{ "html": "html" }`
+    #[serde(default)]
+    pub document_extensions: BTreeMap<String, ExtractStrategy>,
+
+    /// How GraphQL is found in JavaScript/TypeScript documents. Outputs
+    /// can replace it with their own `pluckConfig`.
+    #[serde(default)]
+    pub pluck_config: Option<PluckConfig>,
+
+    /// File and directory names (or globs on names) skipped when expanding
+    /// document and schema globs, in addition to `.gitignore`d paths
+    /// (default: `node_modules`, `.git`, `target`, `__generated__`)
+    #[serde(default)]
+    pub ignore: Option<Vec<String>>,
+}
+
+/// Request options for schemas loaded from a URL
+#[derive(Debug, Clone, Default, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct RemoteSchemaConfig {
+    /// Request headers. `${VAR}` and `$VAR` in values expand to environment
+    /// variables, e.g. `{ "Authorization": "Bearer ${API_TOKEN}" }`
+    #[serde(default)]
+    pub headers: BTreeMap<String, String>,
+
+    /// HTTP method for the introspection query (default: POST)
+    #[serde(default)]
+    pub method: HttpMethod,
+
+    /// Request timeout in milliseconds (default: 10000)
+    #[serde(default)]
+    pub timeout: Option<u64>,
+}
+
+/// Apollo Federation version, `1` or `2`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize, JsonSchema)]
+#[serde(try_from = "u8", into = "u8")]
+pub enum Federation {
+    V1,
+    V2,
+}
+
+impl TryFrom<u8> for Federation {
+    type Error = String;
+
+    fn try_from(version: u8) -> std::result::Result<Self, Self::Error> {
+        match version {
+            1 => Ok(Self::V1),
+            2 => Ok(Self::V2),
+            _ => Err(format!(
+                "unsupported federation version {version}, expected 1 or 2"
+            )),
+        }
+    }
+}
+
+impl From<Federation> for u8 {
+    fn from(federation: Federation) -> Self {
+        match federation {
+            Federation::V1 => 1,
+            Federation::V2 => 2,
+        }
+    }
+}
+
+/// HTTP method for remote schema requests
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "UPPERCASE")]
+pub enum HttpMethod {
+    Get,
+    #[default]
+    Post,
+}
+
+/// Settings for extracting GraphQL from JavaScript/TypeScript
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct PluckConfig {
+    /// Tag names recognized in addition to `gql` and `graphql`
+    #[serde(default)]
+    pub tags: Vec<String>,
+
+    /// Magic comments recognized in addition to `/* GraphQL */`
+    #[serde(default)]
+    pub magic_comments: Vec<String>,
+
+    /// Identifiers that are tags without being imported, e.g. a global `gql`
+    #[serde(default)]
+    pub global_identifiers: Vec<String>,
+
+    /// Only treat tags as GraphQL when imported from one of these modules
+    /// (e.g. `@apollo/client`, `graphql-tag`). Renamed imports are followed.
+    #[serde(default)]
+    pub modules: Option<Vec<String>>,
+}
+
+/// Limits and cost model for operation complexity analysis
+#[derive(Debug, Clone, Default, Hash, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct ComplexityConfig {
+    /// Maximum nesting of fields, with fragments expanded
+    #[serde(default)]
+    pub max_depth: Option<usize>,
+
+    /// Maximum number of aliased fields, with fragments expanded
+    #[serde(default)]
+    pub max_aliases: Option<usize>,
+
+    /// Maximum cost
+    #[serde(default)]
+    pub max_cost: Option<u64>,
+
+    /// Cost of selecting a field, by coordinate (`Query.search`), for
+    /// fields other than the default weight of 1
+    #[serde(default)]
+    pub field_weights: BTreeMap<String, u64>,
+
+    /// Assumed size of lists without a `first` or `last` argument, or where
+    /// it is a variable without a default (default: 1)
+    #[serde(default)]
+    pub default_list_size: Option<u64>,
+}
+
+impl CodegenConfig {
+    /// Names skipped when expanding globs
+    pub fn ignore_list(&self) -> Vec<String> {
+        match &self.ignore {
+            Some(ignore) => ignore.clone(),
+            None => default_ignore(),
+        }
+    }
+
+    /// Extractor configuration for this project's documents
+    pub fn extract_config(&self) -> ExtractConfig {
+        self.extract_config_with(self.pluck_config.as_ref())
+    }
+
+    /// Extractor configuration for the documents of `output`
+    pub fn output_extract_config(&self, output: &OutputConfig) -> ExtractConfig {
+        self.extract_config_with(output.pluck_config.as_ref().or(self.pluck_config.as_ref()))
+    }
+
+    fn extract_config_with(&self, pluck: Option<&PluckConfig>) -> ExtractConfig {
+        let mut extract_config = ExtractConfig::default();
+        extract_config.extensions.extend(
+            self.document_extensions
+                .iter()
+                .map(|(ext, strategy)| (ext.trim_start_matches('.').to_string(), *strategy)),
+        );
+
+        if let Some(pluck) = pluck {
+            extract_config.tags.extend(pluck.tags.iter().cloned());
+            extract_config
+                .magic_comments
+                .extend(pluck.magic_comments.iter().cloned());
+            extract_config.global_identifiers = pluck.global_identifiers.clone();
+            extract_config.modules = pluck.modules.clone();
+        }
+
+        extract_config
+    }
+
+    /// Options for loading this project's schema
+    pub fn schema_options(&self) -> SchemaOptions {
+        SchemaOptions {
+            federation: self.federation,
+            validation: self.schema_validation,
+        }
+    }
+}
+
+/// Handling of schema validation errors
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum SchemaValidation {
+    /// Any error fails generation
+    #[default]
+    Strict,
+    /// Errors that leave the schema usable, such as an undefined directive
+    /// or type, are reported as warnings and types are generated from what
+    /// could be built, with undefined types rendered as `unknown`. Syntax
+    /// errors still fail.
+    Lenient,
+}
+
+/// Reporting level for document validation against the schema
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum DocumentValidation {
+    /// Invalid documents fail generation
+    Error,
+    /// Invalid documents are reported, generation continues
+    #[default]
+    Warning,
+    /// Skip validation
+    Off,
+}
+
+impl DocumentValidation {
+    /// Severity to report validation failures with, `None` when disabled
+    pub fn severity(self) -> Option<Severity> {
+        match self {
+            DocumentValidation::Error => Some(Severity::Error),
+            DocumentValidation::Warning => Some(Severity::Warning),
+            DocumentValidation::Off => None,
+        }
+    }
+}
+
+/// Reporting level for usage of deprecated schema members in documents
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum DeprecatedUsage {
+    /// Reported, generation continues
+    #[default]
+    Warning,
+    /// Usage fails generation
+    Error,
+    /// Not reported
+    Off,
+}
+
+impl DeprecatedUsage {
+    /// Severity to report deprecated usage with, `None` when disabled
+    pub fn severity(self) -> Option<Severity> {
+        match self {
+            DeprecatedUsage::Warning => Some(Severity::Warning),
+            DeprecatedUsage::Error => Some(Severity::Error),
+            DeprecatedUsage::Off => None,
+        }
+    }
+}
+
+/// Handling of anonymous operations in documents
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub enum AnonymousOperations {
+    /// Named `Unknown_1_`, `Unknown_2_`, ... per document
+    #[default]
+    Allow,
+    /// Anonymous operations fail generation
+    Error,
+    /// Named after their source file, e.g. `user-list.graphql` becomes
+    /// `UserList`, with a `_2`, `_3`, ... suffix for further operations.
+    /// Files sharing a name are told apart by their parent directories:
+    /// `a/index.graphql` and `b/index.graphql` give `Index` and `BIndex`.
+    FileName,
 }
 
 /// Either a single string or array of strings
-#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+#[derive(Debug, Clone, Hash, Serialize, Deserialize, JsonSchema)]
 #[serde(untagged)]
 pub enum StringOrArray {
     Single(String),
@@ -70,6 +358,71 @@ pub struct OutputConfig {
     /// Shared config for all generators in this output
     #[serde(default)]
     pub config: Option<GeneratorOptions>,
+
+    /// Path of another output that generates the schema types. When set and
+    /// this output has no `schema-types` generator, the types it references
+    /// are imported from there (`import type { ... } from './schema'`)
+    /// instead of being duplicated inline.
+    #[serde(default)]
+    pub import_types_from: Option<String>,
+
+    /// Documents for this output, replacing the top-level `documents`.
+    /// They're collected and validated on their own, so operation names only
+    /// need to be unique within them and spread fragments must be among them.
+    #[serde(default)]
+    pub documents: Option<StringOrArray>,
+
+    /// Only generate operations matching this filter
+    #[serde(default)]
+    pub include: Option<DocumentFilter>,
+
+    /// Skip operations matching this filter
+    #[serde(default)]
+    pub exclude: Option<DocumentFilter>,
+
+    /// How GraphQL is found in the JavaScript/TypeScript documents of this
+    /// output, replacing the top-level `pluckConfig`
+    #[serde(default)]
+    pub pluck_config: Option<PluckConfig>,
+}
+
+/// Operation filter for an output. An operation matches when it satisfies
+/// every criterion that is set, and any entry within a criterion.
+#[derive(Debug, Clone, Default, Hash, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct DocumentFilter {
+    /// Operation name globs, e.g. `Admin*`
+    #[serde(default)]
+    pub names: Vec<String>,
+
+    /// Operation types
+    #[serde(default)]
+    pub types: Vec<OperationKind>,
+
+    /// Globs on the path of the file defining the operation
+    #[serde(default)]
+    pub paths: Vec<String>,
+}
+
+/// GraphQL operation type
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum OperationKind {
+    Query,
+    Mutation,
+    Subscription,
+}
+
+impl From<apollo_compiler::ast::OperationType> for OperationKind {
+    fn from(operation_type: apollo_compiler::ast::OperationType) -> Self {
+        use apollo_compiler::ast::OperationType;
+
+        match operation_type {
+            OperationType::Query => Self::Query,
+            OperationType::Mutation => Self::Mutation,
+            OperationType::Subscription => Self::Subscription,
+        }
+    }
 }
 
 /// Generator configuration — either just a name or name with config
@@ -98,6 +451,11 @@ impl GeneratorConfig {
             Self::WithConfig(map) => map.values().next(),
         }
     }
+
+    /// Whether this is the schema types generator, under either of its names
+    pub(crate) fn is_schema_types(&self) -> bool {
+        matches!(self.name(), "schema-types" | "typescript")
+    }
 }
 
 #[derive(Debug, Clone, Default, PartialEq, Eq, Hash, Serialize, Deserialize, JsonSchema)]
@@ -154,33 +512,99 @@ impl AvoidOptionals {
     }
 }
 
-pub struct ScalarConfigNormalized {
-    input: String,
-    output: String,
+/// The TypeScript type a scalar maps to in one direction (input or output).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ScalarMapping<'a> {
+    /// Type expression written into the generated code
+    pub ts_type: &'a str,
+    /// Module the type is imported from, if any
+    pub module: Option<&'a str>,
 }
 
+/// Custom scalar mapping.
+///
+/// Accepts:
+/// - `"Date"` — a plain TypeScript type
+/// - `"dayjs#Dayjs"` — type `Dayjs` imported from module `dayjs`
+/// - `{ "input": "string", "output": "Date" }` — per-direction types
+/// - `{ "module": "dayjs", "export": "Dayjs", "input"?: ..., "output"?: ... }` —
+///   imported type, with optional per-direction exports from the same module
 #[derive(Debug, Clone, Hash, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
 #[serde(untagged)]
 pub enum ScalarConfig {
     Simple(String),
-    Detailed { input: String, output: String },
+    // Must come before `Detailed`: untagged variants are tried in order and
+    // `Detailed` would otherwise swallow the module form
+    Module {
+        module: String,
+        export: String,
+        #[serde(default)]
+        input: Option<String>,
+        #[serde(default)]
+        output: Option<String>,
+    },
+    Detailed {
+        input: String,
+        output: String,
+    },
 }
 
 impl ScalarConfig {
-    pub fn normalize(&self) -> ScalarConfigNormalized {
+    /// Type used where the scalar is an argument, variable or input field
+    pub fn input(&self) -> ScalarMapping<'_> {
+        match self {
+            ScalarConfig::Simple(value) => parse_scalar_type(value),
+            ScalarConfig::Module {
+                module,
+                export,
+                input,
+                ..
+            } => ScalarMapping {
+                ts_type: input.as_deref().unwrap_or(export),
+                module: Some(module),
+            },
+            ScalarConfig::Detailed { input, .. } => ScalarMapping {
+                ts_type: input,
+                module: None,
+            },
+        }
+    }
+
+    /// Type used where the scalar is a result field
+    pub fn output(&self) -> ScalarMapping<'_> {
         match self {
-            ScalarConfig::Simple(s) => ScalarConfigNormalized {
-                input: s.clone(),
-                output: s.clone(),
+            ScalarConfig::Simple(value) => parse_scalar_type(value),
+            ScalarConfig::Module {
+                module,
+                export,
+                output,
+                ..
+            } => ScalarMapping {
+                ts_type: output.as_deref().unwrap_or(export),
+                module: Some(module),
             },
-            ScalarConfig::Detailed { input, output } => ScalarConfigNormalized {
-                input: input.clone(),
-                output: output.clone(),
+            ScalarConfig::Detailed { output, .. } => ScalarMapping {
+                ts_type: output,
+                module: None,
             },
         }
     }
 }
 
+/// Split `module#Export` shorthand; anything else is a plain TS type.
+fn parse_scalar_type(value: &str) -> ScalarMapping<'_> {
+    match value.rsplit_once('#') {
+        Some((module, export)) if !module.is_empty() && !export.is_empty() => ScalarMapping {
+            ts_type: export,
+            module: Some(module),
+        },
+        _ => ScalarMapping {
+            ts_type: value,
+            module: None,
+        },
+    }
+}
+
 /// Generator options — shared config structure for all generators.
 ///
 /// User-configurable fields are exposed via normal serde deserialization.
@@ -237,6 +661,17 @@ pub struct GeneratorOptions {
     #[serde(default)]
     pub only_referenced_types: bool,
 
+    /// Emit a `<Name>Document` constant with the source of each operation
+    /// and its fragments in the `operation-registry` output, and add a
+    /// `document: typeof <Name>Document` entry referencing it
+    #[serde(default)]
+    pub registry_documents: bool,
+
+    /// Drop deprecated fields, arguments, input fields and enum values from
+    /// the schema types output
+    #[serde(default)]
+    pub omit_deprecated: bool,
+
     /// Default type to use for unknown scalars (default: "unknown")
     #[schemars(skip)]
     pub default_scalar_type: Option<String>,
@@ -266,6 +701,8 @@ impl Default for GeneratorOptions {
             future_proof_unions: Some(true),
             immutable_types: Some(true),
             only_referenced_types: false,
+            registry_documents: false,
+            omit_deprecated: false,
             default_scalar_type: Some("unknown".to_string()),
             typename_policy: Some(TypenamePolicy::Always),
             strict_scalars: false,
@@ -362,6 +799,35 @@ mod tests {
         assert_eq!(hooks.after_generate, vec!["biome format --write"]);
     }
 
+    #[test]
+    fn scalar_config_module_shorthand() {
+        let config: ScalarConfig = serde_json::from_str(r#""dayjs#Dayjs""#).unwrap();
+        let expected = ScalarMapping {
+            ts_type: "Dayjs",
+            module: Some("dayjs"),
+        };
+        assert_eq!(config.input(), expected);
+        assert_eq!(config.output(), expected);
+    }
+
+    #[test]
+    fn scalar_config_module_object() {
+        let json = r#"{"module": "./scalars", "export": "DateTime", "input": "DateTimeInput"}"#;
+        let config: ScalarConfig = serde_json::from_str(json).unwrap();
+        assert_eq!(config.input().ts_type, "DateTimeInput");
+        assert_eq!(config.output().ts_type, "DateTime");
+        assert_eq!(config.output().module, Some("./scalars"));
+    }
+
+    #[test]
+    fn scalar_config_detailed_has_no_module() {
+        let json = r#"{"input": "string", "output": "Date"}"#;
+        let config: ScalarConfig = serde_json::from_str(json).unwrap();
+        assert_eq!(config.input().ts_type, "string");
+        assert_eq!(config.output().ts_type, "Date");
+        assert_eq!(config.output().module, None);
+    }
+
     #[test]
     fn codegen_config_with_hooks() {
         let json = r#"{
@@ -0,0 +1,564 @@
+//! Schema comparison for detecting breaking API changes
+//!
+//! Changes are classified like graphql-js `findBreakingChanges`: breaking
+//! changes fail existing operations, dangerous changes may change the
+//! behavior of existing clients (e.g. a new enum value they don't handle),
+//! and safe changes are purely additive.
+
+use std::fmt;
+
+use apollo_compiler::ast::{FieldDefinition, InputValueDefinition, Type};
+use apollo_compiler::collections::IndexMap;
+use apollo_compiler::schema::{Component, ExtendedType, ObjectType};
+use apollo_compiler::validation::Valid;
+use apollo_compiler::{Name, Node, Schema};
+use serde::Serialize;
+
+/// How a change affects existing clients
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ChangeSeverity {
+    /// Existing operations may fail
+    Breaking,
+    /// Existing operations keep working but may see unexpected values
+    Dangerous,
+    /// Additive change
+    Safe,
+}
+
+impl fmt::Display for ChangeSeverity {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            ChangeSeverity::Breaking => "breaking",
+            ChangeSeverity::Dangerous => "dangerous",
+            ChangeSeverity::Safe => "safe",
+        })
+    }
+}
+
+/// What changed
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum ChangeKind {
+    TypeRemoved,
+    TypeAdded,
+    TypeKindChanged,
+    FieldRemoved,
+    FieldAdded,
+    FieldTypeChanged,
+    FieldDeprecated,
+    ArgumentRemoved,
+    ArgumentAdded,
+    ArgumentTypeChanged,
+    ArgumentDefaultChanged,
+    InputFieldRemoved,
+    InputFieldAdded,
+    InputFieldTypeChanged,
+    InputFieldDefaultChanged,
+    EnumValueRemoved,
+    EnumValueAdded,
+    UnionMemberRemoved,
+    UnionMemberAdded,
+    InterfaceRemoved,
+    InterfaceAdded,
+    RootTypeChanged,
+    DirectiveRemoved,
+    DirectiveAdded,
+    DirectiveLocationRemoved,
+    DirectiveRepeatableRemoved,
+}
+
+/// A single difference between two schemas
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct SchemaChange {
+    pub severity: ChangeSeverity,
+    pub kind: ChangeKind,
+    /// Schema coordinate of the changed element, e.g. `User.email` or
+    /// `Query.users(first:)`
+    pub path: String,
+    pub message: String,
+}
+
+/// All differences between two schemas, in schema order
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+pub struct SchemaDiff {
+    pub changes: Vec<SchemaChange>,
+}
+
+impl SchemaDiff {
+    pub fn is_empty(&self) -> bool {
+        self.changes.is_empty()
+    }
+
+    pub fn has_breaking(&self) -> bool {
+        self.changes
+            .iter()
+            .any(|c| c.severity == ChangeSeverity::Breaking)
+    }
+
+    /// Changes with the given severity
+    pub fn with_severity(&self, severity: ChangeSeverity) -> impl Iterator<Item = &SchemaChange> {
+        self.changes.iter().filter(move |c| c.severity == severity)
+    }
+
+    fn push(
+        &mut self,
+        severity: ChangeSeverity,
+        kind: ChangeKind,
+        path: impl Into<String>,
+        message: String,
+    ) {
+        self.changes.push(SchemaChange {
+            severity,
+            kind,
+            path: path.into(),
+            message,
+        });
+    }
+}
+
+/// Compare `old` to `new` and classify each change
+pub fn schema_diff(old: &Valid<Schema>, new: &Valid<Schema>) -> SchemaDiff {
+    use ChangeSeverity::*;
+
+    let mut diff = SchemaDiff::default();
+
+    diff_root_types(old, new, &mut diff);
+
+    for (name, old_ty) in &old.types {
+        if old_ty.is_built_in() {
+            continue;
+        }
+        let Some(new_ty) = new.types.get(name) else {
+            diff.push(
+                Breaking,
+                ChangeKind::TypeRemoved,
+                name.as_str(),
+                format!("Type '{name}' was removed"),
+            );
+            continue;
+        };
+
+        match (old_ty, new_ty) {
+            (ExtendedType::Object(old), ExtendedType::Object(new)) => {
+                diff_fields(name, &old.fields, &new.fields, &mut diff);
+                diff_interfaces(old, new, &mut diff);
+            }
+            (ExtendedType::Interface(old), ExtendedType::Interface(new)) => {
+                diff_fields(name, &old.fields, &new.fields, &mut diff);
+            }
+            (ExtendedType::Union(old), ExtendedType::Union(new)) => {
+                for member in old.members.difference(&new.members) {
+                    diff.push(
+                        Breaking,
+                        ChangeKind::UnionMemberRemoved,
+                        name.as_str(),
+                        format!("Member '{member}' was removed from union '{name}'"),
+                    );
+                }
+                for member in new.members.difference(&old.members) {
+                    diff.push(
+                        Dangerous,
+                        ChangeKind::UnionMemberAdded,
+                        name.as_str(),
+                        format!("Member '{member}' was added to union '{name}'"),
+                    );
+                }
+            }
+            (ExtendedType::Enum(old), ExtendedType::Enum(new)) => {
+                for value in old.values.keys().filter(|v| !new.values.contains_key(*v)) {
+                    diff.push(
+                        Breaking,
+                        ChangeKind::EnumValueRemoved,
+                        format!("{name}.{value}"),
+                        format!("Enum value '{value}' was removed from enum '{name}'"),
+                    );
+                }
+                for value in new.values.keys().filter(|v| !old.values.contains_key(*v)) {
+                    diff.push(
+                        Dangerous,
+                        ChangeKind::EnumValueAdded,
+                        format!("{name}.{value}"),
+                        format!("Enum value '{value}' was added to enum '{name}'"),
+                    );
+                }
+            }
+            (ExtendedType::InputObject(old), ExtendedType::InputObject(new)) => {
+                for (field_name, old_field) in &old.fields {
+                    let path = format!("{name}.{field_name}");
+                    match new.fields.get(field_name) {
+                        Some(new_field) => diff_input_value(
+                            path,
+                            old_field,
+                            new_field,
+                            InputKind::InputField,
+                            &mut diff,
+                        ),
+                        None => diff.push(
+                            Breaking,
+                            ChangeKind::InputFieldRemoved,
+                            path,
+                            format!("Input field '{field_name}' was removed from '{name}'"),
+                        ),
+                    }
+                }
+                for (field_name, new_field) in &new.fields {
+                    if old.fields.contains_key(field_name) {
+                        continue;
+                    }
+                    let (severity, requirement) = added_input_severity(new_field);
+                    diff.push(
+                        severity,
+                        ChangeKind::InputFieldAdded,
+                        format!("{name}.{field_name}"),
+                        format!("{requirement} input field '{field_name}' was added to '{name}'"),
+                    );
+                }
+            }
+            (ExtendedType::Scalar(_), ExtendedType::Scalar(_)) => {}
+            (old, new) => diff.push(
+                Breaking,
+                ChangeKind::TypeKindChanged,
+                name.as_str(),
+                format!(
+                    "'{name}' changed from {} to {}",
+                    kind_name(old),
+                    kind_name(new)
+                ),
+            ),
+        }
+    }
+
+    for (name, new_ty) in &new.types {
+        if !new_ty.is_built_in() && !old.types.contains_key(name) {
+            diff.push(
+                Safe,
+                ChangeKind::TypeAdded,
+                name.as_str(),
+                format!("Type '{name}' was added"),
+            );
+        }
+    }
+
+    diff_directives(old, new, &mut diff);
+
+    diff
+}
+
+fn diff_root_types(old: &Schema, new: &Schema, diff: &mut SchemaDiff) {
+    let roots = [
+        (
+            "query",
+            &old.schema_definition.query,
+            &new.schema_definition.query,
+        ),
+        (
+            "mutation",
+            &old.schema_definition.mutation,
+            &new.schema_definition.mutation,
+        ),
+        (
+            "subscription",
+            &old.schema_definition.subscription,
+            &new.schema_definition.subscription,
+        ),
+    ];
+
+    for (operation, old_root, new_root) in roots {
+        let (Some(old_root), new_root) = (old_root, new_root) else {
+            continue;
+        };
+        if new_root.as_ref().map(|n| &n.name) == Some(&old_root.name) {
+            continue;
+        }
+        let message = match new_root {
+            Some(new_root) => format!(
+                "Root {operation} type changed from '{}' to '{}'",
+                old_root.name, new_root.name
+            ),
+            None => format!("Root {operation} type '{}' was removed", old_root.name),
+        };
+        diff.push(
+            ChangeSeverity::Breaking,
+            ChangeKind::RootTypeChanged,
+            operation,
+            message,
+        );
+    }
+}
+
+type Fields = IndexMap<Name, Component<FieldDefinition>>;
+
+fn diff_fields(type_name: &str, old: &Fields, new: &Fields, diff: &mut SchemaDiff) {
+    for (name, old_field) in old {
+        let path = format!("{type_name}.{name}");
+        let Some(new_field) = new.get(name) else {
+            diff.push(
+                ChangeSeverity::Breaking,
+                ChangeKind::FieldRemoved,
+                path,
+                format!("Field '{name}' was removed from '{type_name}'"),
+            );
+            continue;
+        };
+
+        if old_field.ty != new_field.ty {
+            let severity = if is_safe_output_change(&old_field.ty, &new_field.ty) {
+                ChangeSeverity::Safe
+            } else {
+                ChangeSeverity::Breaking
+            };
+            diff.push(
+                severity,
+                ChangeKind::FieldTypeChanged,
+                path.clone(),
+                format!(
+                    "Field '{path}' changed type from '{}' to '{}'",
+                    old_field.ty, new_field.ty
+                ),
+            );
+        }
+
+        if new_field.directives.get("deprecated").is_some()
+            && old_field.directives.get("deprecated").is_none()
+        {
+            diff.push(
+                ChangeSeverity::Safe,
+                ChangeKind::FieldDeprecated,
+                path.clone(),
+                format!("Field '{path}' was deprecated"),
+            );
+        }
+
+        diff_arguments(&path, &old_field.arguments, &new_field.arguments, diff);
+    }
+
+    for name in new.keys().filter(|name| !old.contains_key(*name)) {
+        diff.push(
+            ChangeSeverity::Safe,
+            ChangeKind::FieldAdded,
+            format!("{type_name}.{name}"),
+            format!("Field '{name}' was added to '{type_name}'"),
+        );
+    }
+}
+
+/// `owner` is a field path like `Query.users` or a directive like `@auth`
+fn diff_arguments(
+    owner: &str,
+    old: &[Node<InputValueDefinition>],
+    new: &[Node<InputValueDefinition>],
+    diff: &mut SchemaDiff,
+) {
+    for old_arg in old {
+        let path = format!("{owner}({}:)", old_arg.name);
+        match new.iter().find(|arg| arg.name == old_arg.name) {
+            Some(new_arg) => diff_input_value(path, old_arg, new_arg, InputKind::Argument, diff),
+            None => diff.push(
+                ChangeSeverity::Breaking,
+                ChangeKind::ArgumentRemoved,
+                path,
+                format!("Argument '{}' was removed from '{owner}'", old_arg.name),
+            ),
+        }
+    }
+
+    for new_arg in new {
+        if old.iter().any(|arg| arg.name == new_arg.name) {
+            continue;
+        }
+        let (severity, requirement) = added_input_severity(new_arg);
+        diff.push(
+            severity,
+            ChangeKind::ArgumentAdded,
+            format!("{owner}({}:)", new_arg.name),
+            format!(
+                "{requirement} argument '{}' was added to '{owner}'",
+                new_arg.name
+            ),
+        );
+    }
+}
+
+#[derive(Clone, Copy)]
+enum InputKind {
+    Argument,
+    InputField,
+}
+
+/// Compare an argument or input field that exists in both schemas
+fn diff_input_value(
+    path: String,
+    old: &InputValueDefinition,
+    new: &InputValueDefinition,
+    kind: InputKind,
+    diff: &mut SchemaDiff,
+) {
+    let (type_changed, default_changed, label) = match kind {
+        InputKind::Argument => (
+            ChangeKind::ArgumentTypeChanged,
+            ChangeKind::ArgumentDefaultChanged,
+            "Argument",
+        ),
+        InputKind::InputField => (
+            ChangeKind::InputFieldTypeChanged,
+            ChangeKind::InputFieldDefaultChanged,
+            "Input field",
+        ),
+    };
+
+    if old.ty != new.ty {
+        let severity = if is_safe_input_change(&old.ty, &new.ty) {
+            ChangeSeverity::Safe
+        } else {
+            ChangeSeverity::Breaking
+        };
+        diff.push(
+            severity,
+            type_changed,
+            path.clone(),
+            format!(
+                "{label} '{path}' changed type from '{}' to '{}'",
+                old.ty, new.ty
+            ),
+        );
+    }
+
+    let old_default = old.default_value.as_ref().map(|v| v.to_string());
+    let new_default = new.default_value.as_ref().map(|v| v.to_string());
+    if old_default.is_some() && old_default != new_default {
+        diff.push(
+            ChangeSeverity::Dangerous,
+            default_changed,
+            path.clone(),
+            format!(
+                "{label} '{path}' default value changed from '{}' to '{}'",
+                old_default.unwrap_or_default(),
+                new_default.as_deref().unwrap_or("none")
+            ),
+        );
+    }
+}
+
+/// Required additions break existing operations, optional ones don't
+fn added_input_severity(value: &InputValueDefinition) -> (ChangeSeverity, &'static str) {
+    if value.is_required() {
+        (ChangeSeverity::Breaking, "Required")
+    } else {
+        (ChangeSeverity::Dangerous, "Optional")
+    }
+}
+
+fn diff_interfaces(old: &ObjectType, new: &ObjectType, diff: &mut SchemaDiff) {
+    let name = &old.name;
+    for interface in old
+        .implements_interfaces
+        .difference(&new.implements_interfaces)
+    {
+        diff.push(
+            ChangeSeverity::Breaking,
+            ChangeKind::InterfaceRemoved,
+            name.as_str(),
+            format!("'{name}' no longer implements interface '{interface}'"),
+        );
+    }
+    for interface in new
+        .implements_interfaces
+        .difference(&old.implements_interfaces)
+    {
+        diff.push(
+            ChangeSeverity::Dangerous,
+            ChangeKind::InterfaceAdded,
+            name.as_str(),
+            format!("'{name}' now implements interface '{interface}'"),
+        );
+    }
+}
+
+fn diff_directives(old: &Schema, new: &Schema, diff: &mut SchemaDiff) {
+    for (name, old_directive) in &old.directive_definitions {
+        if old_directive.is_built_in() {
+            continue;
+        }
+        let path = format!("@{name}");
+        let Some(new_directive) = new.directive_definitions.get(name) else {
+            diff.push(
+                ChangeSeverity::Breaking,
+                ChangeKind::DirectiveRemoved,
+                path.clone(),
+                format!("Directive '{path}' was removed"),
+            );
+            continue;
+        };
+
+        for location in &old_directive.locations {
+            if !new_directive.locations.contains(location) {
+                diff.push(
+                    ChangeSeverity::Breaking,
+                    ChangeKind::DirectiveLocationRemoved,
+                    path.clone(),
+                    format!("Location {location} was removed from directive '{path}'"),
+                );
+            }
+        }
+        if old_directive.repeatable && !new_directive.repeatable {
+            diff.push(
+                ChangeSeverity::Breaking,
+                ChangeKind::DirectiveRepeatableRemoved,
+                path.clone(),
+                format!("Directive '{path}' is no longer repeatable"),
+            );
+        }
+        diff_arguments(
+            &path,
+            &old_directive.arguments,
+            &new_directive.arguments,
+            diff,
+        );
+    }
+
+    for (name, new_directive) in &new.directive_definitions {
+        if !new_directive.is_built_in() && !old.directive_definitions.contains_key(name) {
+            diff.push(
+                ChangeSeverity::Safe,
+                ChangeKind::DirectiveAdded,
+                format!("@{name}"),
+                format!("Directive '@{name}' was added"),
+            );
+        }
+    }
+}
+
+/// Output positions may become stricter: a field that now can't be null
+/// still satisfies clients that handled null
+fn is_safe_output_change(old: &Type, new: &Type) -> bool {
+    match (old, new) {
+        (Type::Named(old), Type::Named(new) | Type::NonNullNamed(new)) => old == new,
+        (Type::NonNullNamed(old), Type::NonNullNamed(new)) => old == new,
+        (Type::List(old), Type::List(new) | Type::NonNullList(new))
+        | (Type::NonNullList(old), Type::NonNullList(new)) => is_safe_output_change(old, new),
+        _ => false,
+    }
+}
+
+/// Input positions may become looser: a non-null argument can accept null
+fn is_safe_input_change(old: &Type, new: &Type) -> bool {
+    match (old, new) {
+        (Type::Named(old) | Type::NonNullNamed(old), Type::Named(new)) => old == new,
+        (Type::NonNullNamed(old), Type::NonNullNamed(new)) => old == new,
+        (Type::List(old) | Type::NonNullList(old), Type::List(new))
+        | (Type::NonNullList(old), Type::NonNullList(new)) => is_safe_input_change(old, new),
+        _ => false,
+    }
+}
+
+fn kind_name(ty: &ExtendedType) -> &'static str {
+    match ty {
+        ExtendedType::Scalar(_) => "a scalar",
+        ExtendedType::Object(_) => "an object type",
+        ExtendedType::Interface(_) => "an interface",
+        ExtendedType::Union(_) => "a union",
+        ExtendedType::Enum(_) => "an enum",
+        ExtendedType::InputObject(_) => "an input object",
+    }
+}
@@ -1,18 +1,25 @@
 //! Zero-copy document discovery and parsing with parallel processing
 
-use std::path::{Path, PathBuf};
+use std::collections::{HashMap, HashSet};
+use std::path::{Component, Path, PathBuf};
 
 use apollo_compiler::Name;
 use apollo_compiler::ast::{Definition, FragmentDefinition};
-use globset::{Glob, GlobSetBuilder};
+use apollo_compiler::diagnostic::ToCliReport;
+use apollo_compiler::parser::SourceSpan;
+use apollo_compiler::validation::DiagnosticList;
 use indexmap::IndexMap;
 use rayon::prelude::*;
 
-use crate::config::StringOrArray;
-use crate::diagnostic::{Diagnostic, DiagnosticCategory, Diagnostics, Severity};
+use crate::casing::NamingCase;
+use crate::config::{AnonymousOperations, StringOrArray};
+use crate::diagnostic::{
+    Diagnostic, DiagnosticCategory, DiagnosticLocation, Diagnostics, RelatedLocation, Severity,
+};
 use crate::error::Result;
-use crate::extract::{self, ExtractConfig, Extracted};
+use crate::extract::{self, ExtractConfig, ExtractStrategy, Extracted};
 use crate::source_cache::SourceCache;
+use crate::walk::{self, default_ignore, normalize_path};
 
 /// A parsed GraphQL operation with metadata (zero-copy text)
 #[derive(Debug, Clone)]
@@ -23,7 +30,10 @@ pub struct ParsedOperation<'a> {
     pub source_idx: usize,
     /// Slice into the source file's GraphQL text
     pub text: &'a str,
-    /// Location in source file
+    /// Span of the definition in its parsed document. Spans of nodes inside
+    /// `definition` are relative to the same document.
+    pub span: Option<SourceSpan>,
+    /// 1-indexed line and column of the definition in its source file
     pub line: usize,
     pub column: usize,
 }
@@ -37,6 +47,24 @@ impl<'a> ParsedOperation<'a> {
             .map(|(p, _)| p)
             .expect("source_idx should be valid — assigned from SourceCache during collection")
     }
+
+    /// Location in the source file of a node within this operation
+    pub fn location_of(
+        &self,
+        span: Option<SourceSpan>,
+        cache: &SourceCache,
+    ) -> Option<DiagnosticLocation> {
+        span_location(cache, self.source_idx, self.text, self.span, span?)
+    }
+
+    /// Location of the operation name, or the whole operation if anonymous
+    pub fn name_location(&self, cache: &SourceCache) -> Option<DiagnosticLocation> {
+        let span = match &self.definition.name {
+            Some(name) => name.location(),
+            None => self.span,
+        };
+        self.location_of(span, cache)
+    }
 }
 
 /// A parsed GraphQL fragment with metadata (zero-copy text)
@@ -48,7 +76,10 @@ pub struct ParsedFragment<'a> {
     pub source_idx: usize,
     /// Slice into the source file's GraphQL text
     pub text: &'a str,
-    /// Location in source file
+    /// Span of the definition in its parsed document. Spans of nodes inside
+    /// `definition` are relative to the same document.
+    pub span: Option<SourceSpan>,
+    /// 1-indexed line and column of the definition in its source file
     pub line: usize,
     pub column: usize,
 }
@@ -61,6 +92,51 @@ impl<'a> ParsedFragment<'a> {
             .map(|(p, _)| p)
             .expect("source_idx should be valid — assigned from SourceCache during collection")
     }
+
+    /// Location in the source file of a node within this fragment
+    pub fn location_of(
+        &self,
+        span: Option<SourceSpan>,
+        cache: &SourceCache,
+    ) -> Option<DiagnosticLocation> {
+        span_location(cache, self.source_idx, self.text, self.span, span?)
+    }
+
+    /// Location of the fragment name
+    pub fn name_location(&self, cache: &SourceCache) -> Option<DiagnosticLocation> {
+        self.location_of(self.definition.name.location(), cache)
+    }
+}
+
+/// A type-system definition or extension found in a document, such as the
+/// `extend type Query { isLoggedIn: Boolean! }` of client-side state
+#[derive(Debug, Clone)]
+pub struct ParsedExtension<'a> {
+    /// The definition AST
+    pub definition: Definition,
+    /// Index into SourceCache
+    pub source_idx: usize,
+    /// Slice into the source file's GraphQL text
+    pub text: &'a str,
+}
+
+/// Resolve a span from a definition's AST to a location in its source file.
+///
+/// AST spans are relative to the parsed document, while `text` is the
+/// definition's slice of the source, so the span is rebased on the
+/// definition's own start.
+fn span_location(
+    cache: &SourceCache,
+    source_idx: usize,
+    text: &str,
+    definition: Option<SourceSpan>,
+    span: SourceSpan,
+) -> Option<DiagnosticLocation> {
+    let definition_start = cache.offset_of(source_idx, text)?;
+    let relative = span.offset().checked_sub(definition?.offset())?;
+    let length = span.end_offset().saturating_sub(span.offset());
+
+    cache.location(source_idx, definition_start + relative, length)
 }
 
 /// Result of collecting all documents
@@ -68,6 +144,11 @@ impl<'a> ParsedFragment<'a> {
 pub struct CollectedDocuments<'a> {
     pub operations: IndexMap<Name, ParsedOperation<'a>>,
     pub fragments: IndexMap<Name, ParsedFragment<'a>>,
+    /// Client-side schema definitions and extensions, in source order.
+    /// These are merged into the schema by
+    /// [`extend_schema`](crate::extend_schema), unless their file is a
+    /// schema source, and are never part of an operation's text.
+    pub schema_extensions: Vec<ParsedExtension<'a>>,
     /// Non-fatal diagnostics encountered during collection
     pub diagnostics: Diagnostics,
 }
@@ -82,12 +163,17 @@ pub fn load_sources(
     cache: &mut SourceCache,
 ) -> Result<()> {
     let base = base_dir.unwrap_or(Path::new("."));
-    let paths = expand_document_globs(patterns, base)?;
+    let ignore = default_ignore();
+    let paths = expand_document_globs(patterns, base, &ignore)?;
     load_sources_from_paths(&paths, cache)
 }
 
 /// Load files from pre-resolved paths
+///
+/// `#import "./Fragment.graphql"` lines in GraphQL files are followed, so
+/// imported files are loaded even when no glob matched them.
 pub fn load_sources_from_paths(paths: &[PathBuf], cache: &mut SourceCache) -> Result<()> {
+    let start = cache.len();
     let contents: Vec<_> = paths
         .par_iter()
         .map(|path| {
@@ -111,103 +197,123 @@ pub fn load_sources_from_paths(paths: &[PathBuf], cache: &mut SourceCache) -> Re
         }
     }
 
-    Ok(())
+    load_imports(cache, start)
 }
 
-/// Expand glob patterns to matching file paths
-///
-/// Handles both relative patterns (resolved against base_dir) and absolute patterns.
-/// Supports negation patterns with `!` prefix:
-/// ```ignore
-/// ["src/**/*.tsx", "!src/__generated__/**", "!**/*.test.tsx"]
-/// ```
-pub fn expand_document_globs(patterns: &StringOrArray, base_dir: &Path) -> Result<Vec<PathBuf>> {
-    use std::collections::HashSet;
+/// Load files imported by GraphQL sources from `start` on, transitively.
+/// Imports resolve relative to the importing file.
+fn load_imports(cache: &mut SourceCache, start: usize) -> Result<()> {
+    // Normalized, so a file reached through different relative paths is
+    // only loaded once
+    let mut known: HashMap<PathBuf, usize> = cache
+        .iter()
+        .map(|(idx, path, _)| (normalize_path(path), idx))
+        .collect();
+    let mut missing: HashMap<PathBuf, String> = HashMap::new();
+    let mut diagnostics = Diagnostics::new();
+
+    let mut idx = start;
+    while idx < cache.len() {
+        let (path, content) = cache.get(idx).expect("index below cache length");
+        let dir = path.parent().unwrap_or(Path::new(""));
+
+        let imports: Vec<_> = if is_graphql_file(path) {
+            import_directives(content)
+                .map(|(offset, import)| (offset, import.len(), normalize_path(&dir.join(import))))
+                .collect()
+        } else {
+            Vec::new()
+        };
 
-    let pattern_strs = patterns.as_vec();
+        for (offset, length, resolved) in imports {
+            if let Some(&imported) = known.get(&resolved) {
+                cache.add_import(idx, imported);
+                continue;
+            }
 
-    let mut include_patterns = Vec::new();
-    let mut exclude_builder = GlobSetBuilder::new();
-    let mut has_excludes = false;
+            let error = match missing.get(&resolved) {
+                Some(error) => error.clone(),
+                None => match std::fs::read_to_string(&resolved) {
+                    Ok(content) => {
+                        let imported = cache.push(resolved.clone(), content);
+                        known.insert(resolved, imported);
+                        cache.add_import(idx, imported);
+                        continue;
+                    }
+                    Err(e) => {
+                        missing.insert(resolved.clone(), e.to_string());
+                        e.to_string()
+                    }
+                },
+            };
 
-    for pattern in &pattern_strs {
-        if let Some(negated) = pattern.strip_prefix('!') {
-            let glob = Glob::new(negated).map_err(|e| {
-                Diagnostics::from(Diagnostic::error(
-                    DiagnosticCategory::Document,
-                    format!("Invalid glob pattern '{}': {}", pattern, e),
-                ))
-            })?;
-            exclude_builder.add(glob);
-            has_excludes = true;
-        } else {
-            include_patterns.push(*pattern);
+            let mut diagnostic = Diagnostic::error(
+                DiagnosticCategory::Document,
+                format!("Cannot import '{}': {error}", resolved.display()),
+            );
+            if let Some(location) = cache.location(idx, offset, length) {
+                diagnostic = diagnostic.with_location(location);
+            }
+            diagnostics.push(diagnostic);
         }
+
+        idx += 1;
     }
 
-    if include_patterns.is_empty() {
-        return Ok(Vec::new());
+    if diagnostics.has_errors() {
+        return Err(diagnostics);
     }
+    Ok(())
+}
 
-    let exclude_set = if has_excludes {
-        Some(exclude_builder.build().map_err(|e| {
-            Diagnostics::from(Diagnostic::error(
-                DiagnosticCategory::Document,
-                format!("Invalid glob pattern '{}': {}", pattern_strs.join(", "), e),
-            ))
-        })?)
-    } else {
-        None
-    };
+fn is_graphql_file(path: &Path) -> bool {
+    matches!(
+        path.extension().and_then(|e| e.to_str()),
+        Some("graphql" | "gql")
+    )
+}
 
-    // Use glob crate which handles extracting walk root from patterns
-    let mut all_paths = HashSet::new();
+/// `#import "path"` directives: byte offset and text of each path
+fn import_directives(content: &str) -> impl Iterator<Item = (usize, &str)> {
+    let mut offset = 0;
 
-    for pattern in include_patterns {
-        // If pattern is relative, join with base_dir
-        let full_pattern = if Path::new(pattern).is_absolute() {
-            pattern.to_string()
-        } else {
-            base_dir.join(pattern).to_string_lossy().to_string()
-        };
+    content.split_inclusive('\n').filter_map(move |line| {
+        let line_start = offset;
+        offset += line.len();
 
-        let glob_paths = glob::glob(&full_pattern).map_err(|e| {
-            Diagnostics::from(Diagnostic::error(
-                DiagnosticCategory::Document,
-                format!("Invalid glob pattern '{}': {}", pattern, e),
-            ))
-        })?;
-
-        for entry in glob_paths {
-            match entry {
-                Ok(path) => {
-                    // Skip ignored directories
-                    if path.components().any(|c| {
-                        matches!(
-                            c.as_os_str().to_str(),
-                            Some("node_modules" | ".git" | "target" | "__generated__")
-                        )
-                    }) {
-                        continue;
-                    }
-
-                    // Apply exclude patterns
-                    if let Some(ref excludes) = exclude_set {
-                        if excludes.is_match(&path) {
-                            continue;
-                        }
-                    }
+        let trimmed = line.trim_start();
+        let rest = trimmed.strip_prefix("#import")?;
+        let quoted = rest.trim_start();
+        let quote = quoted.chars().next().filter(|c| matches!(c, '"' | '\''))?;
+        let import = &quoted[1..1 + quoted[1..].find(quote)?];
 
-                    if path.is_file() {
-                        all_paths.insert(path);
-                    }
-                }
-                Err(_) => continue, // Skip unreadable paths
-            }
-        }
-    }
+        let path_start = line_start + (line.len() - quoted.len()) + 1;
+        Some((path_start, import))
+    })
+}
 
-    Ok(all_paths.into_iter().collect())
+/// Expand glob patterns to matching file paths
+///
+/// Handles both relative patterns (resolved against base_dir) and absolute patterns.
+/// Supports negation patterns with `!` prefix:
+/// ```ignore
+/// ["src/**/*.tsx", "!src/__generated__/**", "!**/*.test.tsx"]
+/// ```
+///
+/// Directories are walked in parallel, honoring `.gitignore` files and
+/// skipping entries named in `ignore` (see [`DEFAULT_IGNORE`]).
+pub fn expand_document_globs(
+    patterns: &StringOrArray,
+    base_dir: &Path,
+    ignore: &[String],
+) -> Result<Vec<PathBuf>> {
+    walk::expand_paths(
+        &patterns.as_vec(),
+        base_dir,
+        ignore,
+        false,
+        DiagnosticCategory::Document,
+    )
 }
 
 /// Intermediate result from parallel extraction
@@ -217,6 +323,9 @@ struct ExtractedDoc<'a> {
     text: &'a str,
     line: usize,
     column: usize,
+    binding: Option<&'a str>,
+    /// Template literal content, see [`ExtractStrategy::is_template`]
+    is_template: bool,
 }
 
 /// Extract and parse all GraphQL documents from loaded sources (parallel)
@@ -224,27 +333,71 @@ pub fn collect_documents<'a>(
     cache: &'a SourceCache,
     extract_config: &ExtractConfig,
 ) -> CollectedDocuments<'a> {
+    collect_documents_with(cache, extract_config, AnonymousOperations::default())
+}
+
+/// [`collect_documents`] with explicit handling of anonymous operations
+pub fn collect_documents_with<'a>(
+    cache: &'a SourceCache,
+    extract_config: &ExtractConfig,
+    anonymous_operations: AnonymousOperations,
+) -> CollectedDocuments<'a> {
+    collect(cache, extract_config, anonymous_operations, None)
+}
+
+/// [`collect_documents_with`] limited to the files in `paths`, such as the
+/// documents of one output, and the files they `#import`
+pub fn collect_documents_in<'a>(
+    cache: &'a SourceCache,
+    extract_config: &ExtractConfig,
+    anonymous_operations: AnonymousOperations,
+    paths: &HashSet<PathBuf>,
+) -> CollectedDocuments<'a> {
+    collect(cache, extract_config, anonymous_operations, Some(paths))
+}
+
+/// Indices of the files in `paths` and of every file they import, directly
+/// or through other imports
+fn with_imports(cache: &SourceCache, paths: &HashSet<PathBuf>) -> HashSet<usize> {
+    let mut pending: Vec<usize> = cache
+        .iter()
+        .filter(|(_, path, _)| paths.contains(*path))
+        .map(|(idx, _, _)| idx)
+        .collect();
+    let mut selected = HashSet::new();
+    while let Some(idx) = pending.pop() {
+        if selected.insert(idx) {
+            pending.extend_from_slice(cache.imports(idx));
+        }
+    }
+    selected
+}
+
+fn collect<'a>(
+    cache: &'a SourceCache,
+    extract_config: &ExtractConfig,
+    anonymous_operations: AnonymousOperations,
+    paths: Option<&HashSet<PathBuf>>,
+) -> CollectedDocuments<'a> {
+    let selected = paths.map(|paths| with_imports(cache, paths));
+
     // Phase 1: Extract GraphQL from all files in parallel
     let extracted: Vec<ExtractedDoc<'a>> = cache
         .iter()
+        .filter(|(idx, _, _)| {
+            selected
+                .as_ref()
+                .is_none_or(|selected| selected.contains(idx))
+        })
         .collect::<Vec<_>>()
         .par_iter()
         .flat_map_iter(|(idx, path, source)| {
-            let ext = path.extension().and_then(|e| e.to_str()).unwrap_or("");
-
-            let docs: Vec<Extracted<'a>> = match ext {
-                "graphql" | "gql" => {
-                    vec![Extracted {
-                        text: source,
-                        line: 1,
-                        column: 1,
-                    }]
-                }
-                "js" | "jsx" | "ts" | "tsx" | "mjs" | "cjs" | "mts" | "cts" => {
-                    extract::extract(source, extract_config)
-                }
-                _ => vec![],
+            let strategy = extract_config.strategy_for(path);
+            let docs: Vec<Extracted<'a>> = match strategy {
+                Some(strategy) => extract::extract_with(strategy, source, extract_config),
+                None => vec![],
             };
+            let is_template = strategy.is_some_and(ExtractStrategy::is_template);
 
             docs.into_iter().map(move |doc| ExtractedDoc {
                 source_idx: *idx,
@@ -252,6 +405,8 @@ pub fn collect_documents<'a>(
                 text: doc.text,
                 line: doc.line,
                 column: doc.column,
+                binding: doc.binding,
+                is_template,
             })
         })
         .collect();
@@ -259,33 +414,98 @@ pub fn collect_documents<'a>(
     // Phase 2: Parse all documents in parallel
     let parsed: Vec<ParseResult<'a>> = extracted
         .par_iter()
-        .map(|doc| parse_document(doc))
+        .map(|doc| parse_document(doc, cache))
         .collect();
 
-    // Phase 3: Merge results (sequential - handles duplicates)
+    // Phase 3: Merge results (sequential - handles naming and duplicates)
     let mut result = CollectedDocuments::default();
-
-    for parse_result in parsed {
+    let mut anonymous_per_file: HashMap<usize, usize> = HashMap::new();
+    // Name each file lends its anonymous operations, unique across files
+    let mut file_names: HashMap<usize, String> = HashMap::new();
+    let mut taken_file_names: HashSet<String> = HashSet::new();
+    // Bindings of documents that define fragments, and interpolations to check against them
+    let mut fragment_bindings: HashSet<&str> = HashSet::new();
+    let mut interpolations: Vec<(usize, &str)> = Vec::new();
+
+    for (doc, parse_result) in extracted.iter().zip(parsed) {
         match parse_result {
             ParseResult::Success {
                 operations,
                 fragments,
+                extensions,
+                diagnostics,
             } => {
+                result.diagnostics.extend(diagnostics);
+                result.schema_extensions.extend(extensions);
+
+                if let Some(binding) = doc.binding
+                    && !fragments.is_empty()
+                {
+                    fragment_bindings.insert(binding);
+                }
+                if doc.is_template {
+                    interpolations.extend(
+                        extract::interpolations(doc.text)
+                            .into_iter()
+                            .map(|range| (doc.source_idx, &doc.text[range])),
+                    );
+                }
+
+                let mut anon_count = 0;
+
                 for (name, op) in operations {
-                    if result.operations.contains_key(&name) {
-                        result.diagnostics.push(Diagnostic::warning(
-                            DiagnosticCategory::Document,
-                            format!("Duplicate operation '{}' (skipped)", name),
+                    let name = match name {
+                        Some(name) => name,
+                        None => match anonymous_operations {
+                            AnonymousOperations::Allow => {
+                                anon_count += 1;
+                                Name::new(&format!("Unknown_{anon_count}_")).expect("valid name")
+                            }
+                            AnonymousOperations::FileName => {
+                                let base = file_names.entry(op.source_idx).or_insert_with(|| {
+                                    let name =
+                                        file_operation_name(op.file_path(cache), &taken_file_names);
+                                    taken_file_names.insert(name.clone());
+                                    name
+                                });
+                                let count = anonymous_per_file.entry(op.source_idx).or_default();
+                                *count += 1;
+                                let name = match *count {
+                                    1 => base.clone(),
+                                    count => format!("{base}_{count}"),
+                                };
+                                Name::new(&name).expect("valid name")
+                            }
+                            AnonymousOperations::Error => {
+                                let mut diagnostic = Diagnostic::error(
+                                    DiagnosticCategory::Document,
+                                    "Anonymous operation; name it or set `anonymousOperations` to `fileName`",
+                                );
+                                if let Some(location) = op.name_location(cache) {
+                                    diagnostic = diagnostic.with_location(location);
+                                }
+                                result.diagnostics.push(diagnostic);
+                                continue;
+                            }
+                        },
+                    };
+
+                    if let Some(first) = result.operations.get(&name) {
+                        result.diagnostics.push(duplicate_diagnostic(
+                            format!("Duplicate operation '{name}' (skipped)"),
+                            op.name_location(cache),
+                            first.name_location(cache),
                         ));
                     } else {
                         result.operations.insert(name, op);
                     }
                 }
                 for (name, frag) in fragments {
-                    if result.fragments.contains_key(&name) {
-                        result.diagnostics.push(Diagnostic::warning(
-                            DiagnosticCategory::Document,
-                            format!("Duplicate fragment '{}' (skipped)", name),
+                    if let Some(first) = result.fragments.get(&name) {
+                        result.diagnostics.push(duplicate_diagnostic(
+                            format!("Duplicate fragment '{name}' (skipped)"),
+                            frag.name_location(cache),
+                            first.name_location(cache),
                         ));
                     } else {
                         result.fragments.insert(name, frag);
@@ -298,46 +518,184 @@ pub fn collect_documents<'a>(
         }
     }
 
+    for (source_idx, interpolation) in interpolations {
+        let expr = interpolation[2..interpolation.len() - 1].trim();
+        if is_fragment_reference(expr, &fragment_bindings, &result.fragments) {
+            continue;
+        }
+
+        let mut diagnostic = Diagnostic::warning(
+            DiagnosticCategory::Document,
+            format!("Cannot map interpolated '{expr}' to a known fragment"),
+        );
+        if let Some(offset) = cache.offset_of(source_idx, interpolation)
+            && let Some(location) = cache.location(source_idx, offset, interpolation.len())
+        {
+            diagnostic = diagnostic.with_location(location);
+        }
+        result.diagnostics.push(diagnostic);
+    }
+
     result
 }
 
+/// Whether an interpolated expression refers to a fragment document: a
+/// binding of a template defining fragments (`USER_FIELDS`, `Foo.fragments.USER_FIELDS`),
+/// a fragment name, or a generated `{Name}FragmentDoc`
+fn is_fragment_reference(
+    expr: &str,
+    fragment_bindings: &HashSet<&str>,
+    fragments: &IndexMap<Name, ParsedFragment>,
+) -> bool {
+    let ident = expr.rsplit('.').next().unwrap_or(expr).trim();
+    let fragment_name = ident.strip_suffix("FragmentDoc").unwrap_or(ident);
+
+    fragment_bindings.contains(ident) || fragments.contains_key(fragment_name)
+}
+
+/// Warning for a skipped duplicate, located at the duplicate and pointing
+/// back at the definition that was kept
+fn duplicate_diagnostic(
+    message: String,
+    duplicate: Option<DiagnosticLocation>,
+    first: Option<DiagnosticLocation>,
+) -> Diagnostic {
+    let mut diagnostic = Diagnostic::warning(DiagnosticCategory::Document, message);
+    if let Some(location) = duplicate {
+        diagnostic = diagnostic.with_location(location);
+    }
+    if let Some(location) = first {
+        diagnostic.related.push(RelatedLocation {
+            message: "first defined here".into(),
+            location,
+            inline_source: None,
+        });
+    }
+    diagnostic
+}
+
+/// Operation name derived from a file path: `user-list.graphql` becomes
+/// `UserList`. When another file already lends that name, parent directories
+/// are prepended until it is unique, so `b/index.graphql` next to
+/// `a/index.graphql` becomes `BIndex`. The second anonymous operation in a
+/// file is suffixed at the call site: `UserList_2`.
+fn file_operation_name(path: &Path, taken: &HashSet<String>) -> String {
+    let stem = path.file_stem().and_then(|s| s.to_str()).unwrap_or("");
+    let mut parents = path
+        .parent()
+        .into_iter()
+        .flat_map(|parent| parent.components().rev())
+        .filter_map(|component| match component {
+            Component::Normal(name) => name.to_str(),
+            _ => None,
+        });
+
+    let mut words = stem.to_string();
+    loop {
+        let name = pascal_identifier(&words);
+        if !taken.contains(&name) {
+            return name;
+        }
+        match parents.next() {
+            Some(parent) => words = format!("{parent}_{words}"),
+            // Identical paths can't occur; the duplicate gets reported
+            None => return name,
+        }
+    }
+}
+
+/// PascalCase GraphQL name from arbitrary path words
+fn pascal_identifier(words: &str) -> String {
+    let words: String = words
+        .chars()
+        .map(|c| if c.is_ascii_alphanumeric() { c } else { '_' })
+        .collect();
+
+    let mut name = NamingCase::PascalCase.apply(&words, true).into_owned();
+    if !name.starts_with(|c: char| c.is_ascii_alphabetic()) {
+        name.insert(0, '_');
+    }
+    name
+}
+
 enum ParseResult<'a> {
     Success {
-        operations: Vec<(Name, ParsedOperation<'a>)>,
+        /// Anonymous operations are named while merging
+        operations: Vec<(Option<Name>, ParsedOperation<'a>)>,
         fragments: Vec<(Name, ParsedFragment<'a>)>,
+        extensions: Vec<ParsedExtension<'a>>,
+        diagnostics: Diagnostics,
     },
     Warning(Diagnostics),
 }
 
-fn parse_document<'a>(doc: &ExtractedDoc<'a>) -> ParseResult<'a> {
-    let document = match apollo_compiler::ast::Document::parse(doc.text, doc.path) {
-        Ok(d) => d,
-        Err(e) => {
-            return ParseResult::Warning(Diagnostics::from_apollo(
-                &e.errors,
-                Severity::Warning,
-                DiagnosticCategory::Document,
-            ));
+fn parse_document<'a>(doc: &ExtractedDoc<'a>, cache: &SourceCache) -> ParseResult<'a> {
+    // `${...}` interpolations are blanked out so offsets into `doc.text` stay valid
+    let interpolations = if doc.is_template {
+        extract::interpolations(doc.text)
+    } else {
+        Vec::new()
+    };
+    let blanked;
+    let graphql = if interpolations.is_empty() {
+        doc.text
+    } else {
+        let mut bytes = doc.text.as_bytes().to_vec();
+        for range in &interpolations {
+            for byte in &mut bytes[range.clone()] {
+                if *byte != b'\n' {
+                    *byte = b' ';
+                }
+            }
         }
+        blanked = String::from_utf8(bytes).expect("interpolations are replaced with ASCII");
+        blanked.as_str()
+    };
+
+    let document = match apollo_compiler::ast::Document::parse(graphql, doc.path) {
+        Ok(d) => d,
+        Err(e) => return ParseResult::Warning(parse_error_diagnostics(&e.errors, doc, cache)),
     };
 
     let mut operations = Vec::new();
     let mut fragments = Vec::new();
-    let mut anon_count = 0;
+    let mut extensions = Vec::new();
+    let mut diagnostics = Diagnostics::new();
+    let mut positions = PositionCursor::new(cache, doc);
+
+    // Interpolations are only supported between definitions, where they
+    // pull in fragment documents; inside a definition the text is incomplete
+    let has_interpolation = |span: Option<SourceSpan>| {
+        span.is_some_and(|span| {
+            interpolations
+                .iter()
+                .any(|range| range.start < span.end_offset() && span.offset() < range.end)
+        })
+    };
+    let interpolation_warning = |kind: &str, name: &str| {
+        Diagnostic::warning(
+            DiagnosticCategory::Document,
+            format!(
+                "{kind} '{name}' in '{}' contains a ${{}} interpolation (skipped)",
+                doc.path.display()
+            ),
+        )
+    };
 
     for definition in document.definitions {
         match definition {
             Definition::OperationDefinition(op) => {
-                let name = match &op.name {
-                    Some(n) => n.clone(),
-                    None => {
-                        anon_count += 1;
-                        Name::new(&format!("Unknown_{anon_count}_")).expect("valid name")
-                    }
-                };
+                let name = op.name.clone();
+
+                if has_interpolation(op.location()) {
+                    let label = name.as_ref().map_or("<anonymous>", |n| n.as_str());
+                    diagnostics.push(interpolation_warning("Operation", label));
+                    continue;
+                }
 
                 // Extract just this operation's text using source location
                 let text = extract_definition_text(doc.text, op.location());
+                let (line, column) = positions.advance_to(op.location());
 
                 operations.push((
                     name,
@@ -345,8 +703,9 @@ fn parse_document<'a>(doc: &ExtractedDoc<'a>) -> ParseResult<'a> {
                         definition: (*op).clone(),
                         source_idx: doc.source_idx,
                         text,
-                        line: doc.line,
-                        column: doc.column,
+                        span: op.location(),
+                        line,
+                        column,
                     },
                 ));
             }
@@ -354,8 +713,14 @@ fn parse_document<'a>(doc: &ExtractedDoc<'a>) -> ParseResult<'a> {
             Definition::FragmentDefinition(frag) => {
                 let name = frag.name.clone();
 
+                if has_interpolation(frag.location()) {
+                    diagnostics.push(interpolation_warning("Fragment", &name));
+                    continue;
+                }
+
                 // Extract just this fragment's text using source location
                 let text = extract_definition_text(doc.text, frag.location());
+                let (line, column) = positions.advance_to(frag.location());
 
                 fragments.push((
                     name,
@@ -363,19 +728,113 @@ fn parse_document<'a>(doc: &ExtractedDoc<'a>) -> ParseResult<'a> {
                         definition: (*frag).clone(),
                         source_idx: doc.source_idx,
                         text,
-                        line: doc.line,
-                        column: doc.column,
+                        span: frag.location(),
+                        line,
+                        column,
                     },
                 ));
             }
 
-            _ => {}
+            definition => {
+                let span = definition.location();
+                if has_interpolation(span) {
+                    let label = definition.name().map_or("<schema>", |n| n.as_str());
+                    diagnostics.push(interpolation_warning("Type", label));
+                    continue;
+                }
+
+                extensions.push(ParsedExtension {
+                    text: extract_definition_text(doc.text, span),
+                    source_idx: doc.source_idx,
+                    definition,
+                });
+            }
         }
     }
 
     ParseResult::Success {
         operations,
         fragments,
+        extensions,
+        diagnostics,
+    }
+}
+
+/// Parse errors of a document, located in the host file. Apollo reports
+/// them relative to the parsed text, which for embedded documents starts
+/// somewhere inside the file.
+fn parse_error_diagnostics(
+    errors: &DiagnosticList,
+    doc: &ExtractedDoc,
+    cache: &SourceCache,
+) -> Diagnostics {
+    let mut diagnostics =
+        Diagnostics::from_apollo(errors, Severity::Warning, DiagnosticCategory::Document);
+    let Some(base) = cache.offset_of(doc.source_idx, doc.text) else {
+        return diagnostics;
+    };
+    let source = cache.get(doc.source_idx).map(|(_, content)| content);
+
+    for (diagnostic, error) in diagnostics.iter_mut().zip(errors.iter()) {
+        let Some(span) = error.error.location() else {
+            continue;
+        };
+        let length = span.end_offset().saturating_sub(span.offset());
+        if let Some(location) = cache.location(doc.source_idx, base + span.offset(), length) {
+            diagnostic.location = Some(location);
+            diagnostic.inline_source = source.map(str::to_string);
+        }
+    }
+    diagnostics
+}
+
+/// Maps definition spans, which are relative to the extracted text, to
+/// line/column in the host file. Definitions come in source order, so
+/// positions are counted incrementally rather than from the file start.
+struct PositionCursor<'a> {
+    source: &'a str,
+    /// Offset of the extracted text in `source`
+    base: usize,
+    offset: usize,
+    line: usize,
+    column: usize,
+    /// Fallback when the text isn't a slice of the cached source
+    doc_position: (usize, usize),
+}
+
+impl<'a> PositionCursor<'a> {
+    fn new(cache: &'a SourceCache, doc: &ExtractedDoc) -> Self {
+        let source = cache.get(doc.source_idx).map_or("", |(_, content)| content);
+        Self {
+            source,
+            base: cache.offset_of(doc.source_idx, doc.text).unwrap_or(0),
+            offset: 0,
+            line: 1,
+            column: 1,
+            doc_position: (doc.line, doc.column),
+        }
+    }
+
+    /// Line and column of a span start, which must not precede earlier spans
+    fn advance_to(&mut self, span: Option<SourceSpan>) -> (usize, usize) {
+        let Some(target) = span.map(|span| self.base + span.offset()) else {
+            return self.doc_position;
+        };
+        let Some(skipped) = self.source.get(self.offset..target) else {
+            return self.doc_position;
+        };
+
+        for c in skipped.chars() {
+            if c == '\n' {
+                self.line += 1;
+                self.column = 1;
+            } else {
+                self.column += 1;
+            }
+        }
+        self.offset = target;
+
+        (self.line, self.column)
     }
 }
 
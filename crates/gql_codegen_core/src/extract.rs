@@ -6,9 +6,22 @@
 //! - `graphql(`...``)`  (function call with template literal)
 //! - `/* GraphQL */`...``
 //!
+//! Non-script files are handled by an [`ExtractStrategy`] chosen per file
+//! extension: `<script>` blocks in Vue/Svelte, Astro frontmatter, fenced
+//! code blocks in Markdown. Results are still slices of the original file,
+//! with positions relative to it.
+//!
 //! Inspired by Relay's approach:
 //! https://github.com/facebook/relay/blob/main/compiler/crates/extract-graphql/src/lib.rs
 
+use std::collections::HashMap;
+use std::path::Path;
+
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use crate::source_cache::line_column;
+
 /// Configuration for the extractor
 #[derive(Debug, Clone)]
 pub struct ExtractConfig {
@@ -16,6 +29,14 @@ pub struct ExtractConfig {
     pub tags: Vec<String>,
     /// Magic comments to look for, case-insensitive (default: ["GraphQL"])
     pub magic_comments: Vec<String>,
+    /// Strategy per file extension, without the leading dot. Files with
+    /// other extensions are skipped.
+    pub extensions: HashMap<String, ExtractStrategy>,
+    /// Identifiers that are always tags, even when `modules` is set
+    pub global_identifiers: Vec<String>,
+    /// When set, `tags` only count when imported from one of these modules
+    /// (or a subpath of one), under whatever local name they were imported as
+    pub modules: Option<Vec<String>>,
 }
 
 impl Default for ExtractConfig {
@@ -23,10 +44,104 @@ impl Default for ExtractConfig {
         Self {
             tags: vec!["gql".into(), "graphql".into()],
             magic_comments: vec!["GraphQL".into()],
+            extensions: ExtractStrategy::default_extensions(),
+            global_identifiers: Vec::new(),
+            modules: None,
         }
     }
 }
 
+impl ExtractConfig {
+    /// Strategy for a file, based on its extension
+    pub fn strategy_for(&self, path: &Path) -> Option<ExtractStrategy> {
+        let ext = path.extension()?.to_str()?;
+        self.extensions.get(ext).copied()
+    }
+
+    /// Identifiers treated as tags in `source`
+    fn tags_in<'s>(&'s self, source: &'s str) -> Vec<&'s str> {
+        let mut tags: Vec<&str> = self.global_identifiers.iter().map(String::as_str).collect();
+
+        match &self.modules {
+            None => tags.extend(self.tags.iter().map(String::as_str)),
+            Some(modules) => {
+                for import in imports(source) {
+                    let allowed = modules.iter().any(|module| {
+                        import.module == module
+                            || import
+                                .module
+                                .strip_prefix(module.as_str())
+                                .is_some_and(|rest| rest.starts_with('/'))
+                    });
+                    if !allowed {
+                        continue;
+                    }
+
+                    tags.extend(import.bindings.into_iter().filter_map(|(imported, local)| {
+                        match imported {
+                            // Default imports: `import gql from 'graphql-tag'`
+                            None => Some(local),
+                            Some(imported) => {
+                                self.tags.iter().any(|tag| tag == imported).then_some(local)
+                            }
+                        }
+                    }));
+                }
+            }
+        }
+
+        tags
+    }
+}
+
+/// How GraphQL documents are found in a file
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum ExtractStrategy {
+    /// The whole file is GraphQL (`.graphql`, `.gql`)
+    Graphql,
+    /// Tagged templates and magic comments in JavaScript/TypeScript
+    Script,
+    /// `<script>` blocks of an HTML-like component (`.vue`, `.svelte`)
+    Html,
+    /// Frontmatter and `<script>` blocks of an Astro component
+    Astro,
+    /// Fenced ```` ```graphql ```` code blocks (`.md`, `.mdx`)
+    Markdown,
+}
+
+impl ExtractStrategy {
+    /// Whether documents are template literal content, where `${}` is an
+    /// interpolation rather than GraphQL text
+    pub(crate) fn is_template(self) -> bool {
+        matches!(self, Self::Script | Self::Html | Self::Astro)
+    }
+
+    /// Built-in extension mapping
+    pub fn default_extensions() -> HashMap<String, ExtractStrategy> {
+        [
+            ("graphql", Self::Graphql),
+            ("gql", Self::Graphql),
+            ("js", Self::Script),
+            ("jsx", Self::Script),
+            ("ts", Self::Script),
+            ("tsx", Self::Script),
+            ("mjs", Self::Script),
+            ("cjs", Self::Script),
+            ("mts", Self::Script),
+            ("cts", Self::Script),
+            ("vue", Self::Html),
+            ("svelte", Self::Html),
+            ("astro", Self::Astro),
+            ("md", Self::Markdown),
+            ("mdx", Self::Markdown),
+        ]
+        .into_iter()
+        .map(|(ext, strategy)| (ext.to_string(), strategy))
+        .collect()
+    }
+}
+
 /// An extracted GraphQL document with source location (zero-copy)
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub struct Extracted<'a> {
@@ -36,6 +151,9 @@ pub struct Extracted<'a> {
     pub line: usize,
     /// 1-indexed column number
     pub column: usize,
+    /// Identifier the template is assigned to, e.g. `USER_FIELDS` in
+    /// `const USER_FIELDS = gql`...``
+    pub binding: Option<&'a str>,
 }
 
 /// Owned version of Extracted for when you need to store results
@@ -44,6 +162,7 @@ pub struct ExtractedOwned {
     pub text: String,
     pub line: usize,
     pub column: usize,
+    pub binding: Option<String>,
 }
 
 impl<'a> Extracted<'a> {
@@ -53,6 +172,7 @@ impl<'a> Extracted<'a> {
             text: self.text.to_string(),
             line: self.line,
             column: self.column,
+            binding: self.binding.map(str::to_string),
         }
     }
 }
@@ -63,10 +183,209 @@ impl<'a> From<Extracted<'a>> for ExtractedOwned {
     }
 }
 
+/// Extract all GraphQL documents from a file using the given strategy (zero-copy).
+pub fn extract_with<'a>(
+    strategy: ExtractStrategy,
+    source: &'a str,
+    config: &ExtractConfig,
+) -> Vec<Extracted<'a>> {
+    match strategy {
+        ExtractStrategy::Graphql => vec![Extracted {
+            text: source,
+            line: 1,
+            column: 1,
+            binding: None,
+        }],
+        ExtractStrategy::Script => extract(source, config),
+        ExtractStrategy::Html => extract_blocks(source, script_blocks(source), config),
+        ExtractStrategy::Astro => {
+            let mut blocks: Vec<_> = astro_frontmatter(source).into_iter().collect();
+            blocks.extend(script_blocks(source));
+            extract_blocks(source, blocks, config)
+        }
+        ExtractStrategy::Markdown => fenced_graphql_blocks(source)
+            .into_iter()
+            .map(|(start, end)| {
+                let (line, column) = line_column(source, start);
+                Extracted {
+                    text: &source[start..end],
+                    line,
+                    column,
+                    binding: None,
+                }
+            })
+            .collect(),
+    }
+}
+
+/// Run the script extractor over byte ranges of `source`, rebasing positions
+/// from each block onto the whole file
+fn extract_blocks<'a>(
+    source: &'a str,
+    blocks: Vec<(usize, usize)>,
+    config: &ExtractConfig,
+) -> Vec<Extracted<'a>> {
+    let mut results = Vec::new();
+
+    for (start, end) in blocks {
+        let (line, column) = line_column(source, start);
+
+        results.extend(
+            extract(&source[start..end], config)
+                .into_iter()
+                .map(|doc| Extracted {
+                    text: doc.text,
+                    column: if doc.line == 1 {
+                        doc.column + column - 1
+                    } else {
+                        doc.column
+                    },
+                    line: doc.line + line - 1,
+                    binding: doc.binding,
+                }),
+        );
+    }
+
+    results
+}
+
+/// Content ranges of `<script ...>...</script>` blocks
+fn script_blocks(source: &str) -> Vec<(usize, usize)> {
+    let bytes = source.as_bytes();
+    let mut blocks = Vec::new();
+    let mut pos = 0;
+
+    while let Some(open) = find_ascii_ci(bytes, b"<script", pos) {
+        let after_name = open + b"<script".len();
+        // `<scripts>` or `<script-foo>` are other elements
+        if !matches!(
+            bytes.get(after_name),
+            Some(b'>' | b' ' | b'\t' | b'\n' | b'\r' | b'/')
+        ) {
+            pos = after_name;
+            continue;
+        }
+
+        let Some(tag_end) = opening_tag_end(bytes, after_name) else {
+            break;
+        };
+        // Self-closing `<script src="..." />` has no content
+        if bytes[tag_end - 2] == b'/' {
+            pos = tag_end;
+            continue;
+        }
+
+        let close = find_ascii_ci(bytes, b"</script", tag_end).unwrap_or(bytes.len());
+        blocks.push((tag_end, close));
+        pos = close;
+    }
+
+    blocks
+}
+
+/// Offset just past the `>` closing an opening tag. Quoted attribute values
+/// may contain `>`, e.g. `generic="T extends Record<K, V>"`.
+fn opening_tag_end(bytes: &[u8], from: usize) -> Option<usize> {
+    let mut quote = None;
+    for (i, &byte) in bytes.iter().enumerate().skip(from) {
+        match (quote, byte) {
+            (Some(q), _) if byte == q => quote = None,
+            (Some(_), _) => {}
+            (None, b'"' | b'\'') => quote = Some(byte),
+            (None, b'>') => return Some(i + 1),
+            (None, _) => {}
+        }
+    }
+    None
+}
+
+/// Content range of Astro frontmatter, fenced by `---` lines at the start of the file
+fn astro_frontmatter(source: &str) -> Option<(usize, usize)> {
+    let leading = source.len() - source.trim_start().len();
+    let rest = source[leading..].strip_prefix("---")?;
+    let start = source.len() - rest.len();
+
+    let mut offset = start;
+    for line in source[start..].split_inclusive('\n') {
+        if offset > start && line.trim_end() == "---" {
+            return Some((start, offset));
+        }
+        offset += line.len();
+    }
+
+    None
+}
+
+/// Content ranges of fenced code blocks tagged `graphql` or `gql`
+fn fenced_graphql_blocks(source: &str) -> Vec<(usize, usize)> {
+    let mut blocks = Vec::new();
+    // (fence char, fence length, content start) of the open block
+    let mut open: Option<(char, usize, usize)> = None;
+    let mut graphql = false;
+    let mut offset = 0;
+
+    for line in source.split_inclusive('\n') {
+        let line_start = offset;
+        offset += line.len();
+
+        let trimmed = line.trim_start_matches(' ');
+        if line.len() - trimmed.len() > 3 {
+            continue;
+        }
+        let Some(fence_char) = trimmed.chars().next().filter(|c| matches!(c, '`' | '~')) else {
+            continue;
+        };
+        let fence_len = trimmed.chars().take_while(|c| *c == fence_char).count();
+        if fence_len < 3 {
+            continue;
+        }
+
+        match open {
+            None => {
+                let info = trimmed[fence_len..].trim();
+                let lang = info.split_whitespace().next().unwrap_or("");
+                graphql = lang.eq_ignore_ascii_case("graphql") || lang.eq_ignore_ascii_case("gql");
+                open = Some((fence_char, fence_len, offset));
+            }
+            Some((open_char, open_len, content_start)) => {
+                let closes = fence_char == open_char
+                    && fence_len >= open_len
+                    && trimmed[fence_len..].trim().is_empty();
+                if closes {
+                    if graphql {
+                        blocks.push((content_start, line_start));
+                    }
+                    open = None;
+                }
+            }
+        }
+    }
+
+    // An unclosed fence runs to the end of the document
+    if let Some((_, _, content_start)) = open
+        && graphql
+    {
+        blocks.push((content_start, source.len()));
+    }
+
+    blocks
+}
+
+/// Find an ASCII needle case-insensitively, starting at `from`
+fn find_ascii_ci(haystack: &[u8], needle: &[u8], from: usize) -> Option<usize> {
+    haystack
+        .get(from..)?
+        .windows(needle.len())
+        .position(|w| w.eq_ignore_ascii_case(needle))
+        .map(|i| from + i)
+}
+
 /// Extract all GraphQL documents from source text (zero-copy).
 pub fn extract<'a>(source: &'a str, config: &ExtractConfig) -> Vec<Extracted<'a>> {
+    let tags = config.tags_in(source);
+
     // Quick bail - if none of our markers exist, skip the file entirely
-    let has_tag = config.tags.iter().any(|tag| source.contains(tag.as_str()));
+    let has_tag = tags.iter().any(|tag| source.contains(tag));
     let has_magic = config.magic_comments.iter().any(|mc| {
         // Case-insensitive check without allocation for common case
         source
@@ -105,6 +424,7 @@ pub fn extract<'a>(source: &'a str, config: &ExtractConfig) -> Vec<Extracted<'a>
                         scanner.advance(); // consume *
                         let comment_start_line = scanner.line;
                         let comment_start_col = scanner.column.saturating_sub(2);
+                        let binding = binding_before(source, scanner.pos - 2);
 
                         if let Some(content) = scanner.read_block_comment() {
                             // Check for magic comment: /* GraphQL */ etc
@@ -124,6 +444,7 @@ pub fn extract<'a>(source: &'a str, config: &ExtractConfig) -> Vec<Extracted<'a>
                                             text,
                                             line: comment_start_line,
                                             column: comment_start_col,
+                                            binding,
                                         });
                                     }
                                 }
@@ -138,10 +459,11 @@ pub fn extract<'a>(source: &'a str, config: &ExtractConfig) -> Vec<Extracted<'a>
             c if c.is_ascii_alphabetic() => {
                 let start_line = scanner.line;
                 let start_col = scanner.column;
+                let start_pos = scanner.pos;
                 let ident = scanner.read_identifier();
 
                 // Check if this identifier is one of our tags
-                if config.tags.iter().any(|tag| tag == ident) {
+                if tags.contains(&ident) {
                     scanner.skip_whitespace();
                     let next = scanner.peek();
 
@@ -153,6 +475,7 @@ pub fn extract<'a>(source: &'a str, config: &ExtractConfig) -> Vec<Extracted<'a>
                                 text,
                                 line: start_line,
                                 column: start_col,
+                                binding: binding_before(source, start_pos),
                             });
                         }
                     } else if next == '(' {
@@ -166,6 +489,7 @@ pub fn extract<'a>(source: &'a str, config: &ExtractConfig) -> Vec<Extracted<'a>
                                     text,
                                     line: start_line,
                                     column: start_col,
+                                    binding: binding_before(source, start_pos),
                                 });
                                 // Skip past closing paren
                                 scanner.skip_whitespace();
@@ -193,6 +517,147 @@ pub fn extract<'a>(source: &'a str, config: &ExtractConfig) -> Vec<Extracted<'a>
     results
 }
 
+/// A static ES module import
+struct Import<'a> {
+    module: &'a str,
+    /// `(imported name, local name)`, imported name `None` for default imports
+    bindings: Vec<(Option<&'a str>, &'a str)>,
+}
+
+/// Static `import ... from '...'` declarations in `source`. Type-only and
+/// namespace imports bind no tags and are skipped.
+fn imports(source: &str) -> Vec<Import<'_>> {
+    let mut imports = Vec::new();
+    let mut rest = source;
+
+    while let Some(idx) = rest.find("import") {
+        let at_statement_start = rest[..idx]
+            .chars()
+            .next_back()
+            .is_none_or(|c| c.is_whitespace() || c == ';' || c == '}');
+        let after = &rest[idx + "import".len()..];
+        rest = after;
+
+        if !at_statement_start || !after.starts_with(|c: char| c.is_whitespace() || c == '{') {
+            continue;
+        }
+
+        let Some(from) = find_from_keyword(after) else {
+            break;
+        };
+        let clause = after[..from].trim();
+        let specifier = after[from + "from".len()..].trim_start();
+        let Some(quote) = specifier.chars().next().filter(|c| matches!(c, '\'' | '"')) else {
+            continue;
+        };
+        let Some(end) = specifier[1..].find(quote) else {
+            continue;
+        };
+        let module = &specifier[1..1 + end];
+
+        if clause.starts_with("type ") || clause.contains(';') {
+            continue;
+        }
+
+        let mut bindings = Vec::new();
+        let (default, named) = match clause.find('{') {
+            Some(open) => (
+                clause[..open].trim().trim_end_matches(','),
+                clause[open + 1..].split('}').next().unwrap_or(""),
+            ),
+            None => (clause, ""),
+        };
+
+        let default = default.trim();
+        if !default.is_empty() && !default.starts_with('*') {
+            bindings.push((None, default));
+        }
+
+        for specifier in named.split(',') {
+            let specifier = specifier.trim();
+            if specifier.is_empty() || specifier.starts_with("type ") {
+                continue;
+            }
+            match specifier.split_once(" as ") {
+                Some((imported, local)) => bindings.push((Some(imported.trim()), local.trim())),
+                None => bindings.push((Some(specifier), specifier)),
+            }
+        }
+
+        imports.push(Import { module, bindings });
+    }
+
+    imports
+}
+
+/// Offset of the `from` keyword of an import clause
+fn find_from_keyword(s: &str) -> Option<usize> {
+    let mut offset = 0;
+    while let Some(idx) = s[offset..].find("from") {
+        let at = offset + idx;
+        let before = s[..at].chars().next_back();
+        let after = s[at + "from".len()..].chars().next();
+        if before.is_some_and(|c| c.is_whitespace() || c == '}')
+            && after.is_some_and(|c| c.is_whitespace() || c == '\'' || c == '"')
+        {
+            return Some(at);
+        }
+        offset = at + "from".len();
+    }
+    None
+}
+
+/// Identifier assigned the expression starting at `pos`, for
+/// `const Name = ...` and `const Name: Type = ...`
+fn binding_before(source: &str, pos: usize) -> Option<&str> {
+    let before = source[..pos].trim_end().strip_suffix('=')?;
+    // `==`, `!=`, `<=`, `>=` are comparisons
+    if before.ends_with(['=', '!', '<', '>']) {
+        return None;
+    }
+
+    let mut ident = trailing_identifier(before.trim_end())?;
+    let rest = before.trim_end()[..before.trim_end().len() - ident.len()].trim_end();
+    if let Some(annotated) = rest.strip_suffix(':') {
+        ident = trailing_identifier(annotated.trim_end())?;
+    }
+
+    Some(ident)
+}
+
+fn trailing_identifier(s: &str) -> Option<&str> {
+    let start = s
+        .rfind(|c: char| !(c.is_ascii_alphanumeric() || c == '_' || c == '$'))
+        .map_or(0, |i| i + 1);
+    let ident = &s[start..];
+    ident
+        .starts_with(|c: char| c.is_ascii_alphabetic() || c == '_' || c == '$')
+        .then_some(ident)
+}
+
+/// Byte ranges of `${...}` interpolations in template literal content
+pub(crate) fn interpolations(text: &str) -> Vec<std::ops::Range<usize>> {
+    let mut ranges = Vec::new();
+    let mut scanner = Scanner::new(text);
+
+    while !scanner.is_eof() {
+        match scanner.advance() {
+            '\\' => {
+                scanner.advance();
+            }
+            '$' if scanner.peek() == '{' => {
+                let start = scanner.pos - 1;
+                scanner.advance();
+                scanner.skip_interpolation();
+                ranges.push(start..scanner.pos);
+            }
+            _ => {}
+        }
+    }
+
+    ranges
+}
+
 /// A single-pass character scanner with position tracking (zero-copy)
 struct Scanner<'a> {
     source: &'a str,
@@ -533,6 +998,7 @@ mod tests {
         let config = ExtractConfig {
             tags: vec!["sql".into(), "myGql".into()],
             magic_comments: vec!["GraphQL".into()],
+            ..Default::default()
         };
 
         let source = r#"
@@ -598,6 +1064,7 @@ mod tests {
         let config = ExtractConfig {
             tags: vec![],
             magic_comments: vec!["GraphQL".into(), "GQL".into()],
+            ..Default::default()
         };
 
         let source = r#"
@@ -0,0 +1,86 @@
+//! Apollo Federation subgraph support
+//!
+//! Subgraph SDL uses federation directives without defining them. When
+//! `federation` is configured, the definitions for that version are added
+//! before validation, unless the schema already defines them.
+
+use apollo_compiler::Schema;
+
+use crate::config::Federation;
+
+const FEDERATION_V1: &str = r#"
+scalar _Any
+scalar _FieldSet
+
+directive @key(fields: _FieldSet!) repeatable on OBJECT | INTERFACE
+directive @external on FIELD_DEFINITION | OBJECT
+directive @requires(fields: _FieldSet!) on FIELD_DEFINITION
+directive @provides(fields: _FieldSet!) on FIELD_DEFINITION
+directive @extends on OBJECT | INTERFACE
+"#;
+
+const FEDERATION_V2: &str = r#"
+scalar _Any
+scalar FieldSet
+scalar link__Import
+scalar federation__Scope
+scalar federation__Policy
+
+enum link__Purpose {
+  SECURITY
+  EXECUTION
+}
+
+directive @link(url: String!, as: String, for: link__Purpose, import: [link__Import]) repeatable on SCHEMA
+directive @key(fields: FieldSet!, resolvable: Boolean = true) repeatable on OBJECT | INTERFACE
+directive @requires(fields: FieldSet!) on FIELD_DEFINITION
+directive @provides(fields: FieldSet!) on FIELD_DEFINITION
+directive @external(reason: String) on OBJECT | FIELD_DEFINITION
+directive @extends on OBJECT | INTERFACE
+directive @shareable repeatable on OBJECT | FIELD_DEFINITION
+directive @inaccessible on FIELD_DEFINITION | OBJECT | INTERFACE | UNION | ARGUMENT_DEFINITION | SCALAR | ENUM | ENUM_VALUE | INPUT_OBJECT | INPUT_FIELD_DEFINITION
+directive @override(from: String!, label: String) on FIELD_DEFINITION
+directive @tag(name: String!) repeatable on FIELD_DEFINITION | OBJECT | INTERFACE | UNION | ARGUMENT_DEFINITION | SCALAR | ENUM | ENUM_VALUE | INPUT_OBJECT | INPUT_FIELD_DEFINITION
+directive @composeDirective(name: String!) repeatable on SCHEMA
+directive @interfaceObject on OBJECT
+directive @authenticated on FIELD_DEFINITION | OBJECT | INTERFACE | SCALAR | ENUM
+directive @requiresScopes(scopes: [[federation__Scope!]!]!) on FIELD_DEFINITION | OBJECT | INTERFACE | SCALAR | ENUM
+directive @policy(policies: [[federation__Policy!]!]!) on FIELD_DEFINITION | OBJECT | INTERFACE | SCALAR | ENUM
+directive @context(name: String!) repeatable on INTERFACE | OBJECT | UNION
+"#;
+
+/// Types that only exist to support federation
+const INTERNAL_TYPES: [&str; 5] = ["_Any", "_FieldSet", "FieldSet", "_Service", "_Entity"];
+
+/// Add the federation definitions missing from `schema`
+pub(crate) fn add_definitions(schema: &mut Schema, federation: Federation) {
+    let sdl = match federation {
+        Federation::V1 => FEDERATION_V1,
+        Federation::V2 => FEDERATION_V2,
+    };
+    let definitions = Schema::builder()
+        .parse(sdl, "federation.graphql")
+        .build()
+        .expect("federation definitions should be valid");
+
+    for (name, ty) in definitions.types {
+        if !ty.is_built_in() {
+            schema.types.entry(name).or_insert(ty);
+        }
+    }
+    for (name, directive) in definitions.directive_definitions {
+        schema
+            .directive_definitions
+            .entry(name)
+            .or_insert(directive);
+    }
+}
+
+/// Whether `name` is a federation type that shouldn't be generated. Only
+/// subgraphs, schemas loaded with `federation` configured, have them.
+pub(crate) fn is_internal_type(federation: Option<Federation>, name: &str) -> bool {
+    federation.is_some()
+        && (INTERNAL_TYPES.contains(&name)
+            || name.starts_with("link__")
+            || name.starts_with("federation__"))
+}
@@ -1,11 +1,13 @@
 use std::borrow::Cow;
 
 use apollo_compiler::Name;
-use apollo_compiler::ast::{FieldDefinition, InputValueDefinition, Type, VariableDefinition};
+use apollo_compiler::ast::{
+    DirectiveList, FieldDefinition, InputValueDefinition, Type, VariableDefinition,
+};
 use apollo_compiler::collections::IndexSet;
 use apollo_compiler::schema::{Component, ComponentName};
 
-use crate::config::ScalarConfig;
+use crate::config::{ScalarConfig, ScalarMapping};
 use crate::generators::GeneratorContext;
 use crate::generators::common::list::{render_list_closing, render_list_opening};
 use crate::{DeclarationKind, Result};
@@ -94,6 +96,81 @@ pub(crate) fn get_optional_prop_modifier(field_type: &FieldType) -> &'static str
     if avoid { "" } else { "?" }
 }
 
+/// Reason used by the GraphQL spec when `@deprecated` has no `reason`
+const DEFAULT_DEPRECATION_REASON: &str = "No longer supported";
+
+/// Returns the `@deprecated` reason, or `None` if the member isn't deprecated.
+pub(crate) fn deprecation_reason(directives: &DirectiveList) -> Option<&str> {
+    let directive = directives.get("deprecated")?;
+
+    let reason = directive
+        .specified_argument_by_name("reason")
+        .and_then(|value| value.as_str())
+        .unwrap_or(DEFAULT_DEPRECATION_REASON);
+
+    Some(reason)
+}
+
+/// Whether a deprecated member should be dropped from the schema types.
+pub(crate) fn is_omitted_deprecated(ctx: &GeneratorContext, directives: &DirectiveList) -> bool {
+    ctx.options.omit_deprecated && directives.get("deprecated").is_some()
+}
+
+/// Convert a description and an optional `@deprecated` reason to a
+/// TypeScript doc comment, so editors strike through deprecated members.
+///
+/// **Example:**
+/// ``` graphql
+/// "The user's name"
+/// name: String @deprecated(reason: "Use `fullName`")
+/// ```
+/// **Output:**
+/// ``` typescript
+/// /**
+///  * The user's name
+///  * @deprecated Use `fullName`
+///  */
+/// ```
+pub(crate) fn render_doc_comment(
+    ctx: &mut GeneratorContext,
+    description: Option<&str>,
+    deprecation: Option<&str>,
+    indent_level: usize,
+) -> Result<()> {
+    let description = description.filter(|d| !d.is_empty());
+    let deprecation = deprecation.map(|reason| format!("@deprecated {reason}"));
+
+    let lines: Vec<&str> = description
+        .into_iter()
+        .flat_map(str::lines)
+        .chain(deprecation.as_deref().into_iter().flat_map(str::lines))
+        .collect();
+
+    if lines.is_empty() {
+        return Ok(());
+    }
+
+    let indent = if indent_level > 0 {
+        " ".repeat(indent_level * 2)
+    } else {
+        "".to_string()
+    };
+
+    if lines.len() > 1 {
+        writeln!(ctx.writer, "{indent}/**")?;
+        for line in lines {
+            writeln!(ctx.writer, "{indent} * {line}")?;
+        }
+        writeln!(ctx.writer, "{indent} */")?;
+
+        return Ok(());
+    }
+
+    writeln!(ctx.writer, "{indent}/** {} */", lines[0])?;
+
+    Ok(())
+}
+
 /// Unwrap NonNull/List wrappers to get the inner named type.
 pub(crate) fn unwrap_type_name(ty: &Type) -> Name {
     match ty {
@@ -110,12 +187,14 @@ pub(crate) fn render_type(
 ) -> Result<()> {
     match ty {
         Type::Named(name) => {
+            register_scalar_import(ctx, name, dir);
             // TODO: make this a render only helper?
             let field = render_field_type(ctx, name, dir);
             write!(ctx.writer, "{field}")?;
             render_nullable_closing(ctx, NullableLocation::Field)?;
         }
         Type::NonNullNamed(name) => {
+            register_scalar_import(ctx, name, dir);
             // TODO: make this a render only helper?
             let field = render_field_type(ctx, name, dir);
             write!(ctx.writer, "{field}")?;
@@ -158,6 +237,58 @@ pub(crate) fn gql_scalar_to_ts(name: &str) -> Option<&'static str> {
     }
 }
 
+/// Resolve a scalar mapping for the given direction.
+pub(crate) fn scalar_mapping(config: &ScalarConfig, dir: ScalarDirection) -> ScalarMapping<'_> {
+    match dir {
+        ScalarDirection::Input => config.input(),
+        ScalarDirection::Output => config.output(),
+    }
+}
+
+/// Register the `import type` needed by a custom scalar mapping, if any.
+pub(crate) fn register_scalar_import(
+    ctx: &mut GeneratorContext,
+    name: &Name,
+    dir: ScalarDirection,
+) {
+    let Some(config) = ctx.options.scalars.get(name.as_str()) else {
+        return;
+    };
+
+    if ctx.schema.get_scalar(name).is_none() {
+        return;
+    }
+
+    register_mapping_import(ctx, scalar_mapping(config, dir));
+}
+
+/// Register the `import type` of a scalar mapping to a module type, under
+/// the name [`scalar_type_ref`] refers to it by.
+pub(crate) fn register_mapping_import(ctx: &mut GeneratorContext, mapping: ScalarMapping) {
+    if let Some(module) = mapping.module {
+        let local = scalar_type_ref(ctx, mapping).into_owned();
+        ctx.imports.add_aliased(module, mapping.ts_type, &local);
+    }
+}
+
+/// Type expression a scalar mapping is written as. A type imported from a
+/// module is aliased with a `_` suffix when a schema type of the same name
+/// is declared, e.g. `DateTime` mapped to `./scalars#DateTime`.
+pub(crate) fn scalar_type_ref<'m>(
+    ctx: &GeneratorContext,
+    mapping: ScalarMapping<'m>,
+) -> Cow<'m, str> {
+    let clashes = mapping.module.is_some()
+        && ctx.schema.types.iter().any(|(name, ty)| {
+            !ty.is_built_in() && ctx.transform_type_name(name) == mapping.ts_type
+        });
+    if clashes {
+        Cow::Owned(format!("{}_", mapping.ts_type))
+    } else {
+        Cow::Borrowed(mapping.ts_type)
+    }
+}
+
 pub(crate) fn render_field_type(
     ctx: &GeneratorContext,
     name: &Name,
@@ -165,17 +296,9 @@ pub(crate) fn render_field_type(
 ) -> Cow<'static, str> {
     let name_str = name.as_str();
 
-    let scalar_type = match dir {
-        ScalarDirection::Output => "output",
-        ScalarDirection::Input => "input",
-    };
-
     if ctx.schema.get_scalar(name).is_some() {
         if let Some(mapped) = ctx.options.scalars.get(name_str) {
-            match mapped {
-                ScalarConfig::Simple(value) => return Cow::Owned(value.clone()),
-                ScalarConfig::Detailed { input: _, output } => return Cow::Owned(output.clone()),
-            }
+            return Cow::Owned(scalar_type_ref(ctx, scalar_mapping(mapped, dir)).into_owned());
         }
 
         if let Some(ts_type) = gql_scalar_to_ts(name_str) {
@@ -0,0 +1,56 @@
+use std::collections::{BTreeMap, BTreeSet};
+use std::io::{self, Write};
+
+/// Type-only imports collected while generating a single output file.
+///
+/// Generators register what they reference instead of writing `import`
+/// statements themselves, so each module is imported exactly once and the
+/// statements end up at the top of the file regardless of generator order.
+#[derive(Debug, Default)]
+pub struct TypeImports {
+    /// Module specifier -> imported names (sorted for deterministic output)
+    modules: BTreeMap<String, BTreeSet<String>>,
+}
+
+impl TypeImports {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Register `name` as a type imported from `module`.
+    pub fn add(&mut self, module: &str, name: impl Into<String>) {
+        self.modules
+            .entry(module.to_string())
+            .or_default()
+            .insert(name.into());
+    }
+
+    /// Register `name` imported from `module` under the local name `alias`
+    /// (`name as alias`), or as is when they are the same.
+    pub fn add_aliased(&mut self, module: &str, name: &str, alias: &str) {
+        if name == alias {
+            self.add(module, name);
+        } else {
+            self.add(module, format!("{name} as {alias}"));
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.modules.is_empty()
+    }
+
+    /// Write one `import type { ... } from '...';` line per module,
+    /// followed by a blank line. Writes nothing when empty.
+    pub fn render(&self, w: &mut dyn Write) -> io::Result<()> {
+        if self.is_empty() {
+            return Ok(());
+        }
+
+        for (module, names) in &self.modules {
+            let names = names.iter().map(String::as_str).collect::<Vec<_>>();
+            writeln!(w, "import type {{ {} }} from '{module}';", names.join(", "))?;
+        }
+
+        writeln!(w)
+    }
+}
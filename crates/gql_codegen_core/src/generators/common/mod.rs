@@ -1,3 +1,4 @@
 pub(crate) mod helpers;
+pub(crate) mod imports;
 pub(crate) mod list;
 pub(crate) mod typename;
@@ -9,18 +9,21 @@ use apollo_compiler::validation::Valid;
 use apollo_compiler::{Name, Schema};
 use indexmap::IndexMap;
 
-use crate::config::{GeneratorOptions, NamingCase, NamingConvention};
+use crate::config::{Federation, GeneratorOptions, NamingCase, NamingConvention};
 use crate::diagnostic::{Diagnostic, DiagnosticCategory, Diagnostics};
 use crate::documents::{ParsedFragment, ParsedOperation};
 use crate::{GeneratorConfig, Result};
 
+pub use common::imports::TypeImports;
 pub use documents::generate_documents;
+pub use operation_registry::generate_operation_registry;
 pub use operation_types::generate_typescript_operations;
 pub use schema_types::generate_typescript;
 
 mod common;
 mod document_transform;
 mod documents;
+mod operation_registry;
 mod operation_types;
 mod schema_types;
 
@@ -33,6 +36,22 @@ pub struct GeneratorContext<'a> {
     pub writer: &'a mut dyn Write,
     pub diagnostics: &'a mut Diagnostics,
     pub generators: &'a [GeneratorConfig],
+    /// Type-only imports for the current output file, rendered at its top
+    pub imports: &'a mut TypeImports,
+    /// Output that schema types should be imported from instead of being
+    /// rendered inline
+    pub import_types_from: Option<TypesModule<'a>>,
+    /// Federation version the schema was loaded with; its types aren't generated
+    pub federation: Option<Federation>,
+}
+
+/// Output whose schema types another output imports (`importTypesFrom`)
+#[derive(Clone, Copy)]
+pub struct TypesModule<'a> {
+    /// Module specifier, relative to the importing output
+    pub module: &'a str,
+    /// Options the output's schema types are named with
+    pub options: &'a GeneratorOptions,
 }
 
 impl GeneratorContext<'_> {
@@ -47,19 +66,21 @@ impl GeneratorContext<'_> {
     /// Apply the configured `typeNames` naming convention to a type name,
     /// then apply `types_prefix` / `types_suffix` if configured.
     pub fn transform_type_name<'a>(&self, name: &'a str) -> Cow<'a, str> {
-        let (case, transform_underscore) = get_type_name_case(self.options);
-        let cased = case.apply(name, transform_underscore);
+        transform_type_name(self.options, name)
+    }
+}
+
+/// [`GeneratorContext::transform_type_name`] with the given options
+pub(crate) fn transform_type_name<'a>(options: &GeneratorOptions, name: &'a str) -> Cow<'a, str> {
+    let (case, transform_underscore) = get_type_name_case(options);
+    let cased = case.apply(name, transform_underscore);
 
-        match (
-            &self.options.type_name_prefix,
-            &self.options.type_name_suffix,
-        ) {
-            (None, None) => cased,
-            (prefix, suffix) => {
-                let prefix = prefix.as_deref().unwrap_or("");
-                let suffix = suffix.as_deref().unwrap_or("");
-                Cow::Owned(format!("{prefix}{cased}{suffix}"))
-            }
+    match (&options.type_name_prefix, &options.type_name_suffix) {
+        (None, None) => cased,
+        (prefix, suffix) => {
+            let prefix = prefix.as_deref().unwrap_or("");
+            let suffix = suffix.as_deref().unwrap_or("");
+            Cow::Owned(format!("{prefix}{cased}{suffix}"))
         }
     }
 }
@@ -82,6 +103,7 @@ pub fn run_generator(name: &str, ctx: &mut GeneratorContext) -> Result<()> {
         "schema-types" | "typescript" => generate_typescript(ctx),
         "operation-types" | "typescript-operations" => generate_typescript_operations(ctx),
         "typed-documents" | "typescript-documents" | "documents" => generate_documents(ctx),
+        "operation-registry" => generate_operation_registry(ctx),
         _ => Err(Diagnostic::error(
             DiagnosticCategory::Generation,
             format!("Unknown generator: '{name}'"),
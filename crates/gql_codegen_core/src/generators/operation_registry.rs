@@ -0,0 +1,151 @@
+//! Operation registry generator
+//!
+//! Generates a single `Operations` type mapping every operation name to its
+//! result type, variables type and kind. This lets one generic client
+//! function (`request<K extends keyof Operations>(name: K, ...)`) be fully
+//! typed without importing each operation's types individually.
+
+use apollo_compiler::Name;
+use apollo_compiler::ast::OperationType;
+
+use super::GeneratorContext;
+use crate::Result;
+use crate::analysis::collect_spreads;
+use crate::generators::common::helpers::{get_export_kw, get_readonly_kw, indent};
+use crate::generators::operation_types::operation::operation_type_name;
+use crate::generators::operation_types::variables::variables_type_name;
+
+/// Type used for operations without variables
+const EMPTY_VARIABLES_TYPE: &str = "Record<string, never>";
+
+/// Generate the operation registry type
+///
+/// **Example**
+/// ``` graphql
+/// query GetUser($id: ID!) {
+///   user(id: $id) { id }
+/// }
+/// ```
+///
+/// With `registryDocuments`, a `<Name>Document` constant holding the
+/// operation and the fragments it uses is emitted for each operation and
+/// referenced from its entry. It is named like the operation's types.
+///
+/// ```ts
+/// export interface Operations {
+///   readonly GetUser: {
+///     readonly kind: 'query';
+///     readonly result: GetUserQuery;
+///     readonly variables: GetUserQueryVariables;
+///   };
+/// }
+/// ```
+pub fn generate_operation_registry(ctx: &mut GeneratorContext) -> Result<()> {
+    let export = get_export_kw(ctx);
+    let readonly = get_readonly_kw(ctx);
+    let include_document = ctx.options.registry_documents;
+
+    // Sort for deterministic output
+    let mut names: Vec<_> = ctx.operations.keys().collect();
+    names.sort();
+
+    if include_document {
+        for &name in &names {
+            let document = escape_template(&document_text(ctx, name));
+            let document_name = document_name(ctx, name);
+            writeln!(
+                ctx.writer,
+                "{export}const {document_name} = `{document}` as const;"
+            )?;
+        }
+        writeln!(ctx.writer)?;
+    }
+
+    writeln!(ctx.writer, "{export}interface Operations {{")?;
+
+    for name in names {
+        let operation = &ctx.operations[name].definition;
+
+        let Some(root_type) = ctx.schema.root_operation(operation.operation_type) else {
+            continue;
+        };
+
+        let result = operation_type_name(ctx, name, root_type);
+        let variables = if operation.variables.is_empty() {
+            EMPTY_VARIABLES_TYPE.to_string()
+        } else {
+            variables_type_name(ctx, &result)
+        };
+        let kind = match operation.operation_type {
+            OperationType::Query => "query",
+            OperationType::Mutation => "mutation",
+            OperationType::Subscription => "subscription",
+        };
+
+        indent(ctx, 1)?;
+        writeln!(ctx.writer, "{readonly}{name}: {{")?;
+        indent(ctx, 2)?;
+        writeln!(ctx.writer, "{readonly}kind: '{kind}';")?;
+        indent(ctx, 2)?;
+        writeln!(ctx.writer, "{readonly}result: {result};")?;
+        indent(ctx, 2)?;
+        writeln!(ctx.writer, "{readonly}variables: {variables};")?;
+
+        if include_document {
+            indent(ctx, 2)?;
+            let document_name = document_name(ctx, name);
+            writeln!(ctx.writer, "{readonly}document: typeof {document_name};")?;
+        }
+
+        indent(ctx, 1)?;
+        writeln!(ctx.writer, "}};")?;
+    }
+
+    writeln!(ctx.writer, "}}")?;
+    writeln!(ctx.writer)?;
+
+    Ok(())
+}
+
+/// Name of the document constant of an operation, e.g. `GetUserDocument`,
+/// named like the operation's types
+fn document_name(ctx: &GeneratorContext, name: &Name) -> String {
+    ctx.transform_type_name(&format!("{name}Document"))
+        .into_owned()
+}
+
+/// Source of an operation followed by every fragment it uses, directly or
+/// through other fragments, in the order they are first spread
+fn document_text(ctx: &GeneratorContext, name: &Name) -> String {
+    let operation = &ctx.operations[name];
+    let mut fragments: Vec<&Name> = Vec::new();
+    collect_spreads(&operation.definition.selection_set, &mut |spread| {
+        if !fragments.contains(&&spread.fragment_name) {
+            fragments.push(&spread.fragment_name);
+        }
+    });
+
+    let mut text = operation.text.trim().to_string();
+    let mut i = 0;
+    while let Some(&fragment_name) = fragments.get(i) {
+        i += 1;
+        let Some(fragment) = ctx.fragments.get(fragment_name) else {
+            continue;
+        };
+        collect_spreads(&fragment.definition.selection_set, &mut |spread| {
+            if !fragments.contains(&&spread.fragment_name) {
+                fragments.push(&spread.fragment_name);
+            }
+        });
+        text.push_str("\n\n");
+        text.push_str(fragment.text.trim());
+    }
+    text
+}
+
+/// Escape text for a TypeScript template literal
+fn escape_template(text: &str) -> String {
+    text.replace('\\', "\\\\")
+        .replace('`', "\\`")
+        .replace("${", "\\${")
+}
@@ -3,8 +3,8 @@ use apollo_compiler::ast::Type;
 use crate::Result;
 use crate::generators::GeneratorContext;
 use crate::generators::common::helpers::{
-    NullableLocation, ScalarDirection, get_readonly_kw, indent, render_nullable_closing,
-    render_type,
+    NullableLocation, ScalarDirection, deprecation_reason, get_readonly_kw, indent,
+    render_doc_comment, render_nullable_closing, render_type,
 };
 use crate::generators::common::list::{render_list_closing, render_list_opening};
 use crate::generators::operation_types::selection::{
@@ -20,6 +20,12 @@ pub(crate) fn render_field(
     let readonly = get_readonly_kw(ctx);
     let optional = get_optional_prop_modifier(field);
 
+    if let Ok(definition) = ctx.schema.type_field(&field.parent_type, &field.field_name)
+        && let Some(reason) = deprecation_reason(&definition.directives)
+    {
+        render_doc_comment(ctx, None, Some(reason), depth)?;
+    }
+
     indent(ctx, depth)?;
     write!(ctx.writer, "{readonly}{response_type}{optional}: ")?;
 
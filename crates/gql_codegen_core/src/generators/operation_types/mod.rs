@@ -2,19 +2,21 @@ use apollo_compiler::Name;
 use apollo_compiler::ast::OperationDefinition;
 use apollo_compiler::schema::ExtendedType;
 
-use crate::generators::GeneratorContext;
 use crate::generators::operation_types::fragment::render_fragment;
 use crate::generators::operation_types::operation::render_operation;
+use crate::generators::operation_types::referenced::collect_referenced_schema_types;
 use crate::generators::schema_types::r#enum::render_enum;
 use crate::generators::schema_types::union::render_union;
-use crate::{ParsedFragment, Result};
+use crate::generators::{GeneratorContext, transform_type_name};
+use crate::{GeneratorConfig, ParsedFragment, Result};
 
 mod field;
 mod fragment;
-mod operation;
+pub(crate) mod operation;
+mod referenced;
 mod selection;
 mod typename;
-mod variables;
+pub(crate) mod variables;
 
 /// Item to generate - either a fragment or operation
 enum GenerateItem<'a> {
@@ -22,6 +24,11 @@ enum GenerateItem<'a> {
     Operation(&'a Name, &'a OperationDefinition),
 }
 
+/// Whether the schema types are generated into the same output.
+pub(crate) fn has_schema_types_generator(ctx: &GeneratorContext) -> bool {
+    ctx.generators.iter().any(GeneratorConfig::is_schema_types)
+}
+
 /// Generate TypeScript types for GraphQL operations
 ///
 /// **Example**
@@ -47,19 +54,30 @@ pub fn generate_typescript_operations(ctx: &mut GeneratorContext) -> Result<()>
     let mut items: Vec<GenerateItem> =
         Vec::with_capacity(ctx.fragments.len() + ctx.operations.len());
 
-    let schema_types_generator = ctx.generators.iter().find(|g| g.name() == "schema-types");
-
-    // need to render dependencies if schema types plugin is not available
-    if schema_types_generator.is_none() {
-        for (name, ty) in &ctx.schema.types {
-            if name.starts_with("__") {
-                continue;
+    // need to import or render dependencies if schema types plugin is not available
+    if !has_schema_types_generator(ctx) {
+        match ctx.import_types_from {
+            Some(source) => {
+                // The target output may name types differently; import its
+                // names under the ones this output refers to
+                for name in collect_referenced_schema_types(ctx) {
+                    let local = ctx.transform_type_name(&name).into_owned();
+                    let exported = transform_type_name(source.options, &name);
+                    ctx.imports.add_aliased(source.module, &exported, &local);
+                }
             }
+            None => {
+                for (name, ty) in &ctx.schema.types {
+                    if name.starts_with("__") {
+                        continue;
+                    }
 
-            match ty {
-                ExtendedType::Enum(en) => render_enum(ctx, en)?,
-                ExtendedType::Union(union) => render_union(ctx, name, union)?,
-                _ => {}
+                    match ty {
+                        ExtendedType::Enum(en) => render_enum(ctx, en, false)?,
+                        ExtendedType::Union(union) => render_union(ctx, name, union)?,
+                        _ => {}
+                    }
+                }
             }
         }
     }
@@ -22,8 +22,7 @@ pub(crate) fn render_operation(
     let mut normalized = NormalizedSelectionSet::new();
     collect_selection_set(ctx, &operation.selection_set, root_type, &mut normalized)?;
 
-    let name = format!("{name}{root_type}");
-    let name = ctx.transform_type_name(&name);
+    let name = operation_type_name(ctx, name, root_type);
 
     render_decl_prefix(ctx, &name, None)?;
     writeln!(ctx.writer, "{{")?;
@@ -35,3 +34,9 @@ pub(crate) fn render_operation(
 
     Ok(())
 }
+
+/// Name of the generated result type for an operation, e.g. `GetUserQuery`.
+pub(crate) fn operation_type_name(ctx: &GeneratorContext, name: &Name, root_type: &Name) -> String {
+    let name = format!("{name}{root_type}");
+    ctx.transform_type_name(&name).into_owned()
+}
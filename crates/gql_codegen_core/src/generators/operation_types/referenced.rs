@@ -0,0 +1,76 @@
+use std::collections::BTreeSet;
+
+use apollo_compiler::Name;
+use apollo_compiler::ast::Selection;
+use apollo_compiler::schema::ExtendedType;
+
+use crate::generators::GeneratorContext;
+
+/// Collect the schema types that operation types refer to by name.
+///
+/// Selections are rendered inline, so only leaf enums and the named types of
+/// variables (enums and input objects) end up referenced from the output.
+/// Used with `importTypesFrom` to import exactly those names.
+pub(crate) fn collect_referenced_schema_types(ctx: &GeneratorContext) -> BTreeSet<Name> {
+    let mut referenced = BTreeSet::new();
+
+    for operation in ctx.operations.values() {
+        let definition = &operation.definition;
+
+        for var in &definition.variables {
+            let name = var.ty.inner_named_type();
+            if matches!(
+                ctx.schema.types.get(name),
+                Some(ExtendedType::Enum(_) | ExtendedType::InputObject(_))
+            ) {
+                referenced.insert(name.clone());
+            }
+        }
+
+        if let Some(root_type) = ctx.schema.root_operation(definition.operation_type) {
+            collect_from_selections(ctx, &definition.selection_set, root_type, &mut referenced);
+        }
+    }
+
+    for fragment in ctx.fragments.values() {
+        let definition = &fragment.definition;
+        collect_from_selections(
+            ctx,
+            &definition.selection_set,
+            &definition.type_condition,
+            &mut referenced,
+        );
+    }
+
+    referenced
+}
+
+fn collect_from_selections(
+    ctx: &GeneratorContext,
+    selections: &[Selection],
+    parent_type: &Name,
+    out: &mut BTreeSet<Name>,
+) {
+    for selection in selections {
+        match selection {
+            Selection::Field(field) => {
+                let Ok(type_field) = ctx.schema.type_field(parent_type, &field.name) else {
+                    continue;
+                };
+                let field_type = type_field.ty.inner_named_type();
+
+                if let Some(ExtendedType::Enum(_)) = ctx.schema.types.get(field_type) {
+                    out.insert(field_type.clone());
+                }
+
+                collect_from_selections(ctx, &field.selection_set, field_type, out);
+            }
+            Selection::InlineFragment(inline) => {
+                let type_name = inline.type_condition.as_ref().unwrap_or(parent_type);
+                collect_from_selections(ctx, &inline.selection_set, type_name, out);
+            }
+            // Fragment definitions are walked on their own
+            Selection::FragmentSpread(_) => {}
+        }
+    }
+}
@@ -1,11 +1,15 @@
-use apollo_compiler::ast::OperationDefinition;
+use apollo_compiler::ast::{OperationDefinition, Type};
+use apollo_compiler::schema::ExtendedType;
 
 use crate::Result;
 use crate::generators::GeneratorContext;
 use crate::generators::common::helpers::{
-    FieldType, ScalarDirection, get_optional_prop_modifier, get_readonly_kw, indent,
-    render_decl_closing, render_decl_opening, render_type,
+    FieldType, NullableLocation, ScalarDirection, get_optional_prop_modifier, get_readonly_kw,
+    indent, render_decl_closing, render_decl_opening, render_nullable_closing, render_type,
 };
+use crate::generators::common::list::{render_list_closing, render_list_opening};
+use crate::generators::operation_types::has_schema_types_generator;
+use crate::generators::schema_types::input::{is_one_of, render_one_of_members};
 
 /// Renders variable for operations as a distinct type.
 ///
@@ -26,8 +30,7 @@ pub(crate) fn render_variables(
     }
 
     let readonly = get_readonly_kw(ctx);
-    let raw_name = format!("{op_name}Variables");
-    let name = ctx.transform_type_name(&raw_name);
+    let name = variables_type_name(ctx, op_name);
 
     render_decl_opening(ctx, &name, None)?;
 
@@ -37,7 +40,7 @@ pub(crate) fn render_variables(
 
         indent(ctx, 1)?;
         write!(ctx.writer, "{readonly}{name}{optional}: ")?;
-        render_type(ctx, &var.ty, ScalarDirection::Input)?;
+        render_variable_type(ctx, &var.ty)?;
         writeln!(ctx.writer, ";")?;
     }
 
@@ -46,3 +49,43 @@ pub(crate) fn render_variables(
 
     Ok(())
 }
+
+/// Render a variable's type. `@oneOf` inputs are inlined as a discriminated
+/// union when no schema types are generated or imported for this output,
+/// since there is no named type to refer to.
+fn render_variable_type(ctx: &mut GeneratorContext, ty: &Type) -> Result<()> {
+    let inline_types = !has_schema_types_generator(ctx) && ctx.import_types_from.is_none();
+
+    let one_of = match ctx.schema.types.get(ty.inner_named_type()) {
+        Some(ExtendedType::InputObject(input)) if inline_types && is_one_of(input) => input,
+        _ => return render_type(ctx, ty, ScalarDirection::Input),
+    };
+
+    render_list_opening(ctx, ty)?;
+    write!(ctx.writer, "(")?;
+    render_one_of_members(ctx, one_of, " | ")?;
+    write!(ctx.writer, ")")?;
+
+    let mut element = ty;
+    while let Type::List(inner) | Type::NonNullList(inner) = element {
+        element = inner;
+    }
+    if ty.is_list() && !element.is_non_null() {
+        render_nullable_closing(ctx, NullableLocation::Field)?;
+    }
+
+    render_list_closing(ctx, ty)?;
+
+    if !ty.is_non_null() {
+        render_nullable_closing(ctx, NullableLocation::Field)?;
+    }
+
+    Ok(())
+}
+
+/// Name of the generated variables type for an operation result type name,
+/// e.g. `GetUserQuery` -> `GetUserQueryVariables`.
+pub(crate) fn variables_type_name(ctx: &GeneratorContext, op_name: &str) -> String {
+    let raw_name = format!("{op_name}Variables");
+    ctx.transform_type_name(&raw_name).into_owned()
+}
@@ -6,7 +6,7 @@ use apollo_compiler::schema::EnumType;
 use crate::Result;
 use crate::config::{GeneratorOptions, NamingCase, NamingConvention};
 use crate::generators::GeneratorContext;
-use crate::generators::common::helpers::get_export_kw;
+use crate::generators::common::helpers::{deprecation_reason, get_export_kw, render_doc_comment};
 use crate::generators::schema_types::helpers::render_description;
 
 // TODO: js lib is not persisting enum key for ts enums
@@ -26,12 +26,20 @@ use crate::generators::schema_types::helpers::render_description;
 /// ``` typescript
 /// type Status = 'ACTIVE' | 'INACTIVE';
 /// ```
-pub(crate) fn render_enum(ctx: &mut GeneratorContext, enum_type: &Node<EnumType>) -> Result<()> {
+///
+/// Deprecated values are dropped when `omit_deprecated` is set. Only the
+/// schema types honor `omitDeprecated`: operation result types must keep
+/// every value the server can still return.
+pub(crate) fn render_enum(
+    ctx: &mut GeneratorContext,
+    enum_type: &Node<EnumType>,
+    omit_deprecated: bool,
+) -> Result<()> {
     render_description(ctx, &enum_type.description, 0)?;
     let name = ctx.transform_type_name(enum_type.name.as_str());
 
     // TODO: maybe as const enum is ok?
-    render_as_type_union(ctx, &name, enum_type)?;
+    render_as_type_union(ctx, &name, enum_type, omit_deprecated)?;
 
     writeln!(ctx.writer)?;
     Ok(())
@@ -47,14 +55,24 @@ fn render_as_type_union(
     ctx: &mut GeneratorContext,
     enum_name: &str,
     enum_type: &Node<EnumType>,
+    omit_deprecated: bool,
 ) -> Result<()> {
     let export = get_export_kw(ctx);
 
     writeln!(ctx.writer, "{export}type {enum_name} =")?;
 
-    let len = enum_type.values.len();
+    let values: Vec<_> = enum_type
+        .values
+        .values()
+        .filter(|value| !(omit_deprecated && value.directives.get("deprecated").is_some()))
+        .collect();
+    let len = values.len();
+
+    if len == 0 && !ctx.options.future_proof_enums() {
+        writeln!(ctx.writer, "  never;")?;
+    }
 
-    for (i, value) in enum_type.values.values().enumerate() {
+    for (i, value) in values.into_iter().enumerate() {
         let transformed = transform_enum_value(value.value.as_str(), ctx.options);
 
         let semi = if i == len - 1 && !ctx.options.future_proof_enums() {
@@ -63,7 +81,8 @@ fn render_as_type_union(
             ""
         };
 
-        render_description(ctx, &value.description, 1)?;
+        let deprecation = deprecation_reason(&value.directives);
+        render_doc_comment(ctx, value.description.as_deref(), deprecation, 1)?;
         writeln!(ctx.writer, "  | '{transformed}'{semi}")?;
     }
 
@@ -3,9 +3,9 @@ use apollo_compiler::Name;
 use crate::Result;
 use crate::generators::GeneratorContext;
 use crate::generators::common::helpers::{
-    FieldType, get_optional_prop_modifier, get_readonly_kw, render_type,
+    FieldType, deprecation_reason, get_optional_prop_modifier, get_readonly_kw, render_doc_comment,
+    render_type,
 };
-use crate::generators::schema_types::helpers::render_description;
 
 pub(crate) fn render_field(
     ctx: &mut GeneratorContext,
@@ -16,13 +16,21 @@ pub(crate) fn render_field(
     let optional = get_optional_prop_modifier(field_type);
     let dir = field_type.direction();
 
-    let (description, ty) = match field_type {
-        FieldType::InputObject(field) => (&field.description, field.ty.as_ref()),
-        FieldType::Object(field) => (&field.description, &field.ty),
-        FieldType::Variable(field) => (&None, field.ty.as_ref()),
+    let (description, deprecation, ty) = match field_type {
+        FieldType::InputObject(field) => (
+            field.description.as_deref(),
+            deprecation_reason(&field.directives),
+            field.ty.as_ref(),
+        ),
+        FieldType::Object(field) => (
+            field.description.as_deref(),
+            deprecation_reason(&field.directives),
+            &field.ty,
+        ),
+        FieldType::Variable(field) => (None, None, field.ty.as_ref()),
     };
 
-    render_description(ctx, description, 1)?;
+    render_doc_comment(ctx, description, deprecation, 1)?;
     write!(ctx.writer, "  {readonly}{name}{optional}: ")?;
 
     render_type(ctx, ty, dir)?;
@@ -2,6 +2,7 @@ use apollo_compiler::Node;
 
 use crate::Result;
 use crate::generators::GeneratorContext;
+use crate::generators::common::helpers::render_doc_comment;
 
 /// Convert a GraphQL description to a TypeScript doc comment.
 ///
@@ -21,33 +22,7 @@ pub(crate) fn render_description(
     description: &Option<Node<str>>,
     indent_level: usize,
 ) -> Result<()> {
-    let Some(description) = description else {
-        return Ok(());
-    };
-
-    if description.is_empty() {
-        return Ok(());
-    }
-
-    let indent = if indent_level > 0 {
-        " ".repeat(indent_level * 2)
-    } else {
-        "".to_string()
-    };
-
-    if description.lines().count() > 1 {
-        writeln!(ctx.writer, "{indent}/**")?;
-        for line in description.lines() {
-            writeln!(ctx.writer, "{indent} * {line}")?;
-        }
-        writeln!(ctx.writer, "{indent} */")?;
-
-        return Ok(());
-    }
-
-    writeln!(ctx.writer, "{indent}/** {description} */")?;
-
-    Ok(())
+    render_doc_comment(ctx, description.as_deref(), None, indent_level)
 }
 
 #[cfg(test)]
@@ -3,7 +3,10 @@ use apollo_compiler::schema::InputObjectType;
 
 use crate::Result;
 use crate::generators::GeneratorContext;
-use crate::generators::common::helpers::{FieldType, render_decl_closing, render_decl_opening};
+use crate::generators::common::helpers::{
+    FieldType, ScalarDirection, get_export_kw, get_readonly_kw, is_omitted_deprecated,
+    render_decl_closing, render_decl_opening, render_type,
+};
 use crate::generators::schema_types::field::render_field;
 use crate::generators::schema_types::helpers::render_description;
 
@@ -31,9 +34,18 @@ pub(crate) fn render_input(
     let type_name = ctx.transform_type_name(input.name.as_str());
 
     render_description(ctx, &input.description, 0)?;
+
+    if is_one_of(input) {
+        return render_one_of_input(ctx, &type_name, input);
+    }
+
     render_decl_opening(ctx, &type_name, None)?;
 
     for (field_name, field) in input.fields.iter() {
+        if is_omitted_deprecated(ctx, &field.directives) {
+            continue;
+        }
+
         render_field(ctx, field_name, &FieldType::InputObject(field.as_ref()))?;
     }
 
@@ -42,3 +54,77 @@ pub(crate) fn render_input(
 
     Ok(())
 }
+
+/// Whether an input object is marked `@oneOf` (exactly one field must be set).
+pub(crate) fn is_one_of(input: &InputObjectType) -> bool {
+    input.directives.get("oneOf").is_some()
+}
+
+/// Render a `@oneOf` input object as a discriminated union, so TypeScript
+/// enforces that exactly one key is set. Always a `type` alias since an
+/// interface can't express a union.
+///
+/// **Example Input:**
+/// ``` graphql
+/// input UserBy @oneOf {
+///   id: ID
+///   email: String
+/// }
+/// ```
+///
+/// **Output:**
+/// ``` typescript
+/// export type UserBy =
+///   | { id: string; email?: never }
+///   | { email: string; id?: never };
+/// ```
+fn render_one_of_input(
+    ctx: &mut GeneratorContext,
+    type_name: &str,
+    input: &InputObjectType,
+) -> Result<()> {
+    let export = get_export_kw(ctx);
+
+    write!(ctx.writer, "{export}type {type_name} =\n  | ")?;
+    render_one_of_members(ctx, input, "\n  | ")?;
+    writeln!(ctx.writer, ";")?;
+    writeln!(ctx.writer)?;
+
+    Ok(())
+}
+
+/// Write one object member per `@oneOf` field, joined by `separator`.
+/// The chosen field is required and non-null; all others are `?: never`.
+pub(crate) fn render_one_of_members(
+    ctx: &mut GeneratorContext,
+    input: &InputObjectType,
+    separator: &str,
+) -> Result<()> {
+    let readonly = get_readonly_kw(ctx);
+    let fields: Vec<_> = input
+        .fields
+        .values()
+        .filter(|field| !is_omitted_deprecated(ctx, &field.directives))
+        .collect();
+
+    for (i, field) in fields.iter().enumerate() {
+        if i > 0 {
+            write!(ctx.writer, "{separator}")?;
+        }
+
+        write!(ctx.writer, "{{ {readonly}{}: ", field.name)?;
+        render_type(
+            ctx,
+            &field.ty.as_ref().clone().non_null(),
+            ScalarDirection::Input,
+        )?;
+
+        for other in fields.iter().filter(|other| other.name != field.name) {
+            write!(ctx.writer, "; {readonly}{}?: never", other.name)?;
+        }
+
+        write!(ctx.writer, " }}")?;
+    }
+
+    Ok(())
+}
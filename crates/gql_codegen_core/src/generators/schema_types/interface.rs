@@ -3,7 +3,9 @@ use apollo_compiler::schema::InterfaceType;
 
 use crate::Result;
 use crate::generators::GeneratorContext;
-use crate::generators::common::helpers::{FieldType, render_decl_closing, render_decl_opening};
+use crate::generators::common::helpers::{
+    FieldType, is_omitted_deprecated, render_decl_closing, render_decl_opening,
+};
 use crate::generators::schema_types::field::render_field;
 use crate::generators::schema_types::helpers::render_description;
 
@@ -38,6 +40,10 @@ pub(crate) fn render_interface(
     render_decl_opening(ctx, &type_name, None)?;
 
     for (field_name, field) in interface.fields.iter() {
+        if is_omitted_deprecated(ctx, &field.directives) {
+            continue;
+        }
+
         render_field(ctx, field_name, &FieldType::Object(field))?;
     }
 
@@ -6,7 +6,6 @@
 use apollo_compiler::schema::ExtendedType;
 
 use super::GeneratorContext;
-use crate::Result;
 use crate::generators::schema_types::r#enum::render_enum;
 use crate::generators::schema_types::input::render_input;
 use crate::generators::schema_types::interface::render_interface;
@@ -14,11 +13,12 @@ use crate::generators::schema_types::object::render_object;
 use crate::generators::schema_types::operation_types::collect_operation_types;
 use crate::generators::schema_types::scalar::render_scalar;
 use crate::generators::schema_types::union::render_union;
+use crate::{Result, federation};
 
 pub(crate) mod r#enum;
 mod field;
 mod helpers;
-mod input;
+pub(crate) mod input;
 mod interface;
 mod object;
 mod operation_types;
@@ -47,6 +47,10 @@ pub fn generate_typescript(ctx: &mut GeneratorContext) -> Result<()> {
             continue;
         }
 
+        if federation::is_internal_type(ctx.federation, name) {
+            continue;
+        }
+
         // Skip types not referenced in operations (if only_operation_types is enabled)
         if let Some(ref referenced) = referenced_types
             && referenced.contains(name.as_str())
@@ -60,7 +64,7 @@ pub fn generate_typescript(ctx: &mut GeneratorContext) -> Result<()> {
 
         match ty {
             ExtendedType::Object(obj) => render_object(ctx, obj)?,
-            ExtendedType::Enum(en) => render_enum(ctx, en)?,
+            ExtendedType::Enum(en) => render_enum(ctx, en, ctx.options.omit_deprecated)?,
             ExtendedType::Interface(iface) => render_interface(ctx, iface)?,
             ExtendedType::Union(union) => render_union(ctx, name, union)?,
             ExtendedType::InputObject(input) => render_input(ctx, input)?,
@@ -3,7 +3,9 @@ use apollo_compiler::schema::ObjectType;
 
 use crate::Result;
 use crate::generators::GeneratorContext;
-use crate::generators::common::helpers::{FieldType, render_decl_closing, render_decl_opening};
+use crate::generators::common::helpers::{
+    FieldType, is_omitted_deprecated, render_decl_closing, render_decl_opening,
+};
 use crate::generators::common::typename::render_typename;
 use crate::generators::schema_types::field::render_field;
 use crate::generators::schema_types::helpers::render_description;
@@ -40,6 +42,10 @@ pub(crate) fn render_object(ctx: &mut GeneratorContext, object: &Node<ObjectType
     render_typename(ctx, raw_name)?;
 
     for (field_name, field) in object.fields.iter() {
+        if is_omitted_deprecated(ctx, &field.directives) {
+            continue;
+        }
+
         render_field(ctx, field_name, &FieldType::Object(field))?;
     }
 
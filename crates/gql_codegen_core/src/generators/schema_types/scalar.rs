@@ -7,7 +7,10 @@ use crate::Result;
 use crate::config::ScalarConfig;
 use crate::diagnostic::{Diagnostic, DiagnosticCategory};
 use crate::generators::GeneratorContext;
-use crate::generators::common::helpers::{get_export_kw, render_decl_closing, render_decl_opening};
+use crate::generators::common::helpers::{
+    get_export_kw, register_mapping_import, render_decl_closing, render_decl_opening,
+    scalar_type_ref,
+};
 use crate::generators::schema_types::helpers::render_description;
 
 const DEFAULT_SCALARS: [(&str, &str); 5] = [
@@ -34,17 +37,20 @@ pub(crate) fn render_scalar(ctx: &mut GeneratorContext, scalar: &Node<ScalarType
     let custom_type = ctx.options.scalars.get(raw_name);
 
     match custom_type {
-        Some(ScalarConfig::Simple(ts_type)) => {
-            render_description(ctx, &scalar.description, 0)?;
-            writeln!(ctx.writer, "{export}type {type_name} = {ts_type};")?;
-            writeln!(ctx.writer)?;
-        }
-        Some(ScalarConfig::Detailed { input, output }) => {
+        Some(config) => {
+            register_scalar_imports(ctx, config);
+            let input = scalar_type_ref(ctx, config.input());
+            let output = scalar_type_ref(ctx, config.output());
+
             render_description(ctx, &scalar.description, 0)?;
-            writeln!(
-                ctx.writer,
-                "{export}type {type_name} = {{\n  input: {input};\n  output: {output};\n}};"
-            )?;
+            if input == output {
+                writeln!(ctx.writer, "{export}type {type_name} = {output};")?;
+            } else {
+                writeln!(
+                    ctx.writer,
+                    "{export}type {type_name} = {{\n  input: {input};\n  output: {output};\n}};"
+                )?;
+            }
             writeln!(ctx.writer)?;
         }
         None => {
@@ -84,9 +90,14 @@ pub(crate) fn render_scalars(ctx: &mut GeneratorContext) -> Result<()> {
         }
 
         let (input, output) = match custom {
-            Some(ScalarConfig::Simple(value)) => (value.as_str(), value.as_str()),
-            Some(ScalarConfig::Detailed { input, output }) => (input.as_str(), output.as_str()),
-            None => (default_type, default_type),
+            Some(config) => {
+                register_scalar_imports(ctx, config);
+                (
+                    scalar_type_ref(ctx, config.input()),
+                    scalar_type_ref(ctx, config.output()),
+                )
+            }
+            None => (default_type.into(), default_type.into()),
         };
 
         writeln!(
@@ -121,9 +132,14 @@ pub(crate) fn render_scalars(ctx: &mut GeneratorContext) -> Result<()> {
                 .unwrap_or("unknown");
 
             let (input, output) = match custom_type {
-                Some(ScalarConfig::Simple(value)) => (value.as_str(), value.as_str()),
-                Some(ScalarConfig::Detailed { input, output }) => (input.as_str(), output.as_str()),
-                None => (default_type, default_type),
+                Some(config) => {
+                    register_scalar_imports(ctx, config);
+                    (
+                        scalar_type_ref(ctx, config.input()),
+                        scalar_type_ref(ctx, config.output()),
+                    )
+                }
+                None => (default_type.into(), default_type.into()),
             };
 
             writeln!(
@@ -140,3 +156,10 @@ pub(crate) fn render_scalars(ctx: &mut GeneratorContext) -> Result<()> {
 
     Ok(())
 }
+
+/// Register the imports for both directions of a custom scalar mapping.
+fn register_scalar_imports(ctx: &mut GeneratorContext, config: &ScalarConfig) {
+    for mapping in [config.input(), config.output()] {
+        register_mapping_import(ctx, mapping);
+    }
+}
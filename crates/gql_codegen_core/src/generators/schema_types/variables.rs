@@ -1,13 +1,25 @@
 use apollo_compiler::schema::ObjectType;
 
 use crate::generators::GeneratorContext;
-use crate::generators::common::helpers::{FieldType, render_decl_closing, render_decl_opening};
+use crate::generators::common::helpers::{
+    FieldType, is_omitted_deprecated, render_decl_closing, render_decl_opening,
+};
 use crate::generators::schema_types::field::render_field;
 use crate::{NamingCase, Result};
 
 pub fn render_variables(ctx: &mut GeneratorContext, name: &str, object: &ObjectType) -> Result<()> {
     for (field_name, field) in object.fields.iter() {
-        if field.arguments.is_empty() {
+        if is_omitted_deprecated(ctx, &field.directives) {
+            continue;
+        }
+
+        let arguments: Vec<_> = field
+            .arguments
+            .iter()
+            .filter(|arg| !is_omitted_deprecated(ctx, &arg.directives))
+            .collect();
+
+        if arguments.is_empty() {
             continue;
         }
 
@@ -17,8 +29,8 @@ pub fn render_variables(ctx: &mut GeneratorContext, name: &str, object: &ObjectT
         let args_name = ctx.transform_type_name(&composed);
         render_decl_opening(ctx, &args_name, None)?;
 
-        for field in field.arguments.iter() {
-            render_field(ctx, &field.name, &FieldType::InputObject(field.as_ref()))?;
+        for arg in arguments {
+            render_field(ctx, &arg.name, &FieldType::InputObject(arg.as_ref()))?;
         }
 
         render_decl_closing(ctx)?;
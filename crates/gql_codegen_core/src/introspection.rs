@@ -0,0 +1,549 @@
+//! Introspection JSON schema input
+//!
+//! Converts `{ "data": { "__schema": … } }` or `{ "__schema": … }` results
+//! into SDL. Definitions remember where their name appears in the JSON so
+//! schema errors can point back into the original file.
+
+use std::fmt::Write;
+use std::ops::Range;
+use std::path::Path;
+
+use serde::Deserialize;
+
+use crate::diagnostic::{Diagnostic, DiagnosticCategory, DiagnosticLocation, Diagnostics};
+use crate::error::Result;
+use crate::source_cache::line_column;
+
+/// Whether a schema path should be read as an introspection result.
+/// Schemas fetched from URLs are already SDL.
+pub(crate) fn is_introspection_path(path: &Path) -> bool {
+    !path.to_str().is_some_and(crate::remote::is_url)
+        && path
+            .extension()
+            .is_some_and(|ext| ext.eq_ignore_ascii_case("json"))
+}
+
+/// Convert an introspection result into SDL.
+///
+/// Built-in scalars, directives and introspection types are left out.
+pub fn introspection_to_sdl(json: &str, path: &Path) -> Result<String> {
+    convert(json, path).map(|converted| converted.sdl)
+}
+
+/// SDL converted from an introspection result
+pub(crate) struct Converted {
+    pub sdl: String,
+    /// SDL range of each definition and field, with the JSON range of its name
+    spans: Vec<(Range<usize>, Range<usize>)>,
+}
+
+impl Converted {
+    /// JSON range for the innermost definition containing `sdl_offset`
+    fn json_range(&self, sdl_offset: usize) -> Option<Range<usize>> {
+        self.spans
+            .iter()
+            .filter(|(sdl, _)| sdl.contains(&sdl_offset))
+            .min_by_key(|(sdl, _)| sdl.len())
+            .map(|(_, json)| json.clone())
+    }
+}
+
+pub(crate) fn convert(json: &str, path: &Path) -> Result<Converted> {
+    let root: Root = serde_json::from_str(json).map_err(|e| {
+        let message = e.to_string();
+        let message = message
+            .rsplit_once(" at line ")
+            .map_or(message.as_str(), |(message, _)| message);
+
+        Diagnostics::from(
+            Diagnostic::error(
+                DiagnosticCategory::Schema,
+                format!("Invalid introspection result: {message}"),
+            )
+            .with_location(DiagnosticLocation {
+                file: path.to_path_buf(),
+                line: e.line(),
+                column: e.column(),
+                length: None,
+            }),
+        )
+    })?;
+
+    if let Some(errors) = root.errors.filter(|errors| !errors.is_empty()) {
+        let mut diagnostics = Diagnostics::new();
+        for error in errors {
+            diagnostics.push(Diagnostic::error(
+                DiagnosticCategory::Schema,
+                format!(
+                    "Introspection result '{}' contains an error: {}",
+                    path.display(),
+                    error.message
+                ),
+            ));
+        }
+        return Err(diagnostics);
+    }
+
+    let Some(schema) = root.schema.or(root.data.and_then(|data| data.schema)) else {
+        return Err(Diagnostics::from(Diagnostic::error(
+            DiagnosticCategory::Schema,
+            format!(
+                "'{}' is not an introspection result: expected `__schema` or `data.__schema`",
+                path.display()
+            ),
+        )));
+    };
+
+    let mut converter = Converter {
+        json,
+        path,
+        out: Converted {
+            sdl: String::new(),
+            spans: Vec::new(),
+        },
+        diagnostics: Diagnostics::new(),
+    };
+    converter.schema(&schema);
+
+    if converter.diagnostics.has_errors() {
+        return Err(converter.diagnostics);
+    }
+    Ok(converter.out)
+}
+
+/// Point SDL locations of `diagnostics` from `converted` back into its JSON
+pub(crate) fn remap_diagnostics(
+    diagnostics: &mut Diagnostics,
+    path: &Path,
+    json: &str,
+    converted: &Converted,
+) {
+    for diagnostic in diagnostics.iter_mut() {
+        let Some(location) = diagnostic.location.as_mut() else {
+            continue;
+        };
+        if location.file != path {
+            continue;
+        }
+
+        let sdl_offset = offset_of_line_column(&converted.sdl, location.line, location.column);
+        let Some(range) = converted.json_range(sdl_offset) else {
+            continue;
+        };
+
+        let (line, column) = line_column(json, range.start);
+        location.line = line;
+        location.column = column;
+        location.length = Some(range.len());
+        if diagnostic.inline_source.is_some() {
+            diagnostic.inline_source = Some(json.to_string());
+        }
+    }
+}
+
+fn offset_of_line_column(text: &str, line: usize, column: usize) -> usize {
+    let line_start = text
+        .split_inclusive('\n')
+        .take(line.saturating_sub(1))
+        .map(str::len)
+        .sum::<usize>();
+    let rest = &text[line_start..];
+    rest.char_indices()
+        .nth(column.saturating_sub(1))
+        .map_or(text.len(), |(i, _)| line_start + i)
+}
+
+const BUILTIN_SCALARS: [&str; 5] = ["String", "Int", "Float", "Boolean", "ID"];
+const BUILTIN_DIRECTIVES: [&str; 4] = ["skip", "include", "deprecated", "specifiedBy"];
+const DEFAULT_DEPRECATION_REASON: &str = "No longer supported";
+
+struct Converter<'j> {
+    json: &'j str,
+    path: &'j Path,
+    out: Converted,
+    diagnostics: Diagnostics,
+}
+
+impl Converter<'_> {
+    fn schema(&mut self, schema: &Schema) {
+        let roots = [
+            ("query", &schema.query_type, "Query"),
+            ("mutation", &schema.mutation_type, "Mutation"),
+            ("subscription", &schema.subscription_type, "Subscription"),
+        ];
+        if roots
+            .iter()
+            .any(|(_, root, default)| root.as_ref().is_some_and(|r| r.name != *default))
+        {
+            self.out.sdl.push_str("schema {\n");
+            for (operation, root, _) in roots {
+                if let Some(root) = root {
+                    let _ = writeln!(self.out.sdl, "  {operation}: {}", root.name);
+                }
+            }
+            self.out.sdl.push_str("}\n\n");
+        }
+
+        for directive in &schema.directives {
+            if !BUILTIN_DIRECTIVES.contains(&directive.name) {
+                self.directive(directive);
+            }
+        }
+
+        for ty in &schema.types {
+            if !ty.name.starts_with("__") && !BUILTIN_SCALARS.contains(&ty.name) {
+                self.named_type(ty);
+            }
+        }
+    }
+
+    fn directive(&mut self, directive: &Directive) {
+        let start = self.out.sdl.len();
+        self.description(directive.description.as_deref(), "");
+        let _ = write!(self.out.sdl, "directive @{}", directive.name);
+        self.arguments(&directive.args);
+        if directive.is_repeatable {
+            self.out.sdl.push_str(" repeatable");
+        }
+        let _ = writeln!(self.out.sdl, " on {}", directive.locations.join(" | "));
+        self.out.sdl.push('\n');
+        self.span(start, directive.name);
+    }
+
+    fn named_type(&mut self, ty: &Type) {
+        let start = self.out.sdl.len();
+        self.description(ty.description.as_deref(), "");
+
+        match ty.kind {
+            TypeKind::Scalar => {
+                let _ = write!(self.out.sdl, "scalar {}", ty.name);
+                if let Some(url) = &ty.specified_by_url {
+                    let _ = write!(self.out.sdl, " @specifiedBy(url: {})", string(url));
+                }
+                self.out.sdl.push('\n');
+            }
+            TypeKind::Object | TypeKind::Interface => {
+                let keyword = if ty.kind == TypeKind::Object {
+                    "type"
+                } else {
+                    "interface"
+                };
+                let _ = write!(self.out.sdl, "{keyword} {}", ty.name);
+                self.implements(ty);
+                self.out.sdl.push_str(" {\n");
+                for field in ty.fields.iter().flatten() {
+                    self.field(field);
+                }
+                self.out.sdl.push_str("}\n");
+            }
+            TypeKind::Union => {
+                let members: Vec<&str> = ty
+                    .possible_types
+                    .iter()
+                    .flatten()
+                    .filter_map(|t| t.name.as_deref())
+                    .collect();
+                let _ = writeln!(self.out.sdl, "union {} = {}", ty.name, members.join(" | "));
+            }
+            TypeKind::Enum => {
+                let _ = writeln!(self.out.sdl, "enum {} {{", ty.name);
+                for value in ty.enum_values.iter().flatten() {
+                    let value_start = self.out.sdl.len();
+                    self.description(value.description.as_deref(), "  ");
+                    let _ = write!(self.out.sdl, "  {}", value.name);
+                    self.deprecated(value.is_deprecated, value.deprecation_reason.as_deref());
+                    self.out.sdl.push('\n');
+                    self.span(value_start, value.name);
+                }
+                self.out.sdl.push_str("}\n");
+            }
+            TypeKind::InputObject => {
+                let _ = write!(self.out.sdl, "input {}", ty.name);
+                if ty.is_one_of {
+                    self.out.sdl.push_str(" @oneOf");
+                }
+                self.out.sdl.push_str(" {\n");
+                for field in ty.input_fields.iter().flatten() {
+                    let field_start = self.out.sdl.len();
+                    self.description(field.description.as_deref(), "  ");
+                    self.out.sdl.push_str("  ");
+                    self.input_value(field);
+                    self.out.sdl.push('\n');
+                    self.span(field_start, field.name);
+                }
+                self.out.sdl.push_str("}\n");
+            }
+            TypeKind::List | TypeKind::NonNull => {
+                self.error(ty.name, format!("Type '{}' has a wrapping kind", ty.name));
+            }
+        }
+
+        self.out.sdl.push('\n');
+        self.span(start, ty.name);
+    }
+
+    fn implements(&mut self, ty: &Type) {
+        let interfaces: Vec<&str> = ty
+            .interfaces
+            .iter()
+            .flatten()
+            .filter_map(|t| t.name.as_deref())
+            .collect();
+        if !interfaces.is_empty() {
+            let _ = write!(self.out.sdl, " implements {}", interfaces.join(" & "));
+        }
+    }
+
+    fn field(&mut self, field: &Field) {
+        let start = self.out.sdl.len();
+        self.description(field.description.as_deref(), "  ");
+        let _ = write!(self.out.sdl, "  {}", field.name);
+        self.arguments(&field.args);
+        self.out.sdl.push_str(": ");
+        self.type_ref(&field.ty, field.name);
+        self.deprecated(field.is_deprecated, field.deprecation_reason.as_deref());
+        self.out.sdl.push('\n');
+        self.span(start, field.name);
+    }
+
+    fn arguments(&mut self, args: &[InputValue]) {
+        if args.is_empty() {
+            return;
+        }
+
+        self.out.sdl.push('(');
+        for (i, arg) in args.iter().enumerate() {
+            if i > 0 {
+                self.out.sdl.push_str(", ");
+            }
+            let start = self.out.sdl.len();
+            if let Some(description) = &arg.description {
+                let _ = write!(self.out.sdl, "{} ", string(description));
+            }
+            self.input_value(arg);
+            self.span(start, arg.name);
+        }
+        self.out.sdl.push(')');
+    }
+
+    fn input_value(&mut self, value: &InputValue) {
+        let _ = write!(self.out.sdl, "{}: ", value.name);
+        self.type_ref(&value.ty, value.name);
+        if let Some(default) = &value.default_value {
+            let _ = write!(self.out.sdl, " = {default}");
+        }
+        self.deprecated(value.is_deprecated, value.deprecation_reason.as_deref());
+    }
+
+    fn type_ref(&mut self, ty: &TypeRef, owner: &str) {
+        match (ty.kind, &ty.of_type, &ty.name) {
+            (TypeKind::NonNull, Some(inner), _) => {
+                self.type_ref(inner, owner);
+                self.out.sdl.push('!');
+            }
+            (TypeKind::List, Some(inner), _) => {
+                self.out.sdl.push('[');
+                self.type_ref(inner, owner);
+                self.out.sdl.push(']');
+            }
+            (TypeKind::NonNull | TypeKind::List, None, _) | (_, _, None) => {
+                self.error(owner, format!("Incomplete type reference for '{owner}'"));
+            }
+            (_, _, Some(name)) => self.out.sdl.push_str(name),
+        }
+    }
+
+    fn deprecated(&mut self, is_deprecated: bool, reason: Option<&str>) {
+        if !is_deprecated {
+            return;
+        }
+        match reason {
+            Some(reason) if reason != DEFAULT_DEPRECATION_REASON => {
+                let _ = write!(self.out.sdl, " @deprecated(reason: {})", string(reason));
+            }
+            _ => self.out.sdl.push_str(" @deprecated"),
+        }
+    }
+
+    fn description(&mut self, description: Option<&str>, indent: &str) {
+        if let Some(description) = description {
+            let _ = writeln!(self.out.sdl, "{indent}{}", string(description));
+        }
+    }
+
+    /// Record the SDL written since `start` as coming from `name`, which
+    /// borrows from the JSON source
+    fn span(&mut self, start: usize, name: &str) {
+        let json_start = name.as_ptr() as usize - self.json.as_ptr() as usize;
+        self.out.spans.push((
+            start..self.out.sdl.len(),
+            json_start..json_start + name.len(),
+        ));
+    }
+
+    fn error(&mut self, name: &str, message: String) {
+        let offset = name.as_ptr() as usize - self.json.as_ptr() as usize;
+        let (line, column) = line_column(self.json, offset);
+        self.diagnostics.push(
+            Diagnostic::error(DiagnosticCategory::Schema, message).with_location(
+                DiagnosticLocation {
+                    file: self.path.to_path_buf(),
+                    line,
+                    column,
+                    length: Some(name.len()),
+                },
+            ),
+        );
+    }
+}
+
+/// GraphQL string literal. JSON string escapes are valid GraphQL escapes.
+fn string(value: &str) -> String {
+    serde_json::to_string(value).expect("strings always serialize")
+}
+
+// Introspection result shape. Names borrow from the JSON source so their
+// position can be recovered.
+
+#[derive(Deserialize)]
+struct Root<'a> {
+    #[serde(borrow, default)]
+    data: Option<Data<'a>>,
+    #[serde(borrow, default, rename = "__schema")]
+    schema: Option<Schema<'a>>,
+    #[serde(default)]
+    errors: Option<Vec<ResponseError>>,
+}
+
+#[derive(Deserialize)]
+struct Data<'a> {
+    #[serde(borrow, default, rename = "__schema")]
+    schema: Option<Schema<'a>>,
+}
+
+#[derive(Deserialize)]
+struct ResponseError {
+    message: String,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct Schema<'a> {
+    query_type: Option<RootType>,
+    #[serde(default)]
+    mutation_type: Option<RootType>,
+    #[serde(default)]
+    subscription_type: Option<RootType>,
+    #[serde(borrow)]
+    types: Vec<Type<'a>>,
+    #[serde(borrow, default)]
+    directives: Vec<Directive<'a>>,
+}
+
+#[derive(Deserialize)]
+struct RootType {
+    name: String,
+}
+
+#[derive(Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+enum TypeKind {
+    Scalar,
+    Object,
+    Interface,
+    Union,
+    Enum,
+    InputObject,
+    List,
+    NonNull,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct Type<'a> {
+    kind: TypeKind,
+    name: &'a str,
+    #[serde(default)]
+    description: Option<String>,
+    #[serde(borrow, default)]
+    fields: Option<Vec<Field<'a>>>,
+    #[serde(default)]
+    interfaces: Option<Vec<TypeRef>>,
+    #[serde(default)]
+    possible_types: Option<Vec<TypeRef>>,
+    #[serde(borrow, default)]
+    enum_values: Option<Vec<EnumValue<'a>>>,
+    #[serde(borrow, default)]
+    input_fields: Option<Vec<InputValue<'a>>>,
+    #[serde(default, rename = "specifiedByURL", alias = "specifiedByUrl")]
+    specified_by_url: Option<String>,
+    #[serde(default)]
+    is_one_of: bool,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct Field<'a> {
+    name: &'a str,
+    #[serde(default)]
+    description: Option<String>,
+    #[serde(borrow, default)]
+    args: Vec<InputValue<'a>>,
+    #[serde(rename = "type")]
+    ty: TypeRef,
+    #[serde(default)]
+    is_deprecated: bool,
+    #[serde(default)]
+    deprecation_reason: Option<String>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct InputValue<'a> {
+    name: &'a str,
+    #[serde(default)]
+    description: Option<String>,
+    #[serde(rename = "type")]
+    ty: TypeRef,
+    #[serde(default)]
+    default_value: Option<String>,
+    #[serde(default)]
+    is_deprecated: bool,
+    #[serde(default)]
+    deprecation_reason: Option<String>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct EnumValue<'a> {
+    name: &'a str,
+    #[serde(default)]
+    description: Option<String>,
+    #[serde(default)]
+    is_deprecated: bool,
+    #[serde(default)]
+    deprecation_reason: Option<String>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct TypeRef {
+    kind: TypeKind,
+    #[serde(default)]
+    name: Option<String>,
+    #[serde(default)]
+    of_type: Option<Box<TypeRef>>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct Directive<'a> {
+    name: &'a str,
+    #[serde(default)]
+    description: Option<String>,
+    locations: Vec<String>,
+    #[serde(borrow, default)]
+    args: Vec<InputValue<'a>>,
+    #[serde(default)]
+    is_repeatable: bool,
+}
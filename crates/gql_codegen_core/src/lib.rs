@@ -20,48 +20,71 @@
 //! GenerateResult { files: [...] }
 //! ```
 
+mod analysis;
 pub mod cache;
 mod casing;
 mod codegen;
 mod config;
 pub mod diagnostic;
+mod diff;
 mod documents;
 mod error;
 mod extract;
+mod federation;
 mod generators;
+mod introspection;
 pub mod reader;
+mod remote;
 mod run_codegen;
 pub mod runner;
 mod schema;
+mod selection;
 mod source_cache;
 pub mod test_utils;
 pub mod timing;
 pub mod types;
 mod validation;
+mod walk;
 pub mod writer;
 
 // Public API - Main entry points
 pub use codegen::{
-    GenerateCachedResult, GenerateInput, GenerateResult, GeneratedFile, generate, generate_cached,
-    generate_from_input,
+    GenerateCachedResult, GenerateInput, GenerateResult, GeneratedFile, expand_output_documents,
+    generate, generate_cached, generate_from_input, load_config_schema,
 };
 pub use config::{
-    AvoidOptionals, CodegenConfig, DeclarationKind, GeneratorConfig, GeneratorOptions, GraphqlTag,
-    NamingCase, NamingConvention, NamingConventionConfig, OutputConfig, ScalarConfig,
-    StringOrArray, TypenamePolicy,
+    AnonymousOperations, AvoidOptionals, CodegenConfig, ComplexityConfig, DeclarationKind,
+    DeprecatedUsage, DocumentFilter, DocumentValidation, Federation, GeneratorConfig,
+    GeneratorOptions, GraphqlTag, HttpMethod, NamingCase, NamingConvention, NamingConventionConfig,
+    OperationKind, OutputConfig, PluckConfig, RemoteSchemaConfig, ScalarConfig, ScalarMapping,
+    SchemaValidation, StringOrArray, TypenamePolicy,
 };
 pub use diagnostic::{Diagnostic, DiagnosticCategory, DiagnosticLocation, Diagnostics, Severity};
+pub use diff::{ChangeKind, ChangeSeverity, SchemaChange, SchemaDiff, schema_diff};
 pub use error::Result;
 
 // Public API - Building blocks for custom I/O handling
 // Use these when you need control over file loading, caching, etc.
+pub use analysis::{
+    ComplexityReport, FieldCoverage, FieldStats, FieldUsage, OperationComplexity,
+    analyze_documents, check_deprecated_usage, field_coverage, operation_complexity,
+};
 pub use documents::{
-    CollectedDocuments, ParsedFragment, ParsedOperation, collect_documents, expand_document_globs,
-    load_sources, load_sources_from_paths,
+    CollectedDocuments, ParsedExtension, ParsedFragment, ParsedOperation, collect_documents,
+    collect_documents_in, collect_documents_with, expand_document_globs, load_sources,
+    load_sources_from_paths,
+};
+pub use extract::{ExtractConfig, ExtractStrategy, Extracted};
+pub use introspection::introspection_to_sdl;
+pub use remote::{INTROSPECTION_QUERY, fetch_remote_schema};
+pub use schema::{
+    SchemaOptions, expand_schema_paths, extend_schema, load_schema, load_schema_from_contents,
+    load_schema_from_contents_with, resolve_schema_paths,
 };
-pub use extract::{ExtractConfig, Extracted};
-pub use schema::{load_schema, load_schema_from_contents, resolve_schema_paths};
+pub use selection::select_documents;
 pub use source_cache::SourceCache;
+pub use validation::validate_documents;
+pub use walk::DEFAULT_IGNORE;
 pub use writer::{
     FsWriter, MemoryWriter, NoopWriter, StdoutWriter, WriteResult, Writer, write_outputs,
 };
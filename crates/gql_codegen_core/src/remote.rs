@@ -0,0 +1,331 @@
+//! Remote schemas loaded with an HTTP introspection query
+//!
+//! Requests are sent with `ureq` over rustls, behind the `remote` feature.
+//! Builds without it, such as the WASM package, report URLs as unsupported.
+
+use std::collections::HashMap;
+use std::path::Path;
+use std::time::Duration;
+
+use crate::config::{HttpMethod, RemoteSchemaConfig};
+use crate::diagnostic::{Diagnostic, DiagnosticCategory, Diagnostics};
+use crate::error::Result;
+use crate::introspection::introspection_to_sdl;
+
+/// Default request timeout
+const DEFAULT_TIMEOUT_MS: u64 = 10_000;
+
+/// The standard introspection query, as produced by graphql-js
+/// `getIntrospectionQuery()` with default options
+pub const INTROSPECTION_QUERY: &str = r#"query IntrospectionQuery {
+  __schema {
+    queryType { name }
+    mutationType { name }
+    subscriptionType { name }
+    types { ...FullType }
+    directives {
+      name
+      description
+      locations
+      args { ...InputValue }
+    }
+  }
+}
+
+fragment FullType on __Type {
+  kind
+  name
+  description
+  fields(includeDeprecated: true) {
+    name
+    description
+    args { ...InputValue }
+    type { ...TypeRef }
+    isDeprecated
+    deprecationReason
+  }
+  inputFields { ...InputValue }
+  interfaces { ...TypeRef }
+  enumValues(includeDeprecated: true) {
+    name
+    description
+    isDeprecated
+    deprecationReason
+  }
+  possibleTypes { ...TypeRef }
+}
+
+fragment InputValue on __InputValue {
+  name
+  description
+  type { ...TypeRef }
+  defaultValue
+}
+
+fragment TypeRef on __Type {
+  kind
+  name
+  ofType {
+    kind
+    name
+    ofType {
+      kind
+      name
+      ofType {
+        kind
+        name
+        ofType {
+          kind
+          name
+          ofType {
+            kind
+            name
+            ofType {
+              kind
+              name
+              ofType {
+                kind
+                name
+              }
+            }
+          }
+        }
+      }
+    }
+  }
+}
+"#;
+
+/// Whether a `schema` entry is a URL rather than a path or glob
+pub(crate) fn is_url(pattern: &str) -> bool {
+    let lower = pattern.get(..8).unwrap_or(pattern).to_ascii_lowercase();
+    lower.starts_with("http://") || lower.starts_with("https://")
+}
+
+/// Run the introspection query against `url` and convert the result to SDL
+pub fn fetch_remote_schema(url: &str, config: &RemoteSchemaConfig) -> Result<String> {
+    let fail = |message: String| {
+        Diagnostics::from(Diagnostic::error(
+            DiagnosticCategory::Schema,
+            format!("Failed to fetch schema from '{url}': {message}"),
+        ))
+    };
+
+    if !is_url(url) {
+        return Err(fail(
+            "only http:// and https:// URLs are supported".to_string(),
+        ));
+    }
+
+    let mut headers = Vec::with_capacity(config.headers.len());
+    for (name, value) in &config.headers {
+        let value = expand_env(value).map_err(|var| {
+            fail(format!(
+                "environment variable '{var}' in header '{name}' is not set"
+            ))
+        })?;
+        // A line break would end the header and smuggle in others
+        if value.contains(['\r', '\n']) {
+            return Err(fail(format!("header '{name}' contains a line break")));
+        }
+        headers.push((name.as_str(), value));
+    }
+
+    let timeout = Duration::from_millis(config.timeout.unwrap_or(DEFAULT_TIMEOUT_MS));
+    let response = send(url, config.method, &headers, timeout).map_err(|e| match e {
+        SendError::TimedOut => fail(format!("timed out after {}ms", timeout.as_millis())),
+        SendError::Failed(message) => fail(message),
+    })?;
+
+    if !(200..300).contains(&response.status) {
+        let body = response.body.trim();
+        let detail = if body.is_empty() || body.len() > 200 {
+            String::new()
+        } else {
+            format!(": {body}")
+        };
+        return Err(fail(format!("HTTP {}{detail}", response.status)));
+    }
+
+    introspection_to_sdl(&response.body, Path::new(url))
+}
+
+/// Fetch each URL, falling back to the SDL from a previous run when the
+/// server can't be reached (with a warning)
+pub(crate) fn fetch_remote_schemas(
+    urls: &[&str],
+    config: &RemoteSchemaConfig,
+    cached: Option<&HashMap<String, String>>,
+    diagnostics: &mut Diagnostics,
+) -> Result<HashMap<String, String>> {
+    let mut schemas = HashMap::with_capacity(urls.len());
+
+    for url in urls {
+        let sdl = match fetch_remote_schema(url, config) {
+            Ok(sdl) => sdl,
+            Err(errors) => {
+                let Some(sdl) = cached.and_then(|cached| cached.get(*url)) else {
+                    return Err(errors);
+                };
+
+                let reason = errors
+                    .iter()
+                    .next()
+                    .map_or_else(String::new, |e| format!(": {}", e.message));
+                diagnostics.push(Diagnostic::warning(
+                    DiagnosticCategory::Schema,
+                    format!("Using cached schema for '{url}'{reason}"),
+                ));
+                sdl.clone()
+            }
+        };
+        schemas.insert(url.to_string(), sdl);
+    }
+
+    Ok(schemas)
+}
+
+/// Expand `${VAR}` and `$VAR` references, returning the first unset name
+fn expand_env(value: &str) -> std::result::Result<String, String> {
+    let mut out = String::with_capacity(value.len());
+    let mut rest = value;
+
+    while let Some(dollar) = rest.find('$') {
+        out.push_str(&rest[..dollar]);
+        let after = &rest[dollar + 1..];
+
+        let (name, consumed) = if let Some(braced) = after.strip_prefix('{') {
+            match braced.find('}') {
+                Some(end) => (&braced[..end], end + 2),
+                None => ("", 0),
+            }
+        } else {
+            let end = after
+                .find(|c: char| !(c.is_ascii_alphanumeric() || c == '_'))
+                .unwrap_or(after.len());
+            (&after[..end], end)
+        };
+
+        if name.is_empty() {
+            out.push('$');
+            rest = after;
+            continue;
+        }
+
+        out.push_str(&std::env::var(name).map_err(|_| name.to_string())?);
+        rest = &after[consumed..];
+    }
+
+    out.push_str(rest);
+    Ok(out)
+}
+
+/// Largest introspection result read
+#[cfg(feature = "remote")]
+const MAX_RESPONSE_BYTES: u64 = 64 * 1024 * 1024;
+
+struct Response {
+    status: u16,
+    body: String,
+}
+
+#[cfg_attr(not(feature = "remote"), allow(dead_code))]
+enum SendError {
+    TimedOut,
+    Failed(String),
+}
+
+/// Send the introspection query to `url`
+#[cfg(feature = "remote")]
+fn send(
+    url: &str,
+    method: HttpMethod,
+    headers: &[(&str, String)],
+    timeout: Duration,
+) -> std::result::Result<Response, SendError> {
+    let agent = ureq::Agent::config_builder()
+        .timeout_global(Some(timeout))
+        // Error statuses are reported with their body
+        .http_status_as_error(false)
+        .user_agent("sgc")
+        .build()
+        .new_agent();
+
+    let result = match method {
+        HttpMethod::Get => with_headers(agent.get(get_url(url)), headers).call(),
+        HttpMethod::Post => {
+            let body = serde_json::json!({
+                "query": INTROSPECTION_QUERY,
+                "operationName": "IntrospectionQuery",
+            });
+            with_headers(agent.post(url), headers)
+                .header("Content-Type", "application/json")
+                .send(body.to_string())
+        }
+    };
+
+    let failed = |e: ureq::Error| match e {
+        ureq::Error::Timeout(_) => SendError::TimedOut,
+        ureq::Error::Io(e) if e.kind() == std::io::ErrorKind::TimedOut => SendError::TimedOut,
+        e => SendError::Failed(e.to_string()),
+    };
+    let mut response = result.map_err(failed)?;
+    let body = response
+        .body_mut()
+        .with_config()
+        .limit(MAX_RESPONSE_BYTES)
+        .read_to_string()
+        .map_err(failed)?;
+
+    Ok(Response {
+        status: response.status().as_u16(),
+        body,
+    })
+}
+
+#[cfg(not(feature = "remote"))]
+fn send(
+    _url: &str,
+    _method: HttpMethod,
+    _headers: &[(&str, String)],
+    _timeout: Duration,
+) -> std::result::Result<Response, SendError> {
+    Err(SendError::Failed(
+        "this build can't fetch remote schemas".to_string(),
+    ))
+}
+
+#[cfg(feature = "remote")]
+fn with_headers<B>(
+    mut request: ureq::RequestBuilder<B>,
+    headers: &[(&str, String)],
+) -> ureq::RequestBuilder<B> {
+    request = request.header("Accept", "application/json");
+    for (name, value) in headers {
+        request = request.header(*name, value);
+    }
+    request
+}
+
+/// URL with the query in the query string, for GET requests
+#[cfg(feature = "remote")]
+fn get_url(url: &str) -> String {
+    let separator = if url.contains('?') { '&' } else { '?' };
+    format!(
+        "{url}{separator}operationName=IntrospectionQuery&query={}",
+        percent_encode(INTROSPECTION_QUERY)
+    )
+}
+
+#[cfg(feature = "remote")]
+fn percent_encode(value: &str) -> String {
+    let mut out = String::with_capacity(value.len() * 3);
+    for byte in value.bytes() {
+        if byte.is_ascii_alphanumeric() || matches!(byte, b'-' | b'_' | b'.' | b'~') {
+            out.push(byte as char);
+        } else {
+            out.push_str(&format!("%{byte:02X}"));
+        }
+    }
+    out
+}
@@ -1,69 +1,416 @@
 //! Schema loading and validation
 
+use std::collections::{BTreeMap, HashSet};
+use std::ops::Range;
 use std::path::{Path, PathBuf};
 
-use apollo_compiler::Schema;
+use apollo_compiler::ast::{self, DirectiveDefinition, DirectiveLocation};
+use apollo_compiler::parser::FileId;
+use apollo_compiler::schema::{ExtendedType, ScalarType, SchemaBuilder};
 use apollo_compiler::validation::Valid;
+use apollo_compiler::{Name, Node, Schema, name};
 
+use crate::config::{Federation, SchemaValidation};
 use crate::diagnostic::{Diagnostic, DiagnosticCategory, Diagnostics, Severity};
+use crate::documents::CollectedDocuments;
 use crate::error::Result;
+use crate::extract::{ExtractConfig, ExtractStrategy, extract_with, interpolations};
+use crate::source_cache::SourceCache;
+use crate::walk::{self, default_ignore};
+use crate::{federation, introspection};
 
 /// Load and validate a GraphQL schema from one or more file paths.
+///
+/// `.json` files are read as introspection results.
 pub fn load_schema(paths: &[PathBuf]) -> Result<Valid<Schema>> {
-    let mut builder = Schema::builder();
+    let mut files = Vec::with_capacity(paths.len());
 
     for path in paths {
         let content = std::fs::read_to_string(path).map_err(|e| {
-            Diagnostics::from(
-                Diagnostic::error(
-                    DiagnosticCategory::Schema,
-                    format!("Failed to read schema '{}': {}", path.display(), e),
-                ),
-            )
+            Diagnostics::from(Diagnostic::error(
+                DiagnosticCategory::Schema,
+                format!("Failed to read schema '{}': {}", path.display(), e),
+            ))
         })?;
 
-        builder = builder.parse(content, path);
+        files.push((path.clone(), content));
     }
 
-    let schema = builder
-        .build()
-        .map_err(|e| Diagnostics::from_apollo(&e.errors, Severity::Error, DiagnosticCategory::Schema))?;
-
-    schema
-        .validate()
-        .map_err(|e| Diagnostics::from_apollo(&e.errors, Severity::Error, DiagnosticCategory::Schema))
+    load_schema_from_contents(&files)
 }
 
 /// Load and validate a GraphQL schema from pre-loaded content.
 ///
 /// Use this when you've already read the schema files (e.g., for caching)
-/// to avoid reading them twice.
+/// to avoid reading them twice. `.json` files are read as introspection
+/// results, and errors in them point back into the JSON.
 pub fn load_schema_from_contents(files: &[(PathBuf, String)]) -> Result<Valid<Schema>> {
+    load_schema_from_contents_with(
+        files,
+        &ExtractConfig::default(),
+        SchemaOptions::default(),
+        &mut Diagnostics::new(),
+    )
+}
+
+/// How a schema is built from its sources
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct SchemaOptions {
+    /// Adds the definitions a subgraph schema relies on
+    pub federation: Option<Federation>,
+    /// Whether validation errors fail loading or become warnings
+    pub validation: SchemaValidation,
+}
+
+/// Like [`load_schema_from_contents`], but SDL in code files (e.g. `gql`
+/// tagged `typeDefs` in `.ts`) is found with `extract_config`, and the
+/// schema is built per `options`. Validation errors downgraded by lenient
+/// validation are added to `warnings`.
+pub fn load_schema_from_contents_with(
+    files: &[(PathBuf, String)],
+    extract_config: &ExtractConfig,
+    options: SchemaOptions,
+    warnings: &mut Diagnostics,
+) -> Result<Valid<Schema>> {
     let mut builder = Schema::builder();
+    let mut converted = Vec::new();
+    let mut diagnostics = Diagnostics::new();
 
     for (path, content) in files {
-        builder = builder.parse(content, path);
+        if introspection::is_introspection_path(path) {
+            match introspection::convert(content, path) {
+                Ok(schema) => {
+                    builder = builder.parse(&schema.sdl, path);
+                    converted.push((path, content, schema));
+                }
+                Err(e) => diagnostics.extend(e),
+            }
+            continue;
+        }
+
+        match extract_config.strategy_for(path) {
+            Some(strategy) if strategy != ExtractStrategy::Graphql => {
+                let sdl = embedded_sdl(content, strategy, extract_config);
+                builder = builder.parse(sdl, path);
+            }
+            _ => builder = builder.parse(content, path),
+        }
     }
 
-    let schema = builder
-        .build()
-        .map_err(|e| Diagnostics::from_apollo(&e.errors, Severity::Error, DiagnosticCategory::Schema))?;
+    if diagnostics.has_errors() {
+        return Err(diagnostics);
+    }
 
-    schema
-        .validate()
-        .map_err(|e| Diagnostics::from_apollo(&e.errors, Severity::Error, DiagnosticCategory::Schema))
+    let remap = |diagnostics: &mut Diagnostics| {
+        for (path, json, schema) in &converted {
+            introspection::remap_diagnostics(diagnostics, path, json, schema);
+        }
+    };
+    let mut downgraded = Diagnostics::new();
+    let result = build_schema(builder, options, &mut downgraded).map_err(|mut diagnostics| {
+        remap(&mut diagnostics);
+        diagnostics
+    });
+    remap(&mut downgraded);
+    warnings.extend(downgraded);
+    result
 }
 
-/// Helper to resolve schema paths from config (convenience for simple cases)
-pub fn resolve_schema_paths(patterns: &[&str], base_dir: Option<&Path>) -> Vec<PathBuf> {
-    patterns
-        .iter()
-        .map(|p| {
-            if let Some(base) = base_dir {
-                base.join(p)
+/// SDL embedded in a code file. Everything outside the extracted templates,
+/// and `${}` interpolations inside them, is blanked so line and column of
+/// schema errors still match the file.
+fn embedded_sdl(source: &str, strategy: ExtractStrategy, config: &ExtractConfig) -> String {
+    let mut keep: Vec<Range<usize>> = Vec::new();
+    for block in extract_with(strategy, source, config) {
+        let start = block.text.as_ptr() as usize - source.as_ptr() as usize;
+        let mut cursor = start;
+        for interpolation in interpolations(block.text) {
+            keep.push(cursor..start + interpolation.start);
+            cursor = start + interpolation.end;
+        }
+        keep.push(cursor..start + block.text.len());
+    }
+
+    blank_outside(source, &keep)
+}
+
+/// Replace every character outside the sorted `keep` ranges with a space,
+/// keeping line breaks.
+fn blank_outside(source: &str, keep: &[Range<usize>]) -> String {
+    let mut kept = keep.iter().peekable();
+    source
+        .char_indices()
+        .map(|(i, c)| {
+            while kept.next_if(|range| range.end <= i).is_some() {}
+            let inside = kept.peek().is_some_and(|range| range.contains(&i));
+            if inside || matches!(c, '\n' | '\r') {
+                c
             } else {
-                Path::new(p).to_path_buf()
+                ' '
             }
         })
         .collect()
 }
+
+/// Merge the type-system definitions found in documents (Apollo client-side
+/// schema) into `schema`, for generating types of `@client` fields.
+///
+/// The schema is rebuilt from its sources with each document's definitions
+/// parsed in place, so errors point into the document. Files that are
+/// themselves schema sources, such as a documents glob also matching the
+/// schema, are skipped. A `@client` directive is defined when the schema
+/// has none. Returns `schema` as is when the documents extend nothing.
+pub fn extend_schema(
+    schema: Valid<Schema>,
+    documents: &CollectedDocuments,
+    cache: &SourceCache,
+    options: SchemaOptions,
+    warnings: &mut Diagnostics,
+) -> Result<Valid<Schema>> {
+    // Definitions in files loaded as schema sources are already part of it
+    let schema_paths: HashSet<&Path> = schema
+        .sources
+        .values()
+        .map(|source| source.path())
+        .collect();
+    let mut ranges: BTreeMap<usize, Vec<Range<usize>>> = BTreeMap::new();
+    for extension in &documents.schema_extensions {
+        let Some((path, _)) = cache.get(extension.source_idx) else {
+            continue;
+        };
+        if schema_paths.contains(path) {
+            continue;
+        }
+        if let Some(start) = cache.offset_of(extension.source_idx, extension.text) {
+            ranges
+                .entry(extension.source_idx)
+                .or_default()
+                .push(start..start + extension.text.len());
+        }
+    }
+    if ranges.is_empty() {
+        return Ok(schema);
+    }
+
+    let mut builder = Schema::builder();
+    for (id, source) in schema.sources.iter() {
+        if *id != FileId::BUILT_IN {
+            builder = builder.parse(source.source_text(), source.path());
+        }
+    }
+    if !schema.directive_definitions.contains_key("client") {
+        builder = builder.parse(CLIENT_DIRECTIVE, "client.graphql");
+    }
+
+    for (idx, mut keep) in ranges {
+        keep.sort_by_key(|range| range.start);
+        if let Some((path, source)) = cache.get(idx) {
+            builder = builder.parse(blank_outside(source, &keep), path);
+        }
+    }
+
+    build_schema(builder, options, warnings)
+}
+
+const CLIENT_DIRECTIVE: &str =
+    "directive @client(always: Boolean) on FIELD | FRAGMENT_DEFINITION | INLINE_FRAGMENT";
+
+/// Build and validate the schema parsed so far. With lenient validation,
+/// recoverable errors are pushed to `warnings` and the partial schema is
+/// used as if it were valid, once references to undefined types are pruned.
+fn build_schema(
+    builder: SchemaBuilder,
+    options: SchemaOptions,
+    warnings: &mut Diagnostics,
+) -> Result<Valid<Schema>> {
+    // Subgraphs extend entities defined in other subgraphs
+    let builder = match options.federation {
+        Some(_) => builder.adopt_orphan_extensions(),
+        None => builder,
+    };
+    let lenient = options.validation == SchemaValidation::Lenient;
+
+    let mut schema = match builder.build() {
+        Ok(schema) => schema,
+        Err(e) if lenient && is_recoverable(&e.partial) => {
+            warnings.extend(Diagnostics::from_apollo(
+                &e.errors,
+                Severity::Warning,
+                DiagnosticCategory::Schema,
+            ));
+            e.partial
+        }
+        Err(e) => {
+            return Err(Diagnostics::from_apollo(
+                &e.errors,
+                Severity::Error,
+                DiagnosticCategory::Schema,
+            ));
+        }
+    };
+
+    add_one_of_directive(&mut schema);
+    if let Some(federation) = options.federation {
+        federation::add_definitions(&mut schema, federation);
+    }
+
+    match schema.validate() {
+        Ok(schema) => Ok(schema),
+        Err(e) if lenient => {
+            warnings.extend(Diagnostics::from_apollo(
+                &e.errors,
+                Severity::Warning,
+                DiagnosticCategory::Schema,
+            ));
+            let mut schema = e.partial;
+            prune_undefined_types(&mut schema);
+            Ok(Valid::assume_valid(schema))
+        }
+        Err(e) => Err(Diagnostics::from_apollo(
+            &e.errors,
+            Severity::Error,
+            DiagnosticCategory::Schema,
+        )),
+    }
+}
+
+/// Whether `schema`, built despite errors, is still complete enough to
+/// generate from. Syntax errors and parser limits drop definitions, and are
+/// the only errors parsing its sources on their own reports.
+fn is_recoverable(schema: &Schema) -> bool {
+    schema
+        .sources
+        .values()
+        .all(|source| ast::Document::parse(source.source_text(), source.path()).is_ok())
+}
+
+/// Drop references to types a leniently built `schema` never defines, so
+/// generators only see names they can render. Union members and implemented
+/// interfaces are removed; other references, and unions left without members,
+/// become scalars and render as the default scalar type.
+fn prune_undefined_types(schema: &mut Schema) {
+    let objects: HashSet<Name> = schema
+        .types
+        .iter()
+        .filter(|(_, ty)| ty.is_object())
+        .map(|(name, _)| name.clone())
+        .collect();
+    let interfaces: HashSet<Name> = schema
+        .types
+        .iter()
+        .filter(|(_, ty)| ty.is_interface())
+        .map(|(name, _)| name.clone())
+        .collect();
+    let mut referenced = Vec::new();
+    let mut stubs = Vec::new();
+
+    for (name, ty) in schema.types.iter_mut() {
+        match ty {
+            ExtendedType::Object(object) => {
+                let object = object.make_mut();
+                object
+                    .implements_interfaces
+                    .retain(|i| interfaces.contains(&i.name));
+                for field in object.fields.values() {
+                    referenced.push(field.ty.inner_named_type().clone());
+                    referenced.extend(
+                        field
+                            .arguments
+                            .iter()
+                            .map(|a| a.ty.inner_named_type().clone()),
+                    );
+                }
+            }
+            ExtendedType::Interface(interface) => {
+                let interface = interface.make_mut();
+                interface
+                    .implements_interfaces
+                    .retain(|i| interfaces.contains(&i.name));
+                for field in interface.fields.values() {
+                    referenced.push(field.ty.inner_named_type().clone());
+                    referenced.extend(
+                        field
+                            .arguments
+                            .iter()
+                            .map(|a| a.ty.inner_named_type().clone()),
+                    );
+                }
+            }
+            ExtendedType::Union(union) => {
+                let union = union.make_mut();
+                union.members.retain(|m| objects.contains(&m.name));
+                if union.members.is_empty() {
+                    stubs.push(name.clone());
+                }
+            }
+            ExtendedType::InputObject(input) => {
+                referenced.extend(
+                    input
+                        .fields
+                        .values()
+                        .map(|f| f.ty.inner_named_type().clone()),
+                );
+            }
+            _ => {}
+        }
+    }
+
+    stubs.extend(
+        referenced
+            .into_iter()
+            .filter(|name| !schema.types.contains_key(name)),
+    );
+    for name in stubs {
+        schema.types.insert(
+            name.clone(),
+            ExtendedType::Scalar(Node::new(ScalarType {
+                description: None,
+                name,
+                directives: Default::default(),
+            })),
+        );
+    }
+}
+
+/// `@oneOf` is part of the spec but not predefined by apollo-compiler, and
+/// schemas printed by graphql-js omit it, so define it when missing.
+fn add_one_of_directive(schema: &mut Schema) {
+    if schema.directive_definitions.contains_key("oneOf") {
+        return;
+    }
+
+    schema.directive_definitions.insert(
+        name!("oneOf"),
+        Node::new(DirectiveDefinition {
+            description: Some(
+                "Indicates exactly one field must be supplied and this field must not be `null`."
+                    .into(),
+            ),
+            name: name!("oneOf"),
+            arguments: Vec::new(),
+            repeatable: false,
+            locations: vec![DirectiveLocation::InputObject],
+        }),
+    );
+}
+
+/// Helper to resolve schema paths from config (convenience for simple cases)
+///
+/// Glob patterns are expanded like document globs, skipping
+/// [`DEFAULT_IGNORE`](crate::DEFAULT_IGNORE) names. Invalid patterns are kept
+/// as plain paths so loading reports them.
+pub fn resolve_schema_paths(patterns: &[&str], base_dir: Option<&Path>) -> Vec<PathBuf> {
+    let base = base_dir.unwrap_or(Path::new(""));
+    expand_schema_paths(patterns, base, &default_ignore())
+        .unwrap_or_else(|_| patterns.iter().map(|p| base.join(p)).collect())
+}
+
+/// Expand schema path patterns. Plain paths are kept even when missing, so
+/// loading reports them.
+pub fn expand_schema_paths(
+    patterns: &[&str],
+    base_dir: &Path,
+    ignore: &[String],
+) -> Result<Vec<PathBuf>> {
+    walk::expand_paths(patterns, base_dir, ignore, true, DiagnosticCategory::Schema)
+}
@@ -0,0 +1,160 @@
+//! Per-output selection of operations and fragments
+
+use std::collections::HashSet;
+use std::path::{Component, Path, PathBuf};
+
+use apollo_compiler::Name;
+use globset::{Glob, GlobBuilder, GlobSet, GlobSetBuilder};
+
+use crate::analysis::collect_spreads;
+use crate::config::{DocumentFilter, OperationKind};
+use crate::diagnostic::{Diagnostic, DiagnosticCategory, Diagnostics};
+use crate::documents::CollectedDocuments;
+use crate::error::Result;
+use crate::source_cache::SourceCache;
+
+/// Select the operations of an output and the fragments they need.
+///
+/// Operations are kept when their file is in `sources` (any file when
+/// `None`), they match `include` and they don't match `exclude`. Without
+/// filters, all fragments from `sources` are kept as well. Fragments spread
+/// by kept operations are always pulled in, wherever they're defined.
+pub fn select_documents<'a>(
+    documents: &CollectedDocuments<'a>,
+    cache: &SourceCache,
+    sources: Option<&HashSet<PathBuf>>,
+    include: Option<&DocumentFilter>,
+    exclude: Option<&DocumentFilter>,
+) -> Result<CollectedDocuments<'a>> {
+    let include = include.map(CompiledFilter::new).transpose()?;
+    let exclude = exclude.map(CompiledFilter::new).transpose()?;
+    let in_sources = |source_idx: usize| {
+        sources.is_none_or(|sources| {
+            cache
+                .get(source_idx)
+                .is_some_and(|(path, _)| sources.contains(path))
+        })
+    };
+
+    let mut selected = CollectedDocuments::default();
+
+    for (name, operation) in &documents.operations {
+        if !in_sources(operation.source_idx) {
+            continue;
+        }
+
+        let kind = OperationKind::from(operation.definition.operation_type);
+        let path = operation.file_path(cache);
+        if include
+            .as_ref()
+            .is_some_and(|f| !f.matches(name, kind, path))
+            || exclude
+                .as_ref()
+                .is_some_and(|f| f.matches(name, kind, path))
+        {
+            continue;
+        }
+
+        selected.operations.insert(name.clone(), operation.clone());
+    }
+
+    let mut pending: Vec<Name> = Vec::new();
+    for operation in selected.operations.values() {
+        collect_spreads(&operation.definition.selection_set, &mut |spread| {
+            pending.push(spread.fragment_name.clone());
+        });
+    }
+
+    if include.is_none() && exclude.is_none() {
+        pending.extend(
+            documents
+                .fragments
+                .iter()
+                .filter(|(_, fragment)| in_sources(fragment.source_idx))
+                .map(|(name, _)| name.clone()),
+        );
+    }
+
+    let mut needed: HashSet<Name> = HashSet::new();
+    while let Some(name) = pending.pop() {
+        if !needed.insert(name.clone()) {
+            continue;
+        }
+        if let Some(fragment) = documents.fragments.get(&name) {
+            collect_spreads(&fragment.definition.selection_set, &mut |spread| {
+                pending.push(spread.fragment_name.clone());
+            });
+        }
+    }
+
+    // Keep the collection order so output is stable
+    selected.fragments = documents
+        .fragments
+        .iter()
+        .filter(|(name, _)| needed.contains(*name))
+        .map(|(name, fragment)| (name.clone(), fragment.clone()))
+        .collect();
+
+    Ok(selected)
+}
+
+struct CompiledFilter<'f> {
+    names: Option<GlobSet>,
+    types: &'f [OperationKind],
+    paths: Option<GlobSet>,
+}
+
+impl<'f> CompiledFilter<'f> {
+    fn new(filter: &'f DocumentFilter) -> Result<Self> {
+        Ok(Self {
+            names: glob_set(&filter.names, false)?,
+            types: &filter.types,
+            paths: glob_set(&filter.paths, true)?,
+        })
+    }
+
+    fn matches(&self, name: &Name, kind: OperationKind, path: &Path) -> bool {
+        self.names
+            .as_ref()
+            .is_none_or(|n| n.is_match(name.as_str()))
+            && (self.types.is_empty() || self.types.contains(&kind))
+            && self
+                .paths
+                .as_ref()
+                .is_none_or(|p| p.is_match(without_current_dir(path)))
+    }
+}
+
+fn glob_set(patterns: &[String], literal_separator: bool) -> Result<Option<GlobSet>> {
+    if patterns.is_empty() {
+        return Ok(None);
+    }
+
+    let invalid = |pattern: &str, e: globset::Error| {
+        Diagnostics::from(Diagnostic::error(
+            DiagnosticCategory::Config,
+            format!("Invalid filter pattern '{pattern}': {e}"),
+        ))
+    };
+
+    let mut builder = GlobSetBuilder::new();
+    for pattern in patterns {
+        let glob: Glob = GlobBuilder::new(pattern)
+            .literal_separator(literal_separator)
+            .build()
+            .map_err(|e| invalid(pattern, e))?;
+        builder.add(glob);
+    }
+    builder
+        .build()
+        .map(Some)
+        .map_err(|e| invalid(&patterns.join(", "), e))
+}
+
+/// Source paths are relative to the working directory (`./src/a.ts`), while
+/// filter globs are written without the leading `./`
+fn without_current_dir(path: &Path) -> PathBuf {
+    path.components()
+        .filter(|c| !matches!(c, Component::CurDir))
+        .collect()
+}
@@ -1,6 +1,18 @@
 use std::collections::HashMap;
 use std::path::{Path, PathBuf};
 
+use crate::diagnostic::DiagnosticLocation;
+
+/// 1-based line and column (in characters) of a byte offset in `text`.
+///
+/// Panics if `offset` is out of bounds or not on a character boundary.
+pub(crate) fn line_column(text: &str, offset: usize) -> (usize, usize) {
+    let before = &text[..offset];
+    let line = before.matches('\n').count() + 1;
+    let line_start = before.rfind('\n').map_or(0, |i| i + 1);
+    (line, before[line_start..].chars().count() + 1)
+}
+
 /// Cache of source file contents - keeps sources alive for borrowing
 #[derive(Debug, Default)]
 pub struct SourceCache {
@@ -8,6 +20,8 @@ pub struct SourceCache {
     files: Vec<(PathBuf, String)>,
     /// Path → index lookup for O(1) access by file path
     path_index: HashMap<PathBuf, usize>,
+    /// Index → indices of the files it `#import`s
+    imports: HashMap<usize, Vec<usize>>,
 }
 
 impl SourceCache {
@@ -15,6 +29,7 @@ impl SourceCache {
         Self {
             files: Vec::new(),
             path_index: HashMap::new(),
+            imports: HashMap::new(),
         }
     }
 
@@ -22,6 +37,7 @@ impl SourceCache {
         Self {
             files: Vec::with_capacity(capacity),
             path_index: HashMap::with_capacity(capacity),
+            imports: HashMap::new(),
         }
     }
 
@@ -39,12 +55,55 @@ impl SourceCache {
         self.files.get(idx).map(|(p, c)| (p.as_path(), c.as_str()))
     }
 
+    /// Record that the file at `idx` imports the file at `imported`
+    pub(crate) fn add_import(&mut self, idx: usize, imported: usize) {
+        self.imports.entry(idx).or_default().push(imported);
+    }
+
+    /// Indices of the files the file at `idx` imports directly
+    pub(crate) fn imports(&self, idx: usize) -> &[usize] {
+        self.imports.get(&idx).map_or(&[], Vec::as_slice)
+    }
+
     /// Look up source text by file path
     pub fn get_by_path(&self, path: &Path) -> Option<&str> {
         let &idx = self.path_index.get(path)?;
         self.files.get(idx).map(|(_, c)| c.as_str())
     }
 
+    /// Byte offset of `text` within the file at `idx`.
+    ///
+    /// Only works for slices borrowed from the cached contents (as extracted
+    /// documents are), returns `None` for anything else.
+    pub fn offset_of(&self, idx: usize, text: &str) -> Option<usize> {
+        let (_, content) = self.get(idx)?;
+        let offset = (text.as_ptr() as usize).checked_sub(content.as_ptr() as usize)?;
+
+        (offset + text.len() <= content.len()).then_some(offset)
+    }
+
+    /// 1-based line and column (in characters) of a byte offset in the file
+    /// at `idx`.
+    pub fn line_column(&self, idx: usize, offset: usize) -> Option<(usize, usize)> {
+        let (_, content) = self.get(idx)?;
+        content
+            .is_char_boundary(offset)
+            .then(|| line_column(content, offset))
+    }
+
+    /// Diagnostic location for `length` bytes at `offset` in the file at `idx`.
+    pub fn location(&self, idx: usize, offset: usize, length: usize) -> Option<DiagnosticLocation> {
+        let (path, _) = self.get(idx)?;
+        let (line, column) = self.line_column(idx, offset)?;
+
+        Some(DiagnosticLocation {
+            file: path.to_path_buf(),
+            line,
+            column,
+            length: if length > 1 { Some(length) } else { None },
+        })
+    }
+
     /// Iterate over all loaded files
     pub fn iter(&self) -> impl Iterator<Item = (usize, &Path, &str)> {
         self.files
@@ -19,7 +19,7 @@ use crate::config::GeneratorOptions;
 use crate::diagnostic::{Diagnostic, DiagnosticCategory, Diagnostics};
 use crate::documents::collect_documents;
 use crate::extract::ExtractConfig;
-use crate::generators::{GeneratorContext, run_generator};
+use crate::generators::{GeneratorContext, TypeImports, run_generator};
 use crate::schema::load_schema_from_contents;
 use crate::source_cache::SourceCache;
 
@@ -28,6 +28,15 @@ pub fn fixtures_dir() -> PathBuf {
     PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures")
 }
 
+/// Build a [`SourceCache`] from inline `(path, content)` pairs.
+pub fn source_cache(sources: &[(&str, &str)]) -> SourceCache {
+    let mut cache = SourceCache::new();
+    for (path, content) in sources {
+        cache.push(PathBuf::from(path), content.to_string());
+    }
+    cache
+}
+
 // ─────────────────────────────────────────────────────────────────────────────
 // TestGen: Full pipeline builder
 // ─────────────────────────────────────────────────────────────────────────────
@@ -216,6 +225,7 @@ impl TestGen {
         // Test options are used as-is — no merge with SGC defaults.
         let mut buffer = Vec::new();
         let mut diagnostics = documents.diagnostics.clone();
+        let mut imports = TypeImports::new();
 
         let mut ctx = GeneratorContext {
             schema: &schema,
@@ -225,11 +235,19 @@ impl TestGen {
             writer: &mut buffer,
             diagnostics: &mut diagnostics,
             generators: &[],
+            imports: &mut imports,
+            import_types_from: None,
+            federation: None,
         };
 
         run_generator(&self.generator, &mut ctx)?;
 
-        let content = String::from_utf8(buffer).expect("generator output should be valid UTF-8");
+        // Same layout as `generate_from_input`: imports first, then generated code
+        let mut output = Vec::new();
+        imports.render(&mut output)?;
+        output.extend(buffer);
+
+        let content = String::from_utf8(output).expect("generator output should be valid UTF-8");
 
         Ok(GenerateResult {
             files: vec![GeneratedFile {
@@ -262,7 +280,7 @@ const DEFAULT_SCHEMA: &str = "type Query { _: Boolean }";
 ///     .schema_str("enum Role { ADMIN USER }")
 ///     .build();
 /// let output = ctx.run(|gen_ctx| {
-///     render_enum(gen_ctx, ctx.get_enum("Role"))?;
+///     render_enum(gen_ctx, ctx.get_enum("Role"), false)?;
 ///     Ok(())
 /// });
 ///
@@ -401,6 +419,7 @@ impl TestCtx {
         let fragments = IndexMap::new();
         let mut buffer = Vec::new();
         let mut diagnostics = Diagnostics::new();
+        let mut imports = TypeImports::new();
 
         let mut ctx = GeneratorContext {
             schema: &self.schema,
@@ -410,6 +429,9 @@ impl TestCtx {
             writer: &mut buffer,
             diagnostics: &mut diagnostics,
             generators: &[],
+            imports: &mut imports,
+            import_types_from: None,
+            federation: None,
         };
 
         f(&mut ctx).expect("renderer should not error");
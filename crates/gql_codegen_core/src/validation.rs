@@ -1,8 +1,112 @@
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use apollo_compiler::ast::{Definition, Document};
+use apollo_compiler::diagnostic::ToCliReport;
+use apollo_compiler::parser::FileId;
+use apollo_compiler::validation::Valid;
+use apollo_compiler::{Name, Schema};
+
 use crate::GeneratorOptions;
-use crate::diagnostic::Diagnostics;
+use crate::diagnostic::{Diagnostic, DiagnosticCategory, Diagnostics, Severity};
+use crate::documents::CollectedDocuments;
+use crate::source_cache::SourceCache;
 
 /// Validate resolved generator options and emit warnings for conflicting settings.
 ///
 /// With fixed SGC defaults, options can't conflict — this is a no-op.
 /// Kept as a hook for future validation needs.
 pub(crate) fn validate_options(_options: &GeneratorOptions, _diagnostics: &mut Diagnostics) {}
+
+/// Validate all collected operations and fragments against the schema.
+///
+/// Every definition is validated as part of one executable document, so
+/// fragment spreads resolve across files. Diagnostics point at the original
+/// source file and are reported with the given `severity`.
+pub fn validate_documents(
+    schema: &Valid<Schema>,
+    documents: &CollectedDocuments,
+    cache: &SourceCache,
+    severity: Severity,
+) -> Diagnostics {
+    let mut merged = Document::new();
+    // Parsed file -> (source index, byte offset of the definition in that source)
+    let mut origins: HashMap<FileId, (usize, usize)> = HashMap::new();
+
+    let definitions = documents
+        .operations
+        .iter()
+        .map(|(name, op)| (Some(name), op.source_idx, op.text))
+        .chain(
+            documents
+                .fragments
+                .values()
+                .map(|frag| (None, frag.source_idx, frag.text)),
+        );
+
+    for (operation_name, source_idx, text) in definitions {
+        let Some((path, _)) = cache.get(source_idx) else {
+            continue;
+        };
+
+        // Already parsed successfully during collection
+        let Ok(document) = Document::parse(text, path) else {
+            continue;
+        };
+
+        let offset = cache.offset_of(source_idx, text).unwrap_or(0);
+        for file_id in document.sources.keys() {
+            origins.insert(*file_id, (source_idx, offset));
+        }
+
+        Arc::make_mut(&mut merged.sources)
+            .extend(document.sources.iter().map(|(k, v)| (*k, v.clone())));
+
+        for mut definition in document.definitions {
+            // Anonymous operations can only be validated on their own, so
+            // give them the unique name they were collected under
+            if let (Definition::OperationDefinition(op), Some(name)) =
+                (&mut definition, operation_name)
+                && op.name.is_none()
+            {
+                op.make_mut().name = Some(Name::clone(name));
+            }
+
+            merged.definitions.push(definition);
+        }
+    }
+
+    let Err(errors) = merged.to_executable_validate(schema) else {
+        return Diagnostics::new();
+    };
+
+    let mut diagnostics = Diagnostics::new();
+
+    for diag in errors.errors.iter() {
+        // Reported with related locations by `analyze_documents`
+        if matches!(
+            diag.error.unstable_error_name(),
+            Some("UndefinedFragment" | "UnusedFragment" | "RecursiveFragmentDefinition")
+        ) {
+            continue;
+        }
+
+        let mut diagnostic =
+            Diagnostic::error(DiagnosticCategory::Document, diag.error.to_string());
+        diagnostic.severity = severity;
+
+        let location = diag.error.location().and_then(|span| {
+            let &(source_idx, base) = origins.get(&span.file_id())?;
+            let length = span.end_offset().saturating_sub(span.offset());
+            cache.location(source_idx, base + span.offset(), length)
+        });
+
+        if let Some(location) = location {
+            diagnostic = diagnostic.with_location(location);
+        }
+
+        diagnostics.push(diagnostic);
+    }
+
+    diagnostics
+}
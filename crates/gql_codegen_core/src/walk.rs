@@ -0,0 +1,194 @@
+//! Gitignore-aware path expansion shared by document and schema patterns
+
+use std::collections::HashSet;
+use std::path::{Component, Path, PathBuf};
+use std::sync::Mutex;
+
+use globset::{Glob, GlobBuilder, GlobSet, GlobSetBuilder};
+use ignore::{WalkBuilder, WalkState};
+
+use crate::diagnostic::{Diagnostic, DiagnosticCategory, Diagnostics};
+use crate::error::Result;
+
+/// File and directory names skipped when no `ignore` list is configured
+pub const DEFAULT_IGNORE: &[&str] = &["node_modules", ".git", "target", "__generated__"];
+
+pub(crate) fn default_ignore() -> Vec<String> {
+    DEFAULT_IGNORE.iter().map(|s| s.to_string()).collect()
+}
+
+/// Lexically normalize a path (`./a/../b` becomes `b`), without touching
+/// the filesystem
+pub(crate) fn normalize_path(path: &Path) -> PathBuf {
+    let mut normalized = PathBuf::new();
+    for component in path.components() {
+        match component {
+            Component::CurDir => {}
+            Component::ParentDir => {
+                if matches!(
+                    normalized.components().next_back(),
+                    Some(Component::Normal(_))
+                ) {
+                    normalized.pop();
+                } else {
+                    normalized.push("..");
+                }
+            }
+            other => normalized.push(other),
+        }
+    }
+    normalized
+}
+
+/// Expand path patterns relative to `base_dir`.
+///
+/// Glob patterns are matched by walking from their static prefix in
+/// parallel, honoring `.gitignore`/`.ignore` files and skipping entries named
+/// in `ignore`. Patterns prefixed with `!` exclude matches. Plain paths are
+/// returned as given, if they exist or `keep_missing` is set, so the caller
+/// can report them. Results follow pattern order, sorted within a pattern.
+pub(crate) fn expand_paths(
+    patterns: &[&str],
+    base_dir: &Path,
+    ignore: &[String],
+    keep_missing: bool,
+    category: DiagnosticCategory,
+) -> Result<Vec<PathBuf>> {
+    let invalid = |pattern: &str, e: globset::Error| {
+        Diagnostics::from(Diagnostic::error(
+            category,
+            format!("Invalid glob pattern '{pattern}': {e}"),
+        ))
+    };
+
+    let mut excludes = GlobSetBuilder::new();
+    let mut includes = Vec::new();
+    for pattern in patterns {
+        match pattern.strip_prefix('!') {
+            Some(negated) => {
+                let full = join_pattern(base_dir, negated);
+                excludes.add(glob(&full).map_err(|e| invalid(pattern, e))?);
+            }
+            None => includes.push(*pattern),
+        }
+    }
+    let excludes = excludes
+        .build()
+        .map_err(|e| invalid(&patterns.join(", "), e))?;
+
+    let mut ignored = GlobSetBuilder::new();
+    for name in ignore {
+        ignored.add(Glob::new(name).map_err(|e| invalid(name, e))?);
+    }
+    let ignored = ignored
+        .build()
+        .map_err(|e| invalid(&ignore.join(", "), e))?;
+
+    let mut seen = HashSet::new();
+    let mut paths = Vec::new();
+
+    for pattern in includes {
+        let full = join_pattern(base_dir, pattern);
+
+        let mut matches = if is_glob(&full) {
+            let matcher = glob(&full)
+                .map_err(|e| invalid(pattern, e))?
+                .compile_matcher();
+            walk(&full, &ignored)
+                .into_iter()
+                .filter(|path| matcher.is_match(path))
+                .collect()
+        } else {
+            let path = PathBuf::from(&full);
+            if keep_missing || path.is_file() {
+                vec![path]
+            } else {
+                vec![]
+            }
+        };
+        matches.sort();
+
+        for path in matches {
+            if !excludes.is_match(&path) && seen.insert(path.clone()) {
+                paths.push(path);
+            }
+        }
+    }
+
+    Ok(paths)
+}
+
+/// All files below the static prefix of `pattern`, in no particular order
+fn walk(pattern: &str, ignored: &GlobSet) -> Vec<PathBuf> {
+    let prefix = static_prefix(pattern);
+    // Without a prefix, walk the current directory but report paths without
+    // a leading `./`, as the pattern has none
+    let root = if prefix.as_os_str().is_empty() {
+        Path::new(".")
+    } else {
+        prefix.as_path()
+    };
+    if !root.is_dir() {
+        return Vec::new();
+    }
+
+    let files = Mutex::new(Vec::new());
+    let ignored = ignored.clone();
+    let strip_dot = prefix.as_os_str().is_empty();
+
+    WalkBuilder::new(root)
+        .hidden(false)
+        .require_git(false)
+        .filter_entry(move |entry| entry.depth() == 0 || !ignored.is_match(entry.file_name()))
+        .build_parallel()
+        .run(|| {
+            let files = &files;
+            Box::new(move |entry| {
+                // Unreadable entries are skipped, as with a plain glob
+                if let Ok(entry) = entry
+                    && entry.file_type().is_some_and(|t| t.is_file())
+                {
+                    let path = entry.into_path();
+                    let path = match path.strip_prefix(".") {
+                        Ok(relative) if strip_dot => relative.to_path_buf(),
+                        _ => path,
+                    };
+                    files.lock().unwrap().push(path);
+                }
+                WalkState::Continue
+            })
+        });
+
+    files.into_inner().unwrap()
+}
+
+fn glob(pattern: &str) -> std::result::Result<Glob, globset::Error> {
+    GlobBuilder::new(pattern).literal_separator(true).build()
+}
+
+fn join_pattern(base_dir: &Path, pattern: &str) -> String {
+    if Path::new(pattern).is_absolute() {
+        pattern.to_string()
+    } else {
+        base_dir.join(pattern).to_string_lossy().to_string()
+    }
+}
+
+fn is_glob(pattern: &str) -> bool {
+    pattern.contains(['*', '?', '[', '{'])
+}
+
+/// Directory to start walking from: the components before the first glob
+fn static_prefix(pattern: &str) -> PathBuf {
+    let mut prefix = PathBuf::new();
+    for component in Path::new(pattern).components() {
+        if let Component::Normal(name) = component
+            && is_glob(&name.to_string_lossy())
+        {
+            break;
+        }
+        prefix.push(component);
+    }
+
+    prefix
+}
@@ -0,0 +1,168 @@
+//! Integration tests for cross-document analysis of fragments
+
+use std::path::Path;
+
+use gql_codegen_core::test_utils::source_cache;
+use gql_codegen_core::{
+    Diagnostics, ExtractConfig, Severity, analyze_documents, collect_documents,
+};
+
+fn analyze(sources: &[(&str, &str)], severity: Severity) -> Diagnostics {
+    let cache = source_cache(sources);
+    let documents = collect_documents(&cache, &ExtractConfig::default());
+    analyze_documents(&documents, &cache, severity)
+}
+
+#[test]
+fn test_no_diagnostics_for_used_fragments() {
+    let diagnostics = analyze(
+        &[
+            ("query.graphql", "query GetUser { user { ...UserFields } }"),
+            (
+                "fragments.graphql",
+                "fragment UserFields on User { id ...UserName }\nfragment UserName on User { name }",
+            ),
+        ],
+        Severity::Error,
+    );
+
+    assert!(diagnostics.is_empty(), "{diagnostics:?}");
+}
+
+#[test]
+fn test_unknown_fragment_spread() {
+    let diagnostics = analyze(
+        &[
+            ("a.graphql", "query GetUser {\n  user { ...Missing }\n}\n"),
+            ("b.graphql", "query GetUsers { users { ...Missing } }"),
+        ],
+        Severity::Error,
+    );
+
+    let errors: Vec<_> = diagnostics.errors().collect();
+    assert_eq!(errors.len(), 1, "{diagnostics:?}");
+    let error = errors[0];
+    assert!(error.message.contains("'Missing'"), "{}", error.message);
+    assert!(error.message.contains("GetUser"), "{}", error.message);
+
+    let location = error.location.as_ref().unwrap();
+    assert_eq!(location.file, Path::new("a.graphql"));
+    assert_eq!(location.line, 2);
+    assert_eq!(location.column, 10);
+
+    assert_eq!(error.related.len(), 1);
+    assert!(error.related[0].message.contains("GetUsers"));
+    assert_eq!(error.related[0].location.file, Path::new("b.graphql"));
+}
+
+#[test]
+fn test_unknown_fragment_spread_as_warning() {
+    let diagnostics = analyze(
+        &[("a.graphql", "query GetUser { user { ...Missing } }")],
+        Severity::Warning,
+    );
+
+    assert!(!diagnostics.has_errors());
+    assert!(
+        diagnostics
+            .warnings()
+            .any(|d| d.message.contains("Missing"))
+    );
+}
+
+#[test]
+fn test_unused_fragment_points_at_definition() {
+    let diagnostics = analyze(
+        &[
+            ("query.graphql", "query GetUser { user { id } }"),
+            (
+                "fragments.graphql",
+                "fragment Unused on User { ...Nested }\n\nfragment Nested on User { name }\n",
+            ),
+        ],
+        Severity::Error,
+    );
+
+    assert!(!diagnostics.has_errors());
+    let warnings: Vec<_> = diagnostics.warnings().collect();
+    assert_eq!(warnings.len(), 2, "{diagnostics:?}");
+
+    let unused = warnings
+        .iter()
+        .find(|d| d.message.contains("'Unused'"))
+        .unwrap();
+    let location = unused.location.as_ref().unwrap();
+    assert_eq!(location.file, Path::new("fragments.graphql"));
+    assert_eq!((location.line, location.column), (1, 10));
+    assert!(unused.related.is_empty());
+
+    // Only spread by another unused fragment
+    let nested = warnings
+        .iter()
+        .find(|d| d.message.contains("'Nested'"))
+        .unwrap();
+    assert_eq!(nested.location.as_ref().unwrap().line, 3);
+    assert_eq!(nested.related.len(), 1);
+    assert!(nested.related[0].message.contains("Unused"));
+    assert_eq!(nested.related[0].location.line, 1);
+}
+
+#[test]
+fn test_fragment_cycle() {
+    let diagnostics = analyze(
+        &[
+            ("query.graphql", "query GetUser { user { ...A } }"),
+            (
+                "fragments.graphql",
+                "fragment A on User { ...B }\nfragment B on User { friends { ...A } }\n",
+            ),
+        ],
+        Severity::Error,
+    );
+
+    let errors: Vec<_> = diagnostics.errors().collect();
+    assert_eq!(errors.len(), 1, "{diagnostics:?}");
+    let error = errors[0];
+    assert_eq!(error.message, "Fragment cycle: A -> B -> A");
+    assert_eq!(error.location.as_ref().unwrap().line, 1);
+
+    let related: Vec<_> = error
+        .related
+        .iter()
+        .map(|r| (r.message.as_str(), r.location.line, r.location.column))
+        .collect();
+    assert_eq!(
+        related,
+        vec![
+            ("'A' spreads 'B' here", 1, 22),
+            ("fragment 'B' defined here", 2, 10),
+            ("'B' spreads 'A' here", 2, 32),
+        ]
+    );
+}
+
+#[test]
+fn test_self_referencing_fragment() {
+    let diagnostics = analyze(
+        &[(
+            "query.graphql",
+            "query GetUser { user { ...A } }\nfragment A on User { friends { ...A } }",
+        )],
+        Severity::Error,
+    );
+
+    let errors: Vec<_> = diagnostics.errors().collect();
+    assert_eq!(errors.len(), 1, "{diagnostics:?}");
+    assert_eq!(errors[0].message, "Fragment cycle: A -> A");
+}
+
+#[test]
+fn test_spread_location_in_embedded_document() {
+    let source = "import { gql } from 'graphql-tag';\n\nexport const QUERY = gql`\n  query GetUser {\n    user { ...Missing }\n  }\n`;\n";
+    let diagnostics = analyze(&[("component.tsx", source)], Severity::Error);
+
+    let error = diagnostics.errors().next().expect("unknown spread");
+    let location = error.location.as_ref().unwrap();
+    assert_eq!(location.file, Path::new("component.tsx"));
+    assert_eq!((location.line, location.column), (5, 12));
+}
@@ -0,0 +1,185 @@
+//! Integration tests for client-side schema extensions in documents
+
+use std::collections::HashMap;
+use std::fs;
+use std::path::PathBuf;
+
+use apollo_compiler::Schema;
+use apollo_compiler::validation::Valid;
+use gql_codegen_core::test_utils::{fixtures_dir, source_cache};
+use gql_codegen_core::{
+    Diagnostics, ExtractConfig, GenerateInput, OutputConfig, SchemaOptions, Severity,
+    collect_documents, extend_schema, generate_from_input, load_schema_from_contents,
+    validate_documents,
+};
+
+const SCHEMA: &str = "schemas/basic.graphql";
+
+const LOCAL_STATE: &str = r#"
+extend type Query {
+  isLoggedIn: Boolean!
+  cartItems: [CartItem!]!
+}
+
+type CartItem {
+  id: ID!
+  quantity: Int!
+}
+
+extend type User {
+  isSelected: Boolean!
+}
+"#;
+
+fn load_schema() -> Valid<Schema> {
+    gql_codegen_core::load_schema(&[fixtures_dir().join(SCHEMA)]).unwrap()
+}
+
+#[test]
+fn test_extensions_are_collected() {
+    let cache = source_cache(&[
+        ("local.graphql", LOCAL_STATE),
+        ("query.graphql", "query IsLoggedIn { isLoggedIn @client }"),
+    ]);
+    let documents = collect_documents(&cache, &ExtractConfig::default());
+
+    assert_eq!(documents.schema_extensions.len(), 3);
+    assert_eq!(documents.operations.len(), 1);
+    assert!(
+        documents.diagnostics.is_empty(),
+        "{:?}",
+        documents.diagnostics
+    );
+
+    let names: Vec<_> = documents
+        .schema_extensions
+        .iter()
+        .filter_map(|extension| extension.definition.name())
+        .map(|name| name.as_str())
+        .collect();
+    assert_eq!(names, ["Query", "CartItem", "User"]);
+}
+
+#[test]
+fn test_extend_schema_merges_client_fields() {
+    let cache = source_cache(&[
+        ("local.graphql", LOCAL_STATE),
+        (
+            "query.graphql",
+            "query Cart { isLoggedIn @client cartItems @client { id quantity } user(id: 1) { name isSelected @client } }",
+        ),
+    ]);
+    let documents = collect_documents(&cache, &ExtractConfig::default());
+    let schema = extend_schema(
+        load_schema(),
+        &documents,
+        &cache,
+        SchemaOptions::default(),
+        &mut Diagnostics::new(),
+    )
+    .unwrap();
+
+    let query = schema.get_object("Query").unwrap();
+    assert!(query.fields.contains_key("isLoggedIn"));
+    assert!(query.fields.contains_key("user"));
+    assert!(schema.types.contains_key("CartItem"));
+    assert!(schema.directive_definitions.contains_key("client"));
+
+    // Client fields keep their origin in the document
+    let location = query.fields["isLoggedIn"].location().unwrap();
+    assert_eq!(
+        schema.sources[&location.file_id()].path(),
+        PathBuf::from("local.graphql")
+    );
+
+    let diagnostics = validate_documents(&schema, &documents, &cache, Severity::Error);
+    assert!(diagnostics.is_empty(), "{diagnostics:?}");
+}
+
+#[test]
+fn test_schema_sources_are_not_merged_again() {
+    let sdl = fs::read_to_string(fixtures_dir().join(SCHEMA)).unwrap();
+    let type_defs = format!("export const typeDefs = gql`\n{sdl}`;\n");
+    for (path, content) in [
+        ("schema.graphql", sdl.clone()),
+        ("src/typeDefs.ts", type_defs),
+    ] {
+        // A documents glob matching the schema file itself
+        let cache = source_cache(&[
+            (path, &content),
+            ("local.graphql", LOCAL_STATE),
+            ("query.graphql", "query IsLoggedIn { isLoggedIn @client }"),
+        ]);
+        let schema = load_schema_from_contents(&[(PathBuf::from(path), content.clone())]).unwrap();
+        let documents = collect_documents(&cache, &ExtractConfig::default());
+        let schema = extend_schema(
+            schema,
+            &documents,
+            &cache,
+            SchemaOptions::default(),
+            &mut Diagnostics::new(),
+        )
+        .unwrap_or_else(|err| panic!("{path}: {err:?}"));
+
+        assert!(schema.types.contains_key("CartItem"), "{path}");
+    }
+}
+
+#[test]
+fn test_extension_errors_point_into_document() {
+    let cache = source_cache(&[(
+        "src/local.ts",
+        "const typeDefs = gql`\n  extend type Query {\n    cart: Cart\n  }\n`;\n",
+    )]);
+    let documents = collect_documents(&cache, &ExtractConfig::default());
+    let err = extend_schema(
+        load_schema(),
+        &documents,
+        &cache,
+        SchemaOptions::default(),
+        &mut Diagnostics::new(),
+    )
+    .unwrap_err();
+
+    let error = err.errors().next().unwrap();
+    assert!(error.message.contains("Cart"), "{}", error.message);
+    let location = error.location.as_ref().unwrap();
+    assert_eq!(location.file, PathBuf::from("src/local.ts"));
+    assert_eq!((location.line, location.column), (3, 11));
+}
+
+#[test]
+fn test_generated_types_include_client_fields() {
+    let cache = source_cache(&[
+        ("local.graphql", LOCAL_STATE),
+        ("query.graphql", "query IsLoggedIn { isLoggedIn @client }"),
+    ]);
+    let documents = collect_documents(&cache, &ExtractConfig::default());
+    let schema = extend_schema(
+        load_schema(),
+        &documents,
+        &cache,
+        SchemaOptions::default(),
+        &mut Diagnostics::new(),
+    )
+    .unwrap();
+    let outputs = HashMap::from([(
+        "types.ts".to_string(),
+        serde_json::from_value::<OutputConfig>(serde_json::json!({
+            "generators": ["operation-types"]
+        }))
+        .unwrap(),
+    )]);
+
+    let result = generate_from_input(&GenerateInput {
+        schema: &schema,
+        documents: &documents,
+        outputs: &outputs,
+        output_documents: None,
+        federation: None,
+    })
+    .unwrap();
+    let content = &result.files[0].content;
+
+    assert!(content.contains("isLoggedIn: boolean"), "{content}");
+}
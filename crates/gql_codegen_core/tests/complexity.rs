@@ -0,0 +1,139 @@
+//! Integration tests for operation depth and complexity analysis
+
+use std::collections::BTreeMap;
+use std::path::Path;
+
+use gql_codegen_core::test_utils::{fixtures_dir, source_cache};
+use gql_codegen_core::{
+    ComplexityConfig, ComplexityReport, ExtractConfig, collect_documents, load_schema,
+    operation_complexity,
+};
+
+fn analyze(source: &str, config: &ComplexityConfig) -> ComplexityReport {
+    let schema = load_schema(&[fixtures_dir().join("schemas/complexity.graphql")]).unwrap();
+    let cache = source_cache(&[("query.graphql", source)]);
+    let documents = collect_documents(&cache, &ExtractConfig::default());
+    operation_complexity(&schema, &documents, &cache, config)
+}
+
+#[test]
+fn test_depth_and_aliases_through_fragments() {
+    let report = analyze(
+        r#"
+query GetUser {
+  me: user(id: 1) { ...UserFields }
+  other: user(id: 2) { __typename id }
+}
+
+fragment UserFields on User {
+  name
+  best: friends { friends { id } }
+}
+"#,
+        &ComplexityConfig::default(),
+    );
+
+    let operation = &report.operations["GetUser"];
+    assert_eq!(operation.depth, 4);
+    assert_eq!(operation.aliases, 3);
+}
+
+#[test]
+fn test_cost_with_list_multipliers() {
+    let config = ComplexityConfig {
+        default_list_size: Some(5),
+        ..Default::default()
+    };
+    let report = analyze(
+        r#"
+query Literal { users(first: 10) { id friends(first: 3) { name } } }
+query Variable($count: Int = 20) { users(last: $count) { id } }
+query Unbounded($count: Int) { users(first: $count) { posts { title } } }
+"#,
+        &config,
+    );
+
+    // 1 + 10 * (1 + (1 + 3 * 1))
+    assert_eq!(report.operations["Literal"].cost, 51);
+    // 1 + 20 * 1
+    assert_eq!(report.operations["Variable"].cost, 21);
+    // 1 + 5 * (1 + 5 * 1), the default list size for both lists
+    assert_eq!(report.operations["Unbounded"].cost, 31);
+}
+
+#[test]
+fn test_field_weights() {
+    let config = ComplexityConfig {
+        field_weights: BTreeMap::from([
+            ("Query.search".to_string(), 10),
+            ("Post.title".to_string(), 0),
+        ]),
+        ..Default::default()
+    };
+    let report = analyze(
+        r#"query Search { search(term: "x") { ... on User { id } ... on Post { id title } } }"#,
+        &config,
+    );
+
+    // 10 for search, every branch counted
+    assert_eq!(report.operations["Search"].cost, 12);
+}
+
+#[test]
+fn test_violations() {
+    let config = ComplexityConfig {
+        max_depth: Some(2),
+        max_aliases: Some(1),
+        max_cost: Some(100),
+        ..Default::default()
+    };
+    let report = analyze(
+        "query Deep {\n  a: user(id: 1) { friends { id } }\n  b: user(id: 2) { id }\n}\n\nquery Shallow { user(id: 1) { id } }\n",
+        &config,
+    );
+
+    let diagnostics = report.violations(&config);
+    let messages: Vec<_> = diagnostics.iter().map(|d| d.message.as_str()).collect();
+    assert_eq!(
+        messages,
+        [
+            "Operation 'Deep' has depth 3, exceeding the limit of 2",
+            "Operation 'Deep' has aliased fields 2, exceeding the limit of 1",
+        ]
+    );
+    assert!(diagnostics.has_errors());
+
+    let location = diagnostics
+        .iter()
+        .next()
+        .unwrap()
+        .location
+        .as_ref()
+        .unwrap();
+    assert_eq!(location.file, Path::new("query.graphql"));
+    assert_eq!((location.line, location.column), (1, 7));
+}
+
+#[test]
+fn test_json_report() {
+    let report = analyze(
+        "query GetUser { user(id: 1) { id } }",
+        &ComplexityConfig::default(),
+    );
+    let json = serde_json::to_value(&report).unwrap();
+
+    assert_eq!(
+        json["operations"]["GetUser"],
+        serde_json::json!({
+            "depth": 2,
+            "aliases": 0,
+            "cost": 2,
+            "location": {
+                "file": "query.graphql",
+                "line": 1,
+                "column": 7,
+                "length": 7
+            }
+        })
+    );
+}
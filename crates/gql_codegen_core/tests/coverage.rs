@@ -0,0 +1,151 @@
+//! Integration tests for field-usage coverage of the schema
+
+use std::path::Path;
+
+use gql_codegen_core::test_utils::{fixtures_dir, source_cache};
+use gql_codegen_core::{
+    ExtractConfig, FieldCoverage, collect_documents, field_coverage, load_schema,
+};
+
+fn coverage(sources: &[(&str, &str)]) -> FieldCoverage {
+    let schema = load_schema(&[
+        fixtures_dir().join("schemas/deprecated.graphql"),
+        fixtures_dir().join("schemas/interface.graphql"),
+        fixtures_dir().join("schemas/union.graphql"),
+    ])
+    .unwrap();
+    let cache = source_cache(sources);
+    let documents = collect_documents(&cache, &ExtractConfig::default());
+    field_coverage(&schema, &documents, &cache, None)
+}
+
+fn unused(coverage: &FieldCoverage) -> Vec<&str> {
+    coverage.unused().map(|(name, _)| name).collect()
+}
+
+#[test]
+fn test_fields_through_fragments() {
+    let coverage = coverage(&[
+        (
+            "query.graphql",
+            "query GetUser {\n  user(id: 1) {\n    ...UserFields\n  }\n}\n",
+        ),
+        (
+            "fragments.graphql",
+            "fragment UserFields on User {\n  id\n  name\n}\n",
+        ),
+    ]);
+
+    let name = &coverage.fields["User.name"];
+    assert_eq!(name.usages.len(), 1);
+    assert_eq!(name.usages[0].operation, "GetUser");
+    let location = name.usages[0].location.as_ref().unwrap();
+    assert_eq!(location.file, Path::new("fragments.graphql"));
+    assert_eq!((location.line, location.column), (3, 3));
+
+    assert!(coverage.fields["Query.user"].is_used());
+    assert!(!coverage.fields["User.email"].is_used());
+}
+
+#[test]
+fn test_abstract_types() {
+    let coverage = coverage(&[(
+        "query.graphql",
+        r#"
+query Search {
+  node { id }
+  search {
+    __typename
+    ... on Movie { title }
+  }
+}
+"#,
+    )]);
+
+    // Selected on the interface: counts for every implementation
+    for field in ["Node.id", "Article.id", "Comment.id"] {
+        assert!(coverage.fields[field].is_used(), "{field}");
+    }
+    // Selected in an inline fragment on a union member
+    assert!(coverage.fields["Movie.title"].is_used());
+    assert!(!coverage.fields["Book.title"].is_used());
+
+    assert_eq!(
+        unused(&coverage),
+        [
+            "Query.user",
+            "Query.users",
+            "User.id",
+            "User.name",
+            "User.username",
+            "User.email",
+            "Article.title",
+            "Comment.text",
+            "Book.isbn",
+            "Book.title",
+            "Movie.imdbId",
+        ]
+    );
+}
+
+#[test]
+fn test_deprecated_fields_in_use() {
+    let coverage = coverage(&[
+        ("a.graphql", "query A { user(id: 1) { username } }"),
+        ("b.graphql", "query B { user(id: 2) { username email } }"),
+    ]);
+
+    let deprecated: Vec<_> = coverage
+        .deprecated_used()
+        .map(|(name, stats)| {
+            (
+                name,
+                stats.deprecation_reason.as_deref().unwrap(),
+                stats.usages.len(),
+            )
+        })
+        .collect();
+    assert_eq!(
+        deprecated,
+        [
+            ("User.username", "Use `name`", 2),
+            ("User.email", "No longer supported", 1)
+        ]
+    );
+}
+
+#[test]
+fn test_unused_fragments_do_not_count() {
+    let coverage = coverage(&[(
+        "query.graphql",
+        "query GetUser { user(id: 1) { id } }\nfragment Unused on User { name }",
+    )]);
+
+    assert!(!coverage.fields["User.name"].is_used());
+}
+
+#[test]
+fn test_json_export() {
+    let coverage = coverage(&[("query.graphql", "query GetUser { user(id: 1) { email } }")]);
+    let json = serde_json::to_value(&coverage).unwrap();
+
+    assert_eq!(
+        json["fields"]["User.email"],
+        serde_json::json!({
+            "deprecationReason": "No longer supported",
+            "usages": [{
+                "operation": "GetUser",
+                "location": {
+                    "file": "query.graphql",
+                    "line": 1,
+                    "column": 31,
+                    "length": 5
+                }
+            }]
+        })
+    );
+    assert_eq!(
+        json["fields"]["User.name"],
+        serde_json::json!({ "usages": [] })
+    );
+}
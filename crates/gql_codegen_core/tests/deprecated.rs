@@ -0,0 +1,130 @@
+//! Integration tests for reporting usage of deprecated schema members
+
+use std::path::Path;
+
+use gql_codegen_core::test_utils::{fixtures_dir, source_cache};
+use gql_codegen_core::{
+    Diagnostics, ExtractConfig, Severity, check_deprecated_usage, collect_documents, load_schema,
+};
+
+fn check(sources: &[(&str, &str)], severity: Severity) -> Diagnostics {
+    let schema = load_schema(&[fixtures_dir().join("schemas/deprecated.graphql")]).unwrap();
+    let cache = source_cache(sources);
+    let documents = collect_documents(&cache, &ExtractConfig::default());
+    check_deprecated_usage(&schema, &documents, &cache, severity)
+}
+
+fn messages(diagnostics: &Diagnostics) -> Vec<&str> {
+    diagnostics.iter().map(|d| d.message.as_str()).collect()
+}
+
+#[test]
+fn test_deprecated_fields() {
+    let diagnostics = check(
+        &[
+            (
+                "query.graphql",
+                "query GetUser {\n  user(id: 1) {\n    name\n    username\n    ...UserFields\n  }\n}\n",
+            ),
+            ("fragment.graphql", "fragment UserFields on User { email }"),
+        ],
+        Severity::Warning,
+    );
+
+    assert_eq!(
+        messages(&diagnostics),
+        [
+            "Field 'User.username' is deprecated: Use `name`",
+            "Field 'User.email' is deprecated: No longer supported",
+        ]
+    );
+    assert!(!diagnostics.has_errors());
+
+    // Reported where the field is selected, including inside fragments
+    let location = diagnostics
+        .iter()
+        .next()
+        .unwrap()
+        .location
+        .as_ref()
+        .unwrap();
+    assert_eq!(location.file, Path::new("query.graphql"));
+    assert_eq!((location.line, location.column), (4, 5));
+    let location = diagnostics
+        .iter()
+        .nth(1)
+        .unwrap()
+        .location
+        .as_ref()
+        .unwrap();
+    assert_eq!(location.file, Path::new("fragment.graphql"));
+    assert_eq!((location.line, location.column), (1, 31));
+}
+
+#[test]
+fn test_deprecated_arguments() {
+    let diagnostics = check(
+        &[(
+            "query.graphql",
+            "query GetUsers {\n  users(limit: 10) { id }\n}\n",
+        )],
+        Severity::Warning,
+    );
+
+    assert_eq!(
+        messages(&diagnostics),
+        ["Argument 'limit' of 'Query.users' is deprecated: Use `first`"]
+    );
+    let location = diagnostics
+        .iter()
+        .next()
+        .unwrap()
+        .location
+        .as_ref()
+        .unwrap();
+    assert_eq!((location.line, location.column), (2, 9));
+}
+
+#[test]
+fn test_deprecated_input_values() {
+    let diagnostics = check(
+        &[(
+            "query.graphql",
+            r#"query GetUsers {
+  users(filter: { name: "a", role: "admin" }, sort: [NAME, CREATED]) { id }
+}
+"#,
+        )],
+        Severity::Warning,
+    );
+
+    assert_eq!(
+        messages(&diagnostics),
+        [
+            "Input field 'UserFilter.role' is deprecated: Roles are gone",
+            "Enum value 'Sort.CREATED' is deprecated: Sort by `NAME`",
+        ]
+    );
+    let location = diagnostics
+        .iter()
+        .nth(1)
+        .unwrap()
+        .location
+        .as_ref()
+        .unwrap();
+    assert_eq!((location.line, location.column), (2, 60));
+}
+
+#[test]
+fn test_escalated_to_error() {
+    let diagnostics = check(
+        &[(
+            "query.graphql",
+            "query GetUser { user(id: 1) { username } }",
+        )],
+        Severity::Error,
+    );
+
+    assert!(diagnostics.has_errors());
+    assert_eq!(diagnostics.errors().count(), 1);
+}
@@ -0,0 +1,102 @@
+//! Tests for `@deprecated` JSDoc and the `omitDeprecated` option
+
+use gql_codegen_core::GeneratorOptions;
+use gql_codegen_core::test_utils::TestGen;
+
+const SCHEMA: &str = r#"
+type Query {
+  user(id: ID!, legacyId: Int @deprecated(reason: "Use `id`")): User
+}
+type User {
+  id: ID!
+  "The user's name"
+  name: String @deprecated(reason: "Use `fullName`")
+  fullName: String
+  nickname: String @deprecated
+  role: Role
+}
+input UserFilter {
+  role: Role
+  admin: Boolean @deprecated(reason: "Filter by `role`")
+}
+enum Role {
+  ADMIN
+  MEMBER
+  GUEST @deprecated(reason: "Guests were removed")
+}
+"#;
+
+fn generate(options: GeneratorOptions) -> String {
+    TestGen::new()
+        .no_base_schema()
+        .schema_str(SCHEMA)
+        .options(options)
+        .generate()
+}
+
+#[test]
+fn test_deprecated_jsdoc() {
+    let output = generate(GeneratorOptions::default());
+
+    // Description and reason share one doc comment
+    assert!(
+        output.contains("  /**\n   * The user's name\n   * @deprecated Use `fullName`\n   */\n")
+    );
+    // Spec default reason when none is given
+    assert!(output.contains("  /** @deprecated No longer supported */\n"));
+    assert!(output.contains("  /** @deprecated Filter by `role` */\n"));
+    assert!(output.contains("  /** @deprecated Use `id` */\n"));
+    assert!(output.contains("  /** @deprecated Guests were removed */\n  | 'GUEST'"));
+}
+
+#[test]
+fn test_omit_deprecated() {
+    let output = generate(GeneratorOptions {
+        omit_deprecated: true,
+        ..GeneratorOptions::default()
+    });
+
+    assert!(!output.contains("@deprecated"));
+    assert!(!output.contains("name?:"));
+    assert!(!output.contains("nickname"));
+    assert!(!output.contains("admin"));
+    assert!(!output.contains("legacyId"));
+    assert!(!output.contains("'GUEST'"));
+    assert!(output.contains("fullName"));
+    assert!(output.contains("| 'MEMBER'\n"));
+}
+
+#[test]
+fn test_omit_deprecated_keeps_operation_enum_values() {
+    let output = TestGen::new()
+        .no_base_schema()
+        .schema_str(SCHEMA)
+        .operations_str("query GetUser { user(id: \"1\") { role } }")
+        .generator("operation-types")
+        .options(GeneratorOptions {
+            omit_deprecated: true,
+            ..GeneratorOptions::default()
+        })
+        .generate();
+
+    // The server can still return deprecated values in results
+    assert!(output.contains("| 'GUEST'"), "unexpected output:\n{output}");
+}
+
+#[test]
+fn test_operation_field_deprecated_jsdoc() {
+    let schema = r#"
+type Query { user: User }
+type User { id: ID!, name: String @deprecated(reason: "Use `fullName`") }
+"#;
+    let query = "query GetUser { user { id name } }";
+    let output = TestGen::new()
+        .no_base_schema()
+        .schema_str(schema)
+        .operations_str(query)
+        .generator("operation-types")
+        .generate();
+
+    assert!(output.contains("/** @deprecated Use `fullName` */\n"));
+    assert_eq!(output.matches("@deprecated").count(), 1);
+}
@@ -0,0 +1,203 @@
+//! Integration tests for schema diffing
+
+use std::path::PathBuf;
+
+use apollo_compiler::Schema;
+use apollo_compiler::validation::Valid;
+use gql_codegen_core::{
+    ChangeKind, ChangeSeverity, SchemaDiff, load_schema_from_contents, schema_diff,
+};
+
+const OLD: &str = r#"
+type Query {
+  user(id: ID!): User
+  users(first: Int = 10): [User]
+  search(term: String!): [SearchResult!]!
+}
+
+type User {
+  id: ID!
+  name: String
+  email: String!
+  role: Role
+}
+
+type Post { id: ID! }
+type Comment { id: ID! }
+
+union SearchResult = User | Post
+
+enum Role { ADMIN, EDITOR, VIEWER }
+
+input UserFilter {
+  role: Role!
+  name: String
+}
+"#;
+
+fn load(sdl: &str) -> Valid<Schema> {
+    load_schema_from_contents(&[(PathBuf::from("schema.graphql"), sdl.to_string())]).unwrap()
+}
+
+fn diff(old: &str, new: &str) -> SchemaDiff {
+    schema_diff(&load(old), &load(new))
+}
+
+fn find<'a>(
+    diff: &'a SchemaDiff,
+    kind: ChangeKind,
+    path: &str,
+) -> &'a gql_codegen_core::SchemaChange {
+    diff.changes
+        .iter()
+        .find(|c| c.kind == kind && c.path == path)
+        .unwrap_or_else(|| panic!("no {kind:?} at {path} in {diff:#?}"))
+}
+
+#[test]
+fn test_identical_schemas() {
+    let diff = diff(OLD, OLD);
+    assert!(diff.is_empty(), "{diff:#?}");
+    assert!(!diff.has_breaking());
+}
+
+#[test]
+fn test_removals_are_breaking() {
+    let new = OLD
+        .replace("  email: String!\n", "")
+        .replace(
+            "enum Role { ADMIN, EDITOR, VIEWER }",
+            "enum Role { ADMIN, VIEWER }",
+        )
+        .replace(
+            "union SearchResult = User | Post",
+            "union SearchResult = User",
+        )
+        .replace("type Comment { id: ID! }\n", "")
+        .replace("user(id: ID!)", "user");
+    let diff = diff(OLD, &new);
+
+    for (kind, path) in [
+        (ChangeKind::FieldRemoved, "User.email"),
+        (ChangeKind::EnumValueRemoved, "Role.EDITOR"),
+        (ChangeKind::UnionMemberRemoved, "SearchResult"),
+        (ChangeKind::TypeRemoved, "Comment"),
+        (ChangeKind::ArgumentRemoved, "Query.user(id:)"),
+    ] {
+        assert_eq!(find(&diff, kind, path).severity, ChangeSeverity::Breaking);
+    }
+    assert!(diff.has_breaking());
+    assert_eq!(
+        find(&diff, ChangeKind::FieldRemoved, "User.email").message,
+        "Field 'email' was removed from 'User'"
+    );
+}
+
+#[test]
+fn test_nullability_changes() {
+    let new = OLD
+        // Output: tightening is safe, loosening breaks
+        .replace("  name: String\n", "  name: String!\n")
+        .replace("  email: String!\n", "  email: String\n")
+        .replace(
+            "users(first: Int = 10): [User]",
+            "users(first: Int = 10): [User!]!",
+        )
+        // Input: loosening is safe, tightening breaks
+        .replace("user(id: ID!)", "user(id: ID)")
+        .replace("  name: String\n}", "  name: String!\n}");
+    let diff = diff(OLD, &new);
+
+    let severity = |kind, path| find(&diff, kind, path).severity;
+    assert_eq!(
+        severity(ChangeKind::FieldTypeChanged, "User.name"),
+        ChangeSeverity::Safe
+    );
+    assert_eq!(
+        severity(ChangeKind::FieldTypeChanged, "User.email"),
+        ChangeSeverity::Breaking
+    );
+    assert_eq!(
+        severity(ChangeKind::FieldTypeChanged, "Query.users"),
+        ChangeSeverity::Safe
+    );
+    assert_eq!(
+        severity(ChangeKind::ArgumentTypeChanged, "Query.user(id:)"),
+        ChangeSeverity::Safe
+    );
+    assert_eq!(
+        severity(ChangeKind::InputFieldTypeChanged, "UserFilter.name"),
+        ChangeSeverity::Breaking
+    );
+}
+
+#[test]
+fn test_additions() {
+    let new = OLD
+        .replace(
+            "users(first: Int = 10)",
+            "users(first: Int = 20, after: String)",
+        )
+        .replace(
+            "search(term: String!)",
+            "search(term: String!, limit: Int!)",
+        )
+        .replace(
+            "enum Role { ADMIN, EDITOR, VIEWER }",
+            "enum Role { ADMIN, EDITOR, VIEWER, GUEST }",
+        )
+        .replace("  role: Role\n}", "  role: Role\n  avatar: String\n}")
+        .replace("  name: String\n}", "  name: String\n  active: Boolean!\n}")
+        + "type Tag { id: ID! }\n";
+    let diff = diff(OLD, &new);
+
+    let severity = |kind, path| find(&diff, kind, path).severity;
+    assert_eq!(
+        severity(ChangeKind::FieldAdded, "User.avatar"),
+        ChangeSeverity::Safe
+    );
+    assert_eq!(severity(ChangeKind::TypeAdded, "Tag"), ChangeSeverity::Safe);
+    assert_eq!(
+        severity(ChangeKind::EnumValueAdded, "Role.GUEST"),
+        ChangeSeverity::Dangerous
+    );
+    assert_eq!(
+        severity(ChangeKind::ArgumentAdded, "Query.users(after:)"),
+        ChangeSeverity::Dangerous
+    );
+    assert_eq!(
+        severity(ChangeKind::ArgumentAdded, "Query.search(limit:)"),
+        ChangeSeverity::Breaking
+    );
+    assert_eq!(
+        severity(ChangeKind::ArgumentDefaultChanged, "Query.users(first:)"),
+        ChangeSeverity::Dangerous
+    );
+    assert_eq!(
+        severity(ChangeKind::InputFieldAdded, "UserFilter.active"),
+        ChangeSeverity::Breaking
+    );
+}
+
+#[test]
+fn test_type_kind_change_and_json() {
+    let new = OLD.replace("type Comment { id: ID! }", "input Comment { id: ID! }");
+    let diff = diff(OLD, &new);
+
+    let change = find(&diff, ChangeKind::TypeKindChanged, "Comment");
+    assert_eq!(
+        change.message,
+        "'Comment' changed from an object type to an input object"
+    );
+
+    let json = serde_json::to_value(change).unwrap();
+    assert_eq!(
+        json,
+        serde_json::json!({
+            "severity": "breaking",
+            "kind": "TYPE_KIND_CHANGED",
+            "path": "Comment",
+            "message": "'Comment' changed from an object type to an input object"
+        })
+    );
+}
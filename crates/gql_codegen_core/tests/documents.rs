@@ -1,10 +1,13 @@
 //! Integration tests for document loading and parsing
 
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 
 use apollo_compiler::Name;
+use gql_codegen_core::test_utils::source_cache;
 use gql_codegen_core::{
-    ExtractConfig, SourceCache, StringOrArray, collect_documents, load_sources,
+    AnonymousOperations, CodegenConfig, DEFAULT_IGNORE, ExtractConfig, ExtractStrategy,
+    SourceCache, StringOrArray, collect_documents, collect_documents_with, expand_document_globs,
+    expand_schema_paths, load_sources,
 };
 
 fn fixtures_dir() -> PathBuf {
@@ -159,3 +162,521 @@ fn test_parallel_load_many_files() {
     // Should have loaded all matching files
     assert!(cache.len() >= 6);
 }
+
+#[test]
+fn test_duplicate_operation_points_at_both_definitions() {
+    let cache = source_cache(&[
+        ("a.graphql", "query GetUser { user { id } }"),
+        ("b.graphql", "\n\nquery GetUser { user { name } }"),
+    ]);
+
+    let docs = collect_documents(&cache, &ExtractConfig::default());
+    assert_eq!(docs.operations.len(), 1);
+
+    let warning = docs.diagnostics.warnings().next().unwrap();
+    assert_eq!(warning.message, "Duplicate operation 'GetUser' (skipped)");
+
+    let location = warning.location.as_ref().unwrap();
+    assert_eq!(location.file, Path::new("b.graphql"));
+    assert_eq!(
+        (location.line, location.column, location.length),
+        (3, 7, Some(7))
+    );
+
+    assert_eq!(warning.related.len(), 1);
+    let first = &warning.related[0];
+    assert_eq!(first.message, "first defined here");
+    assert_eq!(first.location.file, Path::new("a.graphql"));
+    assert_eq!((first.location.line, first.location.column), (1, 7));
+}
+
+#[test]
+fn test_duplicate_fragment_points_at_both_definitions() {
+    let cache = source_cache(&[
+        ("a.graphql", "fragment UserFields on User { id }"),
+        ("b.graphql", "fragment UserFields on User { name }"),
+    ]);
+
+    let docs = collect_documents(&cache, &ExtractConfig::default());
+
+    let warning = docs.diagnostics.warnings().next().unwrap();
+    assert_eq!(warning.message, "Duplicate fragment 'UserFields' (skipped)");
+    assert_eq!(
+        warning.location.as_ref().unwrap().file,
+        Path::new("b.graphql")
+    );
+    assert_eq!(warning.related[0].location.file, Path::new("a.graphql"));
+}
+
+#[test]
+fn test_anonymous_operations_error() {
+    let cache = source_cache(&[(
+        "queries/users.graphql",
+        "query GetUser { user { id } }\n{ users { id } }",
+    )]);
+
+    let docs = collect_documents_with(
+        &cache,
+        &ExtractConfig::default(),
+        AnonymousOperations::Error,
+    );
+    assert_eq!(docs.operations.len(), 1);
+
+    let error = docs.diagnostics.errors().next().unwrap();
+    assert!(error.message.contains("Anonymous operation"));
+    let location = error.location.as_ref().unwrap();
+    assert_eq!(location.file, Path::new("queries/users.graphql"));
+    assert_eq!((location.line, location.column), (2, 1));
+}
+
+#[test]
+fn test_anonymous_operations_named_after_file() {
+    let cache = source_cache(&[
+        (
+            "src/user-list.graphql",
+            "{ users { id } }\nquery { users { name } }",
+        ),
+        (
+            "src/profile.tsx",
+            "const q = gql`{ user { id } }`;\nconst r = gql`{ user { name } }`;",
+        ),
+    ]);
+
+    let docs = collect_documents_with(
+        &cache,
+        &ExtractConfig::default(),
+        AnonymousOperations::FileName,
+    );
+
+    let names: Vec<_> = docs.operations.keys().map(|n| n.as_str()).collect();
+    assert_eq!(names, ["UserList", "UserList_2", "Profile", "Profile_2"]);
+    assert!(docs.diagnostics.is_empty(), "{:?}", docs.diagnostics);
+}
+
+#[test]
+fn test_anonymous_operations_named_after_file_are_unique() {
+    let cache = source_cache(&[
+        ("src/a/index.graphql", "{ users { id } }"),
+        ("src/b/index.graphql", "{ users { name } }\n{ user { id } }"),
+        ("lib/b/index.graphql", "{ user { name } }"),
+    ]);
+
+    let docs = collect_documents_with(
+        &cache,
+        &ExtractConfig::default(),
+        AnonymousOperations::FileName,
+    );
+
+    let names: Vec<_> = docs.operations.keys().map(|n| n.as_str()).collect();
+    assert_eq!(names, ["Index", "BIndex", "BIndex_2", "LibBIndex"]);
+    assert!(docs.diagnostics.is_empty(), "{:?}", docs.diagnostics);
+}
+
+#[test]
+fn test_anonymous_operations_allowed_by_default() {
+    let cache = source_cache(&[("a.graphql", "{ users { id } }\n{ users { name } }")]);
+
+    let docs = collect_documents(&cache, &ExtractConfig::default());
+
+    let names: Vec<_> = docs.operations.keys().map(|n| n.as_str()).collect();
+    assert_eq!(names, ["Unknown_1_", "Unknown_2_"]);
+}
+
+#[test]
+fn test_collect_from_vue_script_blocks() {
+    let source = "<template>\n  <div>{{ user.name }}</div>\n</template>\n\n<script setup lang=\"ts\">\nconst q = gql`\n  query GetUser { user { id } }\n`;\n</script>\n<script>\nexport const F = gql`fragment UserFields on User { id }`;\n</script>\n";
+    let cache = source_cache(&[("src/User.vue", source)]);
+
+    let docs = collect_documents(&cache, &ExtractConfig::default());
+
+    let op = &docs.operations[&Name::new("GetUser").unwrap()];
+    assert_eq!((op.line, op.column), (7, 3));
+    let location = op.name_location(&cache).unwrap();
+    assert_eq!((location.line, location.column), (7, 9));
+
+    let fragment = &docs.fragments[&Name::new("UserFields").unwrap()];
+    assert_eq!((fragment.line, fragment.column), (11, 22));
+}
+
+#[test]
+fn test_collect_from_script_with_generic_attribute() {
+    // `>` inside a quoted attribute doesn't end the tag; columns count characters
+    let source = "<template><div /></template>\n<script setup lang=\"ts\" generic=\"T extends Record<K, V>\" data-title=\"Café\">const q = gql`query GetUser { user { id } }`;</script>\n";
+    let cache = source_cache(&[("src/List.vue", source)]);
+
+    let docs = collect_documents(&cache, &ExtractConfig::default());
+
+    let op = &docs.operations[&Name::new("GetUser").unwrap()];
+    assert_eq!((op.line, op.column), (2, 90));
+    let location = op.name_location(&cache).unwrap();
+    assert_eq!((location.line, location.column), (2, 96));
+}
+
+#[test]
+fn test_parse_error_in_template_located_in_file() {
+    let source = "import { gql } from 'graphql-tag';\n\nconst q = gql`query GetUser { user(id: ) { id } }`;\n";
+    let cache = source_cache(&[("src/user.ts", source)]);
+
+    let docs = collect_documents(&cache, &ExtractConfig::default());
+
+    let warning = docs.diagnostics.warnings().next().expect("parse error");
+    let location = warning.location.as_ref().unwrap();
+    assert_eq!(location.file, Path::new("src/user.ts"));
+    assert_eq!((location.line, location.column), (3, 40));
+}
+
+#[test]
+fn test_collect_from_svelte_and_astro() {
+    let svelte = "<script context=\"module\">\n  const q = gql`query GetUsers { users { id } }`;\n</script>\n<p>gql`query Ignored { users { id } }`</p>\n";
+    let astro = "---\nconst q = gql`query GetPost { post { id } }`;\n---\n<script>const r = gql`query GetPosts { posts { id } }`;</script>\n";
+    let cache = source_cache(&[("src/List.svelte", svelte), ("src/pages/post.astro", astro)]);
+
+    let docs = collect_documents(&cache, &ExtractConfig::default());
+
+    let names: Vec<_> = docs.operations.keys().map(|n| n.as_str()).collect();
+    assert_eq!(names, ["GetUsers", "GetPost", "GetPosts"]);
+
+    let op = &docs.operations[&Name::new("GetPosts").unwrap()];
+    assert_eq!((op.line, op.column), (4, 23));
+}
+
+#[test]
+fn test_collect_from_markdown_fences() {
+    let source = "# Users\n\n```graphql\nquery GetUser {\n  user { id }\n}\n```\n\n```ts\nconst q = gql`query Ignored { user { id } }`;\n```\n\n~~~gql\nfragment UserFields on User { id }\n~~~\n";
+    let cache = source_cache(&[("docs/users.mdx", source)]);
+
+    let docs = collect_documents(&cache, &ExtractConfig::default());
+
+    let names: Vec<_> = docs.operations.keys().map(|n| n.as_str()).collect();
+    assert_eq!(names, ["GetUser"]);
+    assert!(
+        docs.fragments
+            .contains_key(&Name::new("UserFields").unwrap())
+    );
+
+    let op = &docs.operations[&Name::new("GetUser").unwrap()];
+    let location = op.name_location(&cache).unwrap();
+    assert_eq!((location.line, location.column), (4, 7));
+}
+
+#[test]
+fn test_custom_extension_strategy() {
+    let cache = source_cache(&[
+        (
+            "page.html",
+            "<script>gql`query GetUser { user { id } }`</script>",
+        ),
+        (
+            "notes.md",
+            "```graphql\nquery GetUsers { users { id } }\n```",
+        ),
+    ]);
+
+    let mut config = ExtractConfig::default();
+    config
+        .extensions
+        .insert("html".into(), ExtractStrategy::Html);
+    config.extensions.remove("md");
+
+    let docs = collect_documents(&cache, &config);
+
+    let names: Vec<_> = docs.operations.keys().map(|n| n.as_str()).collect();
+    assert_eq!(names, ["GetUser"]);
+}
+
+#[test]
+fn test_fragment_interpolations_are_stripped() {
+    let fragments = "export const USER_FIELDS = gql`\n  fragment UserFields on User { id }\n`;\n";
+    let query = "import { USER_FIELDS } from './fragments';\n\nconst GET_USER = gql`\n  query GetUser { user { ...UserFields ...PostFields } }\n  ${USER_FIELDS}\n  ${PostFieldsFragmentDoc}\n`;\n";
+    let cache = source_cache(&[
+        ("src/fragments.ts", fragments),
+        ("src/query.ts", query),
+        ("src/posts.graphql", "fragment PostFields on User { name }"),
+    ]);
+
+    let docs = collect_documents(&cache, &ExtractConfig::default());
+
+    assert!(docs.diagnostics.is_empty(), "{:?}", docs.diagnostics);
+    let op = &docs.operations[&Name::new("GetUser").unwrap()];
+    assert_eq!(
+        op.text,
+        "query GetUser { user { ...UserFields ...PostFields } }"
+    );
+    let location = op.name_location(&cache).unwrap();
+    assert_eq!((location.line, location.column), (4, 9));
+}
+
+#[test]
+fn test_unknown_interpolation_is_reported() {
+    let source = "const Q = gql`\n  query GetUser { user { id } }\n  ${SOMETHING_ELSE}\n`;\n";
+    let cache = source_cache(&[("src/query.ts", source)]);
+
+    let docs = collect_documents(&cache, &ExtractConfig::default());
+
+    assert!(docs.operations.contains_key(&Name::new("GetUser").unwrap()));
+    let warning = docs.diagnostics.warnings().next().unwrap();
+    assert_eq!(
+        warning.message,
+        "Cannot map interpolated 'SOMETHING_ELSE' to a known fragment"
+    );
+    let location = warning.location.as_ref().unwrap();
+    assert_eq!((location.line, location.column), (3, 3));
+}
+
+#[test]
+fn test_interpolation_inside_definition_is_skipped() {
+    let source = "const Q = gql`query GetUser { user { id ${fields} } }`;";
+    let cache = source_cache(&[("src/query.ts", source)]);
+
+    let docs = collect_documents(&cache, &ExtractConfig::default());
+
+    assert!(docs.operations.is_empty());
+    assert!(
+        docs.diagnostics
+            .warnings()
+            .any(|d| d.message.contains("Operation 'GetUser'"))
+    );
+}
+
+#[test]
+fn test_dollar_brace_in_graphql_file_is_not_interpolation() {
+    let source = "# uses ${x}\nquery B($s: String = \"${x}\") { search(term: $s) { id } }\n";
+    let markdown = format!("```graphql\n{source}```\n");
+
+    for (path, content) in [("src/query.graphql", source), ("docs/query.md", &markdown)] {
+        let cache = source_cache(&[(path, content)]);
+        let docs = collect_documents(&cache, &ExtractConfig::default());
+
+        assert!(
+            docs.diagnostics.is_empty(),
+            "{path}: {:?}",
+            docs.diagnostics
+        );
+        let op = &docs.operations[&Name::new("B").unwrap()];
+        assert!(op.text.contains(r#"String = "${x}""#), "{}", op.text);
+    }
+}
+
+fn extract_config_from(json: serde_json::Value) -> ExtractConfig {
+    let config: CodegenConfig = serde_json::from_value(serde_json::json!({
+        "schema": "schema.graphql",
+        "documents": "src/**/*.ts",
+        "outputs": {},
+        "pluckConfig": json,
+    }))
+    .unwrap();
+    config.extract_config()
+}
+
+#[test]
+fn test_pluck_config_extra_tags_and_comments() {
+    let config = extract_config_from(serde_json::json!({
+        "tags": ["gqlQuery"],
+        "magicComments": ["GQL"],
+    }));
+    let source = "const a = gqlQuery`query A { a }`;\nconst b = /* GQL */ `query B { b }`;\nconst c = gql`query C { c }`;\n";
+    let cache = source_cache(&[("src/a.ts", source)]);
+
+    let docs = collect_documents(&cache, &config);
+
+    let names: Vec<_> = docs.operations.keys().map(|n| n.as_str()).collect();
+    assert_eq!(names, ["A", "B", "C"]);
+}
+
+#[test]
+fn test_pluck_config_allowlisted_modules() {
+    let config = extract_config_from(serde_json::json!({
+        "modules": ["@apollo/client", "graphql-tag"],
+        "globalIdentifiers": ["graphql"],
+    }));
+    let cache = source_cache(&[
+        (
+            "src/apollo.ts",
+            "import {\n  useQuery,\n  gql as apolloGql,\n} from '@apollo/client/core';\nconst q = apolloGql`query Apollo { a }`;\n",
+        ),
+        (
+            "src/tag.ts",
+            "import gqlTag from \"graphql-tag\";\nconst q = gqlTag`query Tag { a }`;\n",
+        ),
+        (
+            "src/styled.ts",
+            "import { gql } from 'styled-gql';\nconst q = gql`query Styled { a }`;\n",
+        ),
+        ("src/global.ts", "const q = graphql`query Global { a }`;\n"),
+    ]);
+
+    let docs = collect_documents(&cache, &config);
+
+    let names: Vec<_> = docs.operations.keys().map(|n| n.as_str()).collect();
+    assert_eq!(names, ["Apollo", "Tag", "Global"]);
+}
+
+#[test]
+fn test_import_directives_load_files_outside_globs() {
+    let mut cache = SourceCache::new();
+    let patterns = StringOrArray::Single("imports/queries/*.graphql".into());
+    load_sources(&patterns, Some(&fixtures_dir()), &mut cache).unwrap();
+
+    // user.graphql, UserFields.graphql and PostFields.graphql; the import
+    // cycle back to user.graphql loads it only once
+    assert_eq!(cache.len(), 3);
+
+    let docs = collect_documents(&cache, &ExtractConfig::default());
+    assert!(docs.operations.contains_key(&Name::new("GetUser").unwrap()));
+    assert!(
+        docs.fragments
+            .contains_key(&Name::new("UserFields").unwrap())
+    );
+    assert!(
+        docs.fragments
+            .contains_key(&Name::new("PostFields").unwrap())
+    );
+}
+
+#[test]
+fn test_missing_import_is_located() {
+    let mut cache = SourceCache::new();
+    let patterns = StringOrArray::Single("imports/broken/*.graphql".into());
+
+    let diagnostics = load_sources(&patterns, Some(&fixtures_dir()), &mut cache).unwrap_err();
+
+    let error = diagnostics.errors().next().unwrap();
+    assert!(
+        error.message.starts_with("Cannot import '") && error.message.contains("Missing.graphql"),
+        "{}",
+        error.message
+    );
+    let location = error.location.as_ref().unwrap();
+    assert!(location.file.ends_with("imports/broken/post.graphql"));
+    assert_eq!((location.line, location.column), (5, 12));
+    assert_eq!(location.length, Some("./Missing.graphql".len()));
+}
+
+#[test]
+fn test_definition_positions_in_multi_definition_files() {
+    let graphql = "query A { a }\n\nfragment F on Query {\n  a\n}\n\n  query B { b }\n";
+    let tsx = "const q = gql`\n  query C { c }\n  query D { d }\n`;\nconst r = /* GraphQL */ `fragment G on Query { c }`;\n";
+    let cache = source_cache(&[("a.graphql", graphql), ("b.tsx", tsx)]);
+
+    let docs = collect_documents(&cache, &ExtractConfig::default());
+
+    let position = |name: &str| {
+        let name = Name::new(name).unwrap();
+        match docs.operations.get(&name) {
+            Some(op) => (op.line, op.column),
+            None => {
+                let fragment = &docs.fragments[&name];
+                (fragment.line, fragment.column)
+            }
+        }
+    };
+    assert_eq!(position("A"), (1, 1));
+    assert_eq!(position("F"), (3, 1));
+    assert_eq!(position("B"), (7, 3));
+    assert_eq!(position("C"), (2, 3));
+    assert_eq!(position("D"), (3, 3));
+    assert_eq!(position("G"), (5, 26));
+}
+
+/// Create a scratch project with the given files, replacing earlier runs
+fn scratch_project(name: &str, files: &[(&str, &str)]) -> PathBuf {
+    let root = PathBuf::from(env!("CARGO_TARGET_TMPDIR")).join(name);
+    let _ = std::fs::remove_dir_all(&root);
+    for (path, content) in files {
+        let path = root.join(path);
+        std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+        std::fs::write(path, content).unwrap();
+    }
+    root
+}
+
+fn relative_paths(root: &Path, paths: Vec<PathBuf>) -> Vec<String> {
+    paths
+        .iter()
+        .map(|p| {
+            p.strip_prefix(root)
+                .unwrap()
+                .to_string_lossy()
+                .replace('\\', "/")
+        })
+        .collect()
+}
+
+#[test]
+fn test_globs_respect_gitignore_and_default_ignore() {
+    let root = scratch_project(
+        "globs_gitignore",
+        &[
+            (".gitignore", "dist/\n*.generated.graphql\n"),
+            ("src/a.graphql", ""),
+            ("src/nested/b.graphql", ""),
+            ("src/types.generated.graphql", ""),
+            ("dist/c.graphql", ""),
+            ("node_modules/pkg/d.graphql", ""),
+            ("src/__generated__/e.graphql", ""),
+        ],
+    );
+
+    let patterns = StringOrArray::Single("**/*.graphql".into());
+    let paths = expand_document_globs(&patterns, &root, &default_ignore()).unwrap();
+
+    assert_eq!(
+        relative_paths(&root, paths),
+        ["src/a.graphql", "src/nested/b.graphql"]
+    );
+}
+
+#[test]
+fn test_globs_custom_ignore_list_and_negation() {
+    let root = scratch_project(
+        "globs_custom_ignore",
+        &[
+            ("src/a.graphql", ""),
+            ("src/a.test.graphql", ""),
+            ("src/fixtures/b.graphql", ""),
+            ("node_modules/pkg/c.graphql", ""),
+        ],
+    );
+
+    let patterns = StringOrArray::Multiple(vec![
+        "src/**/*.graphql".into(),
+        "node_modules/**/*.graphql".into(),
+        "!**/*.test.graphql".into(),
+    ]);
+    let paths = expand_document_globs(&patterns, &root, &["fixtures".to_string()]).unwrap();
+
+    // node_modules is only skipped by the default list
+    assert_eq!(
+        relative_paths(&root, paths),
+        ["src/a.graphql", "node_modules/pkg/c.graphql"]
+    );
+}
+
+#[test]
+fn test_schema_paths_share_glob_expansion() {
+    let root = scratch_project(
+        "schema_globs",
+        &[
+            ("schema/a.graphql", ""),
+            ("schema/b.graphql", ""),
+            ("schema/drafts/c.graphql", ""),
+        ],
+    );
+
+    let paths = expand_schema_paths(
+        &["schema/*.graphql", "missing.graphql"],
+        &root,
+        &default_ignore(),
+    )
+    .unwrap();
+
+    // Plain paths are kept so loading can report them
+    assert_eq!(
+        relative_paths(&root, paths),
+        ["schema/a.graphql", "schema/b.graphql", "missing.graphql"]
+    );
+}
+
+fn default_ignore() -> Vec<String> {
+    DEFAULT_IGNORE.iter().map(|s| s.to_string()).collect()
+}
@@ -0,0 +1,183 @@
+//! Integration tests for Apollo Federation subgraph schemas
+
+use std::collections::HashMap;
+use std::path::PathBuf;
+
+use apollo_compiler::Schema;
+use apollo_compiler::validation::Valid;
+use gql_codegen_core::{
+    Diagnostics, ExtractConfig, Federation, GenerateInput, OutputConfig, Result, SchemaOptions,
+    SourceCache, collect_documents, generate_from_input, load_schema_from_contents,
+    load_schema_from_contents_with,
+};
+
+const SUBGRAPH_V2: &str = r#"
+extend schema @link(url: "https://specs.apollo.dev/federation/v2.3", import: ["@key", "@shareable", "@external", "@requires"])
+
+type Query {
+  product(id: ID!): Product
+}
+
+type Product @key(fields: "id") {
+  id: ID!
+  name: String @shareable
+}
+
+extend type User @key(fields: "id") {
+  id: ID! @external
+  email: String @external
+  reviews: [Product!]! @requires(fields: "email")
+}
+"#;
+
+fn load(sdl: &str, federation: Option<Federation>) -> Result<Valid<Schema>> {
+    load_schema_from_contents_with(
+        &[(PathBuf::from("subgraph.graphql"), sdl.to_string())],
+        &ExtractConfig::default(),
+        SchemaOptions {
+            federation,
+            ..Default::default()
+        },
+        &mut Diagnostics::new(),
+    )
+}
+
+#[test]
+fn test_subgraph_fails_without_federation() {
+    let err =
+        load_schema_from_contents(&[(PathBuf::from("subgraph.graphql"), SUBGRAPH_V2.to_string())])
+            .unwrap_err();
+
+    assert!(
+        err.errors()
+            .any(|d| d.message == "type extension for undefined type `User`"),
+        "{err:?}"
+    );
+}
+
+#[test]
+fn test_federation_v2_definitions() {
+    let schema = load(SUBGRAPH_V2, Some(Federation::V2)).expect("subgraph should load");
+
+    for name in ["_Any", "FieldSet", "link__Import", "link__Purpose"] {
+        assert!(schema.types.contains_key(name), "missing {name}");
+    }
+    for name in [
+        "link",
+        "key",
+        "shareable",
+        "external",
+        "requires",
+        "override",
+    ] {
+        assert!(
+            schema.directive_definitions.contains_key(name),
+            "missing @{name}"
+        );
+    }
+
+    // Orphan `extend type` is adopted as the type definition
+    let user = schema.get_object("User").unwrap();
+    assert!(user.fields.contains_key("reviews"));
+}
+
+#[test]
+fn test_federation_v1_definitions() {
+    let sdl = r#"
+extend type Query { me: User }
+type User @key(fields: "id") @extends { id: ID! @external, name: String }
+"#;
+    let schema = load(sdl, Some(Federation::V1)).expect("subgraph should load");
+
+    assert!(schema.types.contains_key("_FieldSet"));
+    assert!(!schema.types.contains_key("FieldSet"));
+    assert!(
+        schema
+            .get_object("Query")
+            .unwrap()
+            .fields
+            .contains_key("me")
+    );
+}
+
+#[test]
+fn test_existing_definitions_are_kept() {
+    let sdl = r#"
+scalar _Any
+directive @key(fields: String!) on OBJECT
+type Query { product: Product }
+type Product @key(fields: "id") { id: ID! }
+"#;
+    let schema = load(sdl, Some(Federation::V2)).expect("hand-written stubs should load");
+
+    let key = &schema.directive_definitions["key"];
+    assert_eq!(key.arguments[0].ty.inner_named_type(), "String");
+}
+
+#[test]
+fn test_federation_version_config() {
+    let parse = |version: serde_json::Value| {
+        serde_json::from_value::<gql_codegen_core::CodegenConfig>(serde_json::json!({
+            "schema": "schema.graphql",
+            "documents": [],
+            "outputs": {},
+            "federation": version
+        }))
+    };
+
+    assert_eq!(
+        parse(serde_json::json!(2)).unwrap().federation,
+        Some(Federation::V2)
+    );
+    let err = parse(serde_json::json!(3)).unwrap_err();
+    assert!(err.to_string().contains("expected 1 or 2"), "{err}");
+}
+
+fn schema_types(schema: &Valid<Schema>, federation: Option<Federation>) -> String {
+    let cache = SourceCache::new();
+    let documents = collect_documents(&cache, &ExtractConfig::default());
+    let outputs = HashMap::from([(
+        "types.ts".to_string(),
+        serde_json::from_value::<OutputConfig>(serde_json::json!({
+            "generators": ["schema-types"]
+        }))
+        .unwrap(),
+    )]);
+
+    let result = generate_from_input(&GenerateInput {
+        schema,
+        documents: &documents,
+        outputs: &outputs,
+        output_documents: None,
+        federation,
+    })
+    .unwrap();
+    result.files[0].content.clone()
+}
+
+#[test]
+fn test_schema_types_hide_federation_types() {
+    let schema = load(SUBGRAPH_V2, Some(Federation::V2)).unwrap();
+    let content = &schema_types(&schema, Some(Federation::V2));
+
+    assert!(content.contains("Product"), "{content}");
+    for name in [
+        "_Any",
+        "FieldSet",
+        "link__Import",
+        "link__Purpose",
+        "federation__",
+    ] {
+        assert!(!content.contains(name), "{name} in:\n{content}");
+    }
+}
+
+#[test]
+fn test_schema_types_keep_same_named_types_without_federation() {
+    let sdl = "scalar _Any\ninput FieldSet { fields: String }\ntype Query { entity(set: FieldSet): _Any }\n";
+    let schema = load(sdl, None).unwrap();
+    let content = &schema_types(&schema, None);
+
+    assert!(content.contains("export interface FieldSet"), "{content}");
+    assert!(content.contains("export type _Any"), "{content}");
+}
@@ -30,6 +30,11 @@ fn generate_docs(schema_files: &[&str], document_files: &[&str], options: Genera
             generators: Some(vec![GeneratorConfig::Name("typed-documents".to_string())]),
             config: Some(options),
             prelude: None,
+            import_types_from: None,
+            documents: None,
+            include: None,
+            exclude: None,
+            pluck_config: None,
         },
     );
 
@@ -37,6 +42,8 @@ fn generate_docs(schema_files: &[&str], document_files: &[&str], options: Genera
         schema: &schema,
         documents: &docs,
         outputs: &outputs,
+        output_documents: None,
+        federation: None,
     };
 
     let result = generate_from_input(&input).unwrap();
@@ -39,6 +39,11 @@ fn generate_operations(
             generators: Some(vec![GeneratorConfig::Name("operation-types".to_string())]),
             config: Some(options),
             prelude: None,
+            import_types_from: None,
+            documents: None,
+            include: None,
+            exclude: None,
+            pluck_config: None,
         },
     );
 
@@ -46,6 +51,8 @@ fn generate_operations(
         schema: &schema,
         documents: &docs,
         outputs: &outputs,
+        output_documents: None,
+        federation: None,
     };
 
     let result = generate_from_input(&input).unwrap();
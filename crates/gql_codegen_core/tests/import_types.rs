@@ -0,0 +1,188 @@
+//! Tests for `importTypesFrom` — operation types importing shared schema
+//! types from another output instead of rendering them inline.
+
+use std::collections::HashMap;
+use std::path::PathBuf;
+
+use gql_codegen_core::{
+    CollectedDocuments, ExtractConfig, GenerateInput, GeneratorConfig, GeneratorOptions,
+    OutputConfig, SourceCache, collect_documents, generate_from_input, load_schema_from_contents,
+};
+
+const SCHEMA: &str = "\
+type Query { user(id: ID!): User, users(filter: UserFilter): [User!]! }
+type User { id: ID!, status: Status!, role: Role }
+input UserFilter { status: Status }
+enum Status { ACTIVE INACTIVE }
+enum Role { ADMIN MEMBER }
+enum Unused { A B }
+union SearchResult = User
+";
+
+const OPERATIONS: &str = "\
+query GetUser($id: ID!) { user(id: $id) { id status } }
+query FilterUsers($filter: UserFilter) { users(filter: $filter) { ...UserRole } }
+fragment UserRole on User { role }
+";
+
+fn output(generators: &[&str], import_types_from: Option<&str>) -> OutputConfig {
+    OutputConfig {
+        generators: Some(
+            generators
+                .iter()
+                .map(|g| GeneratorConfig::Name(g.to_string()))
+                .collect(),
+        ),
+        prelude: None,
+        config: None,
+        import_types_from: import_types_from.map(str::to_string),
+        documents: None,
+        include: None,
+        exclude: None,
+        pluck_config: None,
+    }
+}
+
+fn generate(outputs: HashMap<String, OutputConfig>) -> HashMap<String, String> {
+    let schema =
+        load_schema_from_contents(&[(PathBuf::from("schema.graphql"), SCHEMA.to_string())])
+            .unwrap();
+
+    let mut cache = SourceCache::new();
+    cache.push(PathBuf::from("ops.graphql"), OPERATIONS.to_string());
+    let docs: CollectedDocuments = collect_documents(&cache, &ExtractConfig::default());
+
+    let input = GenerateInput {
+        schema: &schema,
+        documents: &docs,
+        outputs: &outputs,
+        output_documents: None,
+        federation: None,
+    };
+
+    generate_from_input(&input)
+        .unwrap()
+        .files
+        .into_iter()
+        .map(|f| (f.path, f.content))
+        .collect()
+}
+
+#[test]
+fn imports_referenced_types_from_sibling_output() {
+    let files = generate(HashMap::from([
+        (
+            "src/gql/schema.ts".to_string(),
+            output(&["schema-types"], None),
+        ),
+        (
+            "src/gql/operations.ts".to_string(),
+            output(&["operation-types"], Some("src/gql/schema.ts")),
+        ),
+    ]));
+
+    let ops = &files["src/gql/operations.ts"];
+    assert!(
+        ops.starts_with("import type { Role, Status, UserFilter } from './schema';\n\n"),
+        "unexpected output:\n{ops}"
+    );
+
+    // Shared types are referenced, not duplicated
+    assert!(!ops.contains("export type Status ="));
+    assert!(!ops.contains("export type SearchResult ="));
+    assert!(!ops.contains("Unused"));
+    assert!(ops.contains("readonly status: Status;"));
+}
+
+#[test]
+fn import_path_is_relative_to_output() {
+    let files = generate(HashMap::from([
+        (
+            "types/schema.ts".to_string(),
+            output(&["schema-types"], None),
+        ),
+        (
+            "./src/app/operations.ts".to_string(),
+            output(&["operation-types"], Some("types/schema.ts")),
+        ),
+    ]));
+
+    let ops = &files["./src/app/operations.ts"];
+    assert!(ops.starts_with("import type { Role, Status, UserFilter } from '../../types/schema';"));
+}
+
+#[test]
+fn imports_follow_prelude() {
+    let mut operations = output(&["operation-types"], Some("schema.d.ts"));
+    operations.prelude = Some("/* eslint-disable */".to_string());
+
+    let files = generate(HashMap::from([
+        ("schema.d.ts".to_string(), output(&["schema-types"], None)),
+        ("operations.ts".to_string(), operations),
+    ]));
+
+    assert!(files["operations.ts"].starts_with(
+        "/* eslint-disable */\nimport type { Role, Status, UserFilter } from './schema';\n"
+    ));
+}
+
+#[test]
+fn imports_names_of_target_output() {
+    let mut schema = output(&["schema-types"], None);
+    schema.config = Some(GeneratorOptions {
+        type_name_prefix: Some("I".to_string()),
+        ..GeneratorOptions::default()
+    });
+
+    let files = generate(HashMap::from([
+        ("schema.ts".to_string(), schema),
+        (
+            "operations.ts".to_string(),
+            output(&["operation-types"], Some("schema.ts")),
+        ),
+    ]));
+
+    // Imported as named by the target, under the names this output uses
+    let ops = &files["operations.ts"];
+    assert!(
+        ops.starts_with(
+            "import type { IRole as Role, IStatus as Status, IUserFilter as UserFilter } from './schema';\n"
+        ),
+        "unexpected output:\n{ops}"
+    );
+    assert!(files["schema.ts"].contains("export type IStatus ="));
+}
+
+#[test]
+fn without_import_types_from_renders_inline() {
+    let files = generate(HashMap::from([(
+        "operations.ts".to_string(),
+        output(&["operation-types"], None),
+    )]));
+
+    let ops = &files["operations.ts"];
+    assert!(!ops.contains("import type"));
+    assert!(ops.contains("export type Status ="));
+}
+
+#[test]
+fn same_output_schema_types_needs_no_import() {
+    let files = generate(HashMap::from([(
+        "types.ts".to_string(),
+        output(&["schema-types", "operation-types"], Some("other.ts")),
+    )]));
+
+    assert!(!files["types.ts"].contains("import type"));
+}
+
+#[test]
+fn typescript_alias_counts_as_schema_types() {
+    let files = generate(HashMap::from([(
+        "types.ts".to_string(),
+        output(&["typescript", "operation-types"], Some("other.ts")),
+    )]));
+
+    let types = &files["types.ts"];
+    assert!(!types.contains("import type"), "{types}");
+    assert_eq!(types.matches("export type Status =").count(), 1, "{types}");
+}
@@ -0,0 +1,42 @@
+//! Tests for `@oneOf` input objects
+
+use gql_codegen_core::test_utils::TestGen;
+
+#[test]
+fn test_one_of_input_as_union() {
+    let output = TestGen::new()
+        .no_base_schema()
+        .schema_str(
+            "type Query { user(by: UserBy!): ID }
+             input UserBy @oneOf { id: ID, email: String }",
+        )
+        .generate();
+
+    assert!(output.contains(
+        "export type UserBy =\n  \
+         | { readonly id: string; readonly email?: never }\n  \
+         | { readonly email: string; readonly id?: never };\n"
+    ));
+}
+
+#[test]
+fn test_one_of_variable_inlined_as_union() {
+    let schema = "\
+type Query { user(by: UserBy!): ID }
+input UserBy @oneOf { id: ID, email: String }
+";
+    let query = "query GetUser($by: UserBy!, $many: [UserBy!]) { user(by: $by) }";
+    let output = TestGen::new()
+        .no_base_schema()
+        .schema_str(schema)
+        .operations_str(query)
+        .generator("operation-types")
+        .generate();
+
+    assert!(output.contains(
+        "by: ({ readonly id: string; readonly email?: never } | { readonly email: string; readonly id?: never });"
+    ));
+    assert!(output.contains(
+        "many?: ReadonlyArray<({ readonly id: string; readonly email?: never } | { readonly email: string; readonly id?: never })> | null;"
+    ));
+}
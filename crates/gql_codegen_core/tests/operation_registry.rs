@@ -0,0 +1,149 @@
+//! Tests for the operation-registry generator
+
+use gql_codegen_core::test_utils::TestGen;
+use gql_codegen_core::{GeneratorOptions, NamingCase, NamingConvention};
+
+const SCHEMA: &str = "\
+type Query { user(id: ID!): User, users: [User!]! }
+type Mutation { rename(id: ID!, name: String!): User! }
+type User { id: ID!, name: String! }
+";
+
+const OPERATIONS: &str = "\
+query GetUser($id: ID!) { user(id: $id) { id } }
+query GetUsers { users { id } }
+mutation Rename($id: ID!, $name: String!) { rename(id: $id, name: $name) { id name } }
+";
+
+fn gen_registry(options: GeneratorOptions) -> String {
+    TestGen::new()
+        .no_base_schema()
+        .schema_str(SCHEMA)
+        .operations_str(OPERATIONS)
+        .generator("operation-registry")
+        .options(options)
+        .generate()
+}
+
+#[test]
+fn registry_maps_operations_to_types() {
+    let output = gen_registry(GeneratorOptions::default());
+
+    assert_eq!(
+        output,
+        "\
+export interface Operations {
+  readonly GetUser: {
+    readonly kind: 'query';
+    readonly result: GetUserQuery;
+    readonly variables: GetUserQueryVariables;
+  };
+  readonly GetUsers: {
+    readonly kind: 'query';
+    readonly result: GetUsersQuery;
+    readonly variables: Record<string, never>;
+  };
+  readonly Rename: {
+    readonly kind: 'mutation';
+    readonly result: RenameMutation;
+    readonly variables: RenameMutationVariables;
+  };
+}
+
+"
+    );
+}
+
+#[test]
+fn registry_with_documents() {
+    let output = TestGen::new()
+        .no_base_schema()
+        .schema_str(SCHEMA)
+        .operations_str(
+            "\
+query GetUser($id: ID!) { user(id: $id) { ...UserFields } }
+fragment UserFields on User { id ...UserName }
+fragment UserName on User { name }
+",
+        )
+        .generator("operation-registry")
+        .options(GeneratorOptions {
+            registry_documents: true,
+            ..GeneratorOptions::default()
+        })
+        .generate();
+
+    // The constant the entry refers to is emitted, with the fragments it uses
+    assert!(
+        output.starts_with(
+            "\
+export const GetUserDocument = `query GetUser($id: ID!) { user(id: $id) { ...UserFields } }
+
+fragment UserFields on User { id ...UserName }
+
+fragment UserName on User { name }` as const;
+
+export interface Operations {"
+        ),
+        "{output}"
+    );
+    assert!(output.contains("readonly document: typeof GetUserDocument;"));
+}
+
+#[test]
+fn registry_respects_type_name_affixes() {
+    let output = gen_registry(GeneratorOptions {
+        type_name_prefix: Some("I".to_string()),
+        ..GeneratorOptions::default()
+    });
+
+    // Must match the names emitted by operation-types
+    let ops = TestGen::new()
+        .no_base_schema()
+        .schema_str(SCHEMA)
+        .operations_str(OPERATIONS)
+        .generator("operation-types")
+        .options(GeneratorOptions {
+            type_name_prefix: Some("I".to_string()),
+            ..GeneratorOptions::default()
+        })
+        .generate();
+
+    assert!(output.contains("readonly result: IGetUserQuery;"));
+    assert!(ops.contains("export interface IGetUserQuery "));
+
+    let variables = output
+        .lines()
+        .find(|l| l.contains("variables: ") && l.contains("GetUser"))
+        .unwrap()
+        .trim()
+        .trim_start_matches("readonly variables: ")
+        .trim_end_matches(';');
+    assert!(ops.contains(&format!("export interface {variables} ")));
+}
+
+#[test]
+fn registry_mutable_types() {
+    let output = gen_registry(GeneratorOptions {
+        immutable_types: Some(false),
+        ..GeneratorOptions::default()
+    });
+
+    assert!(output.contains("  GetUser: {\n    kind: 'query';"));
+}
+
+#[test]
+fn registry_documents_follow_naming_convention() {
+    let output = gen_registry(GeneratorOptions {
+        registry_documents: true,
+        naming_convention: Some(NamingConvention::Simple(NamingCase::ConstantCase)),
+        ..GeneratorOptions::default()
+    });
+
+    assert!(
+        output.contains("export const GET_USER_DOCUMENT = `query GetUser"),
+        "{output}"
+    );
+    assert!(output.contains("readonly document: typeof GET_USER_DOCUMENT;"));
+    assert!(output.contains("readonly result: GET_USER_QUERY;"));
+}
@@ -0,0 +1,252 @@
+//! Integration tests for remote schema loading, against a local HTTP server
+
+use std::fs;
+use std::io::{Read, Write};
+use std::net::TcpListener;
+use std::path::PathBuf;
+use std::thread::{self, JoinHandle};
+use std::time::Duration;
+
+use gql_codegen_core::cache::{Cache, MemoryCache};
+use gql_codegen_core::{
+    CodegenConfig, GenerateCachedResult, RemoteSchemaConfig, fetch_remote_schema, generate_cached,
+};
+
+fn introspection_json() -> String {
+    let path =
+        PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures/schemas/introspection.json");
+    fs::read_to_string(path).unwrap()
+}
+
+fn remote_config(json: serde_json::Value) -> RemoteSchemaConfig {
+    serde_json::from_value(json).unwrap()
+}
+
+/// Serve one canned response per connection, returning the raw requests
+fn serve(responses: Vec<String>) -> (String, JoinHandle<Vec<String>>) {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let url = format!("http://{}/graphql", listener.local_addr().unwrap());
+
+    let handle = thread::spawn(move || {
+        let mut requests = Vec::new();
+        for response in responses {
+            let (mut stream, _) = listener.accept().unwrap();
+            requests.push(read_request(&mut stream));
+            stream.write_all(response.as_bytes()).unwrap();
+        }
+        requests
+    });
+
+    (url, handle)
+}
+
+fn read_request(stream: &mut impl Read) -> String {
+    let mut raw = Vec::new();
+    let mut buf = [0; 4096];
+    loop {
+        let n = stream.read(&mut buf).unwrap();
+        raw.extend_from_slice(&buf[..n]);
+
+        let text = String::from_utf8_lossy(&raw);
+        if let Some(head_end) = text.find("\r\n\r\n") {
+            let length = header(&text[..head_end], "Content-Length")
+                .map_or(0, |len| len.parse().unwrap());
+            if raw.len() >= head_end + 4 + length {
+                return text.into_owned();
+            }
+        }
+        if n == 0 {
+            return text.into_owned();
+        }
+    }
+}
+
+/// Value of a request header, matching its name case-insensitively
+fn header<'r>(request: &'r str, name: &str) -> Option<&'r str> {
+    request.lines().find_map(|line| {
+        let (key, value) = line.split_once(':')?;
+        key.eq_ignore_ascii_case(name).then(|| value.trim())
+    })
+}
+
+fn ok(body: &str) -> String {
+    format!(
+        "HTTP/1.1 200 OK\r\nContent-Type: application/json\r\nContent-Length: {}\r\n\r\n{body}",
+        body.len()
+    )
+}
+
+fn ok_chunked(body: &str) -> String {
+    let (first, second) = body.split_at(body.len() / 2);
+    format!(
+        "HTTP/1.1 200 OK\r\nTransfer-Encoding: chunked\r\n\r\n{:x}\r\n{first}\r\n{:x}\r\n{second}\r\n0\r\n\r\n",
+        first.len(),
+        second.len()
+    )
+}
+
+#[test]
+fn test_fetch_remote_schema_with_post() {
+    // SAFETY: no other test reads or writes this variable
+    unsafe { std::env::set_var("SGC_TEST_REMOTE_TOKEN", "secret") };
+
+    let (url, server) = serve(vec![ok_chunked(&introspection_json())]);
+    let config = remote_config(serde_json::json!({
+        "headers": { "Authorization": "Bearer ${SGC_TEST_REMOTE_TOKEN}" }
+    }));
+
+    let sdl = fetch_remote_schema(&url, &config).expect("schema should be fetched");
+    assert!(sdl.contains("type User implements Node {"), "{sdl}");
+
+    let request = &server.join().unwrap()[0];
+    assert!(
+        request.starts_with("POST /graphql HTTP/1.1\r\n"),
+        "{request}"
+    );
+    assert_eq!(
+        header(request, "Authorization"),
+        Some("Bearer secret"),
+        "{request}"
+    );
+    assert_eq!(
+        header(request, "Content-Type"),
+        Some("application/json"),
+        "{request}"
+    );
+    assert!(
+        request.contains(r#""operationName":"IntrospectionQuery""#),
+        "{request}"
+    );
+}
+
+#[test]
+fn test_fetch_remote_schema_with_get() {
+    let (url, server) = serve(vec![ok(&introspection_json())]);
+    let config = remote_config(serde_json::json!({ "method": "GET" }));
+
+    let sdl = fetch_remote_schema(&url, &config).expect("schema should be fetched");
+    assert!(sdl.contains("union SearchResult = User | Post"), "{sdl}");
+
+    let request = &server.join().unwrap()[0];
+    assert!(
+        request.starts_with(
+            "GET /graphql?operationName=IntrospectionQuery&query=query%20IntrospectionQuery%20%7B"
+        ),
+        "{request}"
+    );
+}
+
+#[test]
+fn test_missing_header_variable() {
+    let config = remote_config(serde_json::json!({
+        "headers": { "X-Token": "$SGC_TEST_UNSET_VARIABLE" }
+    }));
+
+    let err = fetch_remote_schema("http://127.0.0.1:9/graphql", &config).unwrap_err();
+    assert_eq!(
+        err.errors().next().unwrap().message,
+        "Failed to fetch schema from 'http://127.0.0.1:9/graphql': environment variable 'SGC_TEST_UNSET_VARIABLE' in header 'X-Token' is not set"
+    );
+}
+
+#[test]
+fn test_header_with_line_break() {
+    // SAFETY: no other test reads or writes this variable
+    unsafe { std::env::set_var("SGC_TEST_REMOTE_SPLIT", "token\r\nX-Injected: 1") };
+    let config = remote_config(serde_json::json!({
+        "headers": { "X-Token": "${SGC_TEST_REMOTE_SPLIT}" }
+    }));
+
+    let err = fetch_remote_schema("http://127.0.0.1:9/graphql", &config).unwrap_err();
+    assert_eq!(
+        err.errors().next().unwrap().message,
+        "Failed to fetch schema from 'http://127.0.0.1:9/graphql': header 'X-Token' contains a line break"
+    );
+}
+
+#[test]
+fn test_error_status() {
+    let body = r#"{"errors":[{"message":"Unauthorized"}]}"#;
+    let (url, server) = serve(vec![format!(
+        "HTTP/1.1 401 Unauthorized\r\nContent-Length: {}\r\n\r\n{body}",
+        body.len()
+    )]);
+
+    let err = fetch_remote_schema(&url, &RemoteSchemaConfig::default()).unwrap_err();
+    server.join().unwrap();
+
+    assert_eq!(
+        err.errors().next().unwrap().message,
+        format!("Failed to fetch schema from '{url}': HTTP 401: {body}")
+    );
+}
+
+#[test]
+fn test_timeout() {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let url = format!("http://{}/graphql", listener.local_addr().unwrap());
+    let server = thread::spawn(move || {
+        let (stream, _) = listener.accept().unwrap();
+        thread::sleep(Duration::from_millis(500));
+        drop(stream);
+    });
+
+    let config = remote_config(serde_json::json!({ "timeout": 100 }));
+    let err = fetch_remote_schema(&url, &config).unwrap_err();
+    server.join().unwrap();
+
+    assert_eq!(
+        err.errors().next().unwrap().message,
+        format!("Failed to fetch schema from '{url}': timed out after 100ms")
+    );
+}
+
+#[test]
+fn test_generate_cached_falls_back_to_cached_schema() {
+    let dir = PathBuf::from(env!("CARGO_TARGET_TMPDIR")).join("remote_schema_cache");
+    let _ = fs::remove_dir_all(&dir);
+    fs::create_dir_all(&dir).unwrap();
+    let document = dir.join("query.graphql");
+    fs::write(&document, "query GetUsers { users { id name } }").unwrap();
+
+    let (url, server) = serve(vec![ok(&introspection_json())]);
+    let config: CodegenConfig = serde_json::from_value(serde_json::json!({
+        "schema": url,
+        "documents": document.to_str().unwrap(),
+        "outputs": { "types.ts": { "generators": ["operation-types"] } }
+    }))
+    .unwrap();
+
+    let mut cache = MemoryCache::new();
+    let GenerateCachedResult::Generated(first) = generate_cached(&config, &mut cache).unwrap()
+    else {
+        panic!("first run should generate");
+    };
+    assert!(first.diagnostics.is_empty(), "{:?}", first.diagnostics);
+    assert!(cache.stored().unwrap().remote_schemas.contains_key(&url));
+    server.join().unwrap();
+
+    // The server is gone: unchanged inputs are still fresh
+    assert!(matches!(
+        generate_cached(&config, &mut cache).unwrap(),
+        GenerateCachedResult::Fresh
+    ));
+
+    fs::write(&document, "query GetUsers { users { id } }").unwrap();
+    let GenerateCachedResult::Generated(second) = generate_cached(&config, &mut cache).unwrap()
+    else {
+        panic!("changed document should regenerate");
+    };
+    let warning = second
+        .diagnostics
+        .warnings()
+        .next()
+        .expect("fallback warning");
+    assert!(
+        warning.message.starts_with(&format!(
+            "Using cached schema for '{url}': Failed to fetch schema"
+        )),
+        "{}",
+        warning.message
+    );
+}
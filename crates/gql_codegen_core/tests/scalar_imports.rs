@@ -0,0 +1,155 @@
+//! Tests for scalar mappings that import their types from modules
+
+use std::collections::BTreeMap;
+
+use gql_codegen_core::test_utils::TestGen;
+use gql_codegen_core::{GeneratorOptions, ScalarConfig};
+
+#[test]
+fn test_scalars_module_import() {
+    let mut scalars = BTreeMap::new();
+    scalars.insert(
+        "DateTime".to_string(),
+        ScalarConfig::Simple("dayjs#Dayjs".to_string()),
+    );
+    scalars.insert(
+        "Money".to_string(),
+        ScalarConfig::Simple("number".to_string()),
+    );
+
+    let output = TestGen::new()
+        .no_base_schema()
+        .schema_str("type Query { now: DateTime, price: Money }\nscalar DateTime\nscalar Money")
+        .options(GeneratorOptions {
+            scalars,
+            ..GeneratorOptions::default()
+        })
+        .generate();
+
+    assert!(output.starts_with("import type { Dayjs } from 'dayjs';\n"));
+    assert!(output.contains("type DateTime = Dayjs;"));
+    assert_eq!(output.matches("from 'dayjs'").count(), 1);
+}
+
+#[test]
+fn test_scalars_module_import_named_like_scalar() {
+    let mut scalars = BTreeMap::new();
+    scalars.insert(
+        "DateTime".to_string(),
+        ScalarConfig::Simple("./scalars#DateTime".to_string()),
+    );
+
+    let output = TestGen::new()
+        .no_base_schema()
+        .schema_str("type Query { now: DateTime }\nscalar DateTime")
+        .options(GeneratorOptions {
+            scalars,
+            ..GeneratorOptions::default()
+        })
+        .generate();
+
+    // Aliased so it doesn't clash with the scalar's own declaration
+    assert!(output.starts_with("import type { DateTime as DateTime_ } from './scalars';\n"));
+    assert!(output.contains("type DateTime = DateTime_;"));
+}
+
+#[test]
+fn test_scalars_module_import_per_direction() {
+    let mut scalars = BTreeMap::new();
+    scalars.insert(
+        "DateTime".to_string(),
+        ScalarConfig::Module {
+            module: "./scalars".to_string(),
+            export: "DateTime".to_string(),
+            input: Some("DateTimeInput".to_string()),
+            output: None,
+        },
+    );
+
+    let output = TestGen::new()
+        .no_base_schema()
+        .schema_str("type Query { now: DateTime }\nscalar DateTime")
+        .options(GeneratorOptions {
+            scalars,
+            ..GeneratorOptions::default()
+        })
+        .generate();
+
+    assert!(
+        output.contains("import type { DateTime as DateTime_, DateTimeInput } from './scalars';")
+    );
+    assert!(output.contains("input: DateTimeInput;"));
+    assert!(output.contains("output: DateTime_;"));
+}
+
+/// Operation types of `query` on `schema`
+fn gen_ops(schema: &str, query: &str, options: GeneratorOptions) -> String {
+    TestGen::new()
+        .no_base_schema()
+        .schema_str(schema)
+        .operations_str(query)
+        .generator("operation-types")
+        .options(options)
+        .generate()
+}
+
+#[test]
+fn test_operations_scalar_module_import() {
+    let schema = "\
+type Query { now(after: DateTime): DateTime }
+scalar DateTime
+";
+    let query = "query Now($after: DateTime) { now(after: $after) }";
+    let mut scalars = BTreeMap::new();
+    scalars.insert(
+        "DateTime".to_string(),
+        ScalarConfig::Simple("dayjs#Dayjs".to_string()),
+    );
+
+    let output = gen_ops(
+        schema,
+        query,
+        GeneratorOptions {
+            scalars,
+            ..GeneratorOptions::default()
+        },
+    );
+
+    // Variables and result both use the type, but it is imported once
+    assert_eq!(
+        output
+            .matches("import type { Dayjs } from 'dayjs';")
+            .count(),
+        1
+    );
+    assert!(output.contains("Dayjs | null"));
+}
+
+#[test]
+fn test_operations_scalar_input_for_variables() {
+    let schema = "\
+type Query { now(after: DateTime): DateTime }
+scalar DateTime
+";
+    let query = "query Now($after: DateTime!) { now(after: $after) }";
+    let mut scalars = BTreeMap::new();
+    scalars.insert(
+        "DateTime".to_string(),
+        ScalarConfig::Detailed {
+            input: "string".to_string(),
+            output: "Date".to_string(),
+        },
+    );
+
+    let output = gen_ops(
+        schema,
+        query,
+        GeneratorOptions {
+            scalars,
+            ..GeneratorOptions::default()
+        },
+    );
+
+    assert!(output.contains("after: string;"));
+    assert!(output.contains("Date | null"));
+}
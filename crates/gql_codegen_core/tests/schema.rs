@@ -1,8 +1,13 @@
 //! Integration tests for schema loading
 
-use std::path::PathBuf;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
 
-use gql_codegen_core::{DiagnosticCategory, load_schema, resolve_schema_paths};
+use gql_codegen_core::{
+    DiagnosticCategory, Diagnostics, ExtractConfig, GenerateInput, OutputConfig, SchemaOptions,
+    SchemaValidation, SourceCache, collect_documents, generate_from_input, introspection_to_sdl,
+    load_schema, load_schema_from_contents, load_schema_from_contents_with, resolve_schema_paths,
+};
 
 fn fixtures_dir() -> PathBuf {
     PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures")
@@ -45,7 +50,10 @@ fn test_load_schema_file_not_found() {
 
     assert!(result.is_err());
     let err = result.unwrap_err();
-    assert!(err.errors().any(|d| d.category == DiagnosticCategory::Schema));
+    assert!(
+        err.errors()
+            .any(|d| d.category == DiagnosticCategory::Schema)
+    );
     assert!(err.errors().any(|d| d.message.contains("Failed to read")));
 }
 
@@ -64,3 +72,348 @@ fn test_load_schema_validation_error() {
 
     assert!(result.is_err());
 }
+
+#[test]
+fn test_one_of_directive_defined_when_missing() {
+    // graphql-js omits `directive @oneOf` when printing schemas
+    let sdl = "type Query { user(by: UserBy!): ID }\ninput UserBy @oneOf { id: ID, email: String }";
+    let result = load_schema_from_contents(&[(PathBuf::from("schema.graphql"), sdl.to_string())]);
+
+    let schema = result.expect("schema with undeclared @oneOf should load");
+    assert!(schema.directive_definitions.contains_key("oneOf"));
+}
+
+#[test]
+fn test_one_of_directive_explicit_definition_kept() {
+    let sdl = "directive @oneOf on INPUT_OBJECT\ntype Query { ok: ID }";
+    let result = load_schema_from_contents(&[(PathBuf::from("schema.graphql"), sdl.to_string())]);
+
+    assert!(result.is_ok());
+}
+
+#[test]
+fn test_load_introspection_json() {
+    let paths = schema_paths(&["schemas/introspection.json"]);
+    let schema = load_schema(&paths).expect("introspection result should load");
+
+    for name in [
+        "Query",
+        "Node",
+        "User",
+        "Post",
+        "SearchResult",
+        "Role",
+        "UserFilter",
+    ] {
+        assert!(schema.types.contains_key(name), "missing {name}");
+    }
+    assert!(schema.directive_definitions.contains_key("cacheControl"));
+
+    let user = schema.get_object("User").unwrap();
+    assert!(user.implements_interfaces.contains("Node"));
+    let username = &user.fields["username"];
+    assert!(username.directives.get("deprecated").is_some());
+}
+
+#[test]
+fn test_introspection_to_sdl() {
+    let json = std::fs::read_to_string(fixtures_dir().join("schemas/introspection.json")).unwrap();
+    let sdl = introspection_to_sdl(&json, Path::new("schema.json")).unwrap();
+
+    assert!(
+        sdl.contains("\"A registered user\"\ntype User implements Node {"),
+        "{sdl}"
+    );
+    assert!(
+        sdl.contains("username: String @deprecated(reason: \"Use `name`\")"),
+        "{sdl}"
+    );
+    assert!(
+        sdl.contains("users(first: Int = 10, filter: UserFilter): [User!]!"),
+        "{sdl}"
+    );
+    assert!(sdl.contains("GUEST @deprecated\n"), "{sdl}");
+    assert!(sdl.contains("union SearchResult = User | Post"), "{sdl}");
+    assert!(sdl.contains(
+        "scalar DateTime @specifiedBy(url: \"https://scalars.graphql.org/andimarek/date-time\")"
+    ));
+    assert!(!sdl.contains("scalar String"));
+    assert!(!sdl.contains("directive @include"));
+    assert!(!sdl.contains("__Schema"));
+}
+
+#[test]
+fn test_bare_introspection_schema() {
+    let json = r#"{"__schema": {
+        "queryType": {"name": "Root"},
+        "types": [{"kind": "OBJECT", "name": "Root", "fields": [
+            {"name": "ok", "args": [], "type": {"kind": "SCALAR", "name": "Boolean"}}
+        ], "interfaces": []}]
+    }}"#;
+    let schema = load_schema_from_contents(&[(PathBuf::from("schema.json"), json.to_string())])
+        .expect("bare __schema should load");
+
+    assert_eq!(schema.schema_definition.query.as_deref().unwrap(), "Root");
+}
+
+#[test]
+fn test_introspection_syntax_error_location() {
+    let json = "{\n  \"data\": {\n    \"__schema\": {\n      \"types\": [,]\n";
+    let err =
+        load_schema_from_contents(&[(PathBuf::from("schema.json"), json.to_string())]).unwrap_err();
+
+    let error = err.errors().next().unwrap();
+    assert!(
+        error.message.starts_with("Invalid introspection result: "),
+        "{}",
+        error.message
+    );
+    let location = error.location.as_ref().unwrap();
+    assert_eq!(location.file, Path::new("schema.json"));
+    assert_eq!((location.line, location.column), (4, 17));
+}
+
+#[test]
+fn test_introspection_without_schema() {
+    let json = r#"{"data": null, "errors": [{"message": "Not authorized"}]}"#;
+    let err =
+        load_schema_from_contents(&[(PathBuf::from("schema.json"), json.to_string())]).unwrap_err();
+
+    let error = err.errors().next().unwrap();
+    assert_eq!(
+        error.message,
+        "Introspection result 'schema.json' contains an error: Not authorized"
+    );
+
+    let json = r#"{"data": {}}"#;
+    let err =
+        load_schema_from_contents(&[(PathBuf::from("schema.json"), json.to_string())]).unwrap_err();
+    assert!(
+        err.errors()
+            .any(|d| d.message.contains("expected `__schema`"))
+    );
+}
+
+#[test]
+fn test_introspection_validation_error_points_into_json() {
+    let json = r#"{"__schema": {
+  "queryType": {"name": "Query"},
+  "types": [
+    {"kind": "OBJECT", "name": "Query", "interfaces": [], "fields": [
+      {"name": "ok", "args": [], "type": {"kind": "SCALAR", "name": "Boolean"}},
+      {"name": "user", "args": [], "type": {"kind": "OBJECT", "name": "Missing"}}
+    ]}
+  ]
+}}"#;
+    let err =
+        load_schema_from_contents(&[(PathBuf::from("schema.json"), json.to_string())]).unwrap_err();
+
+    let error = err
+        .errors()
+        .find(|d| d.message.contains("Missing"))
+        .expect("unknown type should error");
+    let location = error.location.as_ref().unwrap();
+    assert_eq!(location.file, Path::new("schema.json"));
+    assert_eq!(
+        (location.line, location.column, location.length),
+        (6, 17, Some(4))
+    );
+}
+
+#[test]
+fn test_schema_globs_with_negation_and_type_defs() {
+    let paths = schema_paths(&[
+        "services/*/schema/**/*.graphql",
+        "!services/*/schema/draft.graphql",
+        "services/*/typeDefs.ts",
+    ]);
+    let schema = load_schema(&paths).expect("schema should load");
+
+    for name in ["User", "Post", "Comment", "DateTime"] {
+        assert!(schema.types.contains_key(name), "missing {name}");
+    }
+    assert!(!schema.types.contains_key("Draft"));
+
+    let query = schema.get_object("Query").unwrap();
+    assert!(query.fields.contains_key("comments"));
+}
+
+#[test]
+fn test_type_defs_error_location() {
+    let source = "import { gql } from 'graphql-tag';\n\nexport const typeDefs = gql`\n  type Query {\n    user: Usr\n  }\n`;\n";
+    let err = load_schema_from_contents(&[(PathBuf::from("typeDefs.ts"), source.to_string())])
+        .unwrap_err();
+
+    let error = err
+        .errors()
+        .find(|d| d.message.contains("Usr"))
+        .expect("unknown type should error");
+    let location = error.location.as_ref().unwrap();
+    assert_eq!(location.file, Path::new("typeDefs.ts"));
+    assert_eq!((location.line, location.column), (5, 11));
+}
+
+fn load_lenient(sdl: &str) -> (gql_codegen_core::Result<()>, Diagnostics) {
+    let mut warnings = Diagnostics::new();
+    let result = load_schema_from_contents_with(
+        &[(PathBuf::from("schema.graphql"), sdl.to_string())],
+        &ExtractConfig::default(),
+        SchemaOptions {
+            validation: SchemaValidation::Lenient,
+            ..Default::default()
+        },
+        &mut warnings,
+    )
+    .map(|schema| {
+        assert!(schema.types.contains_key("User"));
+    });
+    (result, warnings)
+}
+
+#[test]
+fn test_lenient_validation_downgrades_errors() {
+    let sdl = "type Query { user: User @cacheControl(maxAge: 60) }\ntype User { id: ID!, avatar: Image }\n";
+
+    let strict = load_schema_from_contents(&[(PathBuf::from("schema.graphql"), sdl.to_string())]);
+    assert!(strict.is_err());
+
+    let (result, warnings) = load_lenient(sdl);
+    assert!(result.is_ok(), "{result:?}");
+    assert!(!warnings.has_errors());
+
+    let messages: Vec<_> = warnings.iter().map(|d| d.message.as_str()).collect();
+    assert!(
+        messages.iter().any(|m| m.contains("cacheControl")),
+        "{messages:?}"
+    );
+    assert!(messages.iter().any(|m| m.contains("Image")), "{messages:?}");
+    let location = warnings.iter().next().unwrap().location.as_ref().unwrap();
+    assert_eq!(location.file, PathBuf::from("schema.graphql"));
+}
+
+#[test]
+fn test_lenient_validation_fails_on_syntax_errors() {
+    let (result, warnings) = load_lenient("type Query { user: User }\ntype User { id: ID! \n");
+
+    let err = result.unwrap_err();
+    assert!(
+        err.errors().any(|d| d.message.starts_with("syntax error")),
+        "{err:?}"
+    );
+    assert!(warnings.is_empty());
+}
+
+#[test]
+fn test_lenient_validation_fails_on_parser_limits() {
+    let depth = 1000;
+    let sdl = format!(
+        "type Query {{ user: User }}\ntype User {{ id: ID!, matrix: {}Int{} }}\n",
+        "[".repeat(depth),
+        "]".repeat(depth)
+    );
+    let (result, warnings) = load_lenient(&sdl);
+
+    let err = result.unwrap_err();
+    assert!(
+        err.errors().any(|d| d.message.contains("recursion")),
+        "{err:?}"
+    );
+    assert!(warnings.is_empty());
+}
+
+/// Generate schema and operation types from a leniently loaded schema.
+fn generate_lenient(sdl: &str, operations: &str) -> String {
+    let schema = load_schema_from_contents_with(
+        &[(PathBuf::from("schema.graphql"), sdl.to_string())],
+        &ExtractConfig::default(),
+        SchemaOptions {
+            validation: SchemaValidation::Lenient,
+            ..Default::default()
+        },
+        &mut Diagnostics::new(),
+    )
+    .unwrap();
+    let mut cache = SourceCache::new();
+    cache.push(PathBuf::from("query.graphql"), operations.to_string());
+    let documents = collect_documents(&cache, &ExtractConfig::default());
+    let outputs = HashMap::from([(
+        "types.ts".to_string(),
+        serde_json::from_value::<OutputConfig>(serde_json::json!({
+            "generators": ["schema-types", "operation-types"],
+            "config": { "futureProofUnions": false }
+        }))
+        .unwrap(),
+    )]);
+
+    let result = generate_from_input(&GenerateInput {
+        schema: &schema,
+        documents: &documents,
+        outputs: &outputs,
+        output_documents: None,
+        federation: None,
+    })
+    .unwrap();
+    result.files[0].content.clone()
+}
+
+#[test]
+fn test_lenient_schema_drops_undefined_members_and_interfaces() {
+    let sdl = "type Query { u: U }\nunion U = A | B\ntype A implements Node & Missing { id: ID! }\ninterface Node { id: ID! }\n";
+    let content = generate_lenient(sdl, "query Q { u { ... on A { id } } }");
+
+    assert!(content.contains("export type U = \n  | A;\n"), "{content}");
+    assert!(
+        content.contains("export interface A extends Node {"),
+        "{content}"
+    );
+    assert!(!content.contains("Missing"), "{content}");
+    assert!(!content.contains("| B"), "{content}");
+    assert!(content.contains("export interface QQuery {"), "{content}");
+}
+
+#[test]
+fn test_lenient_schema_renders_undefined_types_as_unknown() {
+    let sdl = "type Query { u: U, user: User }\nunion U = B | C\ntype User { id: ID!, avatar(size: Size): Image }\n";
+    let content = generate_lenient(sdl, "query Q { u user { avatar } }");
+
+    assert!(content.contains("export type U = unknown;"), "{content}");
+    assert!(
+        content.contains("export type Image = unknown;"),
+        "{content}"
+    );
+    assert!(content.contains("export type Size = unknown;"), "{content}");
+    assert!(
+        content.contains("readonly u?: unknown | null;"),
+        "{content}"
+    );
+    assert!(
+        content.contains("readonly avatar?: unknown | null;"),
+        "{content}"
+    );
+    assert!(!content.contains("= \n\n"), "{content}");
+}
+
+#[test]
+fn test_schema_validation_config() {
+    let parse = |json: serde_json::Value| {
+        serde_json::from_value::<gql_codegen_core::CodegenConfig>(json).unwrap()
+    };
+
+    let config = parse(serde_json::json!({
+        "schema": "schema.graphql",
+        "documents": [],
+        "outputs": {}
+    }));
+    assert_eq!(config.schema_validation, SchemaValidation::Strict);
+
+    let config = parse(serde_json::json!({
+        "schema": "schema.graphql",
+        "documents": [],
+        "outputs": {},
+        "schemaValidation": "lenient"
+    }));
+    assert_eq!(
+        config.schema_options().validation,
+        SchemaValidation::Lenient
+    );
+}
@@ -0,0 +1,273 @@
+//! Integration tests for per-output document selection
+
+use std::collections::HashSet;
+use std::fs;
+use std::path::PathBuf;
+
+use gql_codegen_core::test_utils::source_cache;
+use gql_codegen_core::{
+    CodegenConfig, CollectedDocuments, DocumentFilter, ExtractConfig, SourceCache,
+    collect_documents, generate, select_documents,
+};
+
+fn project() -> SourceCache {
+    source_cache(&[
+        (
+            "./src/admin/users.graphql",
+            "query AdminUsers { users { ...UserFields } }\nmutation AdminBan { ban { id } }",
+        ),
+        (
+            "./src/app/user.graphql",
+            "query GetUser { user { ...UserFields } }\nsubscription OnUser { user { id } }",
+        ),
+        (
+            "./src/shared/fragments.graphql",
+            "fragment UserFields on User { id ...Names }\nfragment Names on User { name }\nfragment Unused on User { id }",
+        ),
+    ])
+}
+
+fn filter(json: serde_json::Value) -> DocumentFilter {
+    serde_json::from_value(json).unwrap()
+}
+
+fn names(documents: &CollectedDocuments) -> (Vec<String>, Vec<String>) {
+    let mut operations: Vec<_> = documents.operations.keys().map(|n| n.to_string()).collect();
+    let mut fragments: Vec<_> = documents.fragments.keys().map(|n| n.to_string()).collect();
+    operations.sort();
+    fragments.sort();
+    (operations, fragments)
+}
+
+#[test]
+fn test_sources_pull_in_spread_fragments_from_other_files() {
+    let cache = project();
+    let documents = collect_documents(&cache, &ExtractConfig::default());
+    let sources: HashSet<PathBuf> = [PathBuf::from("./src/app/user.graphql")].into();
+
+    let selected = select_documents(&documents, &cache, Some(&sources), None, None).unwrap();
+
+    assert_eq!(
+        names(&selected),
+        (
+            vec!["GetUser".into(), "OnUser".into()],
+            vec!["Names".into(), "UserFields".into()]
+        )
+    );
+}
+
+#[test]
+fn test_fragment_only_sources_keep_their_fragments() {
+    let cache = project();
+    let documents = collect_documents(&cache, &ExtractConfig::default());
+    let sources: HashSet<PathBuf> = [PathBuf::from("./src/shared/fragments.graphql")].into();
+
+    let selected = select_documents(&documents, &cache, Some(&sources), None, None).unwrap();
+
+    let (operations, fragments) = names(&selected);
+    assert!(operations.is_empty());
+    assert_eq!(fragments, ["Names", "Unused", "UserFields"]);
+}
+
+#[test]
+fn test_include_by_type_and_path() {
+    let cache = project();
+    let documents = collect_documents(&cache, &ExtractConfig::default());
+    let include = filter(serde_json::json!({
+        "types": ["query", "mutation"],
+        "paths": ["src/admin/**"]
+    }));
+
+    let selected = select_documents(&documents, &cache, None, Some(&include), None).unwrap();
+
+    // Unused fragments aren't carried along once a filter is set
+    assert_eq!(
+        names(&selected),
+        (
+            vec!["AdminBan".into(), "AdminUsers".into()],
+            vec!["Names".into(), "UserFields".into()]
+        )
+    );
+}
+
+#[test]
+fn test_exclude_by_name_glob() {
+    let cache = project();
+    let documents = collect_documents(&cache, &ExtractConfig::default());
+    let exclude = filter(serde_json::json!({ "names": ["Admin*", "On*"] }));
+
+    let selected = select_documents(&documents, &cache, None, None, Some(&exclude)).unwrap();
+
+    let (operations, _) = names(&selected);
+    assert_eq!(operations, ["GetUser"]);
+}
+
+#[test]
+fn test_invalid_filter_pattern() {
+    let cache = project();
+    let documents = collect_documents(&cache, &ExtractConfig::default());
+    let include = filter(serde_json::json!({ "names": ["Admin[*"] }));
+
+    let Err(diagnostics) = select_documents(&documents, &cache, None, Some(&include), None) else {
+        panic!("invalid glob should fail");
+    };
+    let error = diagnostics.errors().next().unwrap();
+    assert!(
+        error
+            .message
+            .starts_with("Invalid filter pattern 'Admin[*'"),
+        "{}",
+        error.message
+    );
+}
+
+#[test]
+fn test_outputs_with_own_documents_are_collected_apart() {
+    let dir = PathBuf::from(env!("CARGO_TARGET_TMPDIR")).join("output_document_sets");
+    let _ = fs::remove_dir_all(&dir);
+    for app in ["admin", "web"] {
+        fs::create_dir_all(dir.join(app)).unwrap();
+    }
+    fs::write(
+        dir.join("schema.graphql"),
+        "type Query { user: User }\ntype User { id: ID!, name: String }",
+    )
+    .unwrap();
+    // Same operation name, and a fragment only the admin app uses
+    fs::write(
+        dir.join("admin/user.graphql"),
+        "query GetUser { user { ...AdminUser } }\nfragment AdminUser on User { id name }",
+    )
+    .unwrap();
+    fs::write(
+        dir.join("web/user.graphql"),
+        "query GetUser { user { id } }",
+    )
+    .unwrap();
+
+    let output = |app: &str| {
+        serde_json::json!({
+            "generators": ["operation-types"],
+            "documents": dir.join(app).join("*.graphql").to_str().unwrap(),
+        })
+    };
+    let config: CodegenConfig = serde_json::from_value(serde_json::json!({
+        "schema": dir.join("schema.graphql").to_str().unwrap(),
+        "documents": [],
+        "outputs": { "admin.ts": output("admin"), "web.ts": output("web") }
+    }))
+    .unwrap();
+
+    let result = generate(&config).unwrap();
+
+    assert!(result.diagnostics.is_empty(), "{:?}", result.diagnostics);
+    let content = |path: &str| {
+        &result
+            .files
+            .iter()
+            .find(|file| file.path == path)
+            .unwrap()
+            .content
+    };
+    assert!(content("admin.ts").contains("AdminUserFragment"));
+    assert!(content("web.ts").contains("GetUserQuery"));
+    assert!(content("admin.ts").contains("readonly name?:"));
+    assert!(!content("web.ts").contains("readonly name?:"));
+}
+
+#[test]
+fn test_output_document_sets_keep_imported_files() {
+    let dir = PathBuf::from(env!("CARGO_TARGET_TMPDIR")).join("output_document_imports");
+    let _ = fs::remove_dir_all(&dir);
+    for sub in ["src", "admin", "fragments"] {
+        fs::create_dir_all(dir.join(sub)).unwrap();
+    }
+    fs::write(
+        dir.join("schema.graphql"),
+        "type Query { user: User }\ntype User { id: ID!, name: String }",
+    )
+    .unwrap();
+    // Only reachable through `#import`, not through any documents glob
+    fs::write(
+        dir.join("fragments/user.graphql"),
+        "fragment UserFields on User { id name }",
+    )
+    .unwrap();
+    fs::write(
+        dir.join("src/user.graphql"),
+        "#import \"../fragments/user.graphql\"\nquery GetUser { user { ...UserFields } }",
+    )
+    .unwrap();
+    fs::write(
+        dir.join("admin/users.graphql"),
+        "query AdminUser { user { id } }",
+    )
+    .unwrap();
+
+    let config: CodegenConfig = serde_json::from_value(serde_json::json!({
+        "schema": dir.join("schema.graphql").to_str().unwrap(),
+        "documents": dir.join("src/*.graphql").to_str().unwrap(),
+        "outputs": {
+            "a.ts": { "generators": ["operation-types"] },
+            "b.ts": {
+                "generators": ["operation-types"],
+                "documents": dir.join("admin/*.graphql").to_str().unwrap(),
+            }
+        }
+    }))
+    .unwrap();
+
+    let result = generate(&config).unwrap();
+
+    assert!(result.diagnostics.is_empty(), "{:?}", result.diagnostics);
+    let a = &result
+        .files
+        .iter()
+        .find(|file| file.path == "a.ts")
+        .unwrap()
+        .content;
+    assert!(a.contains("UserFieldsFragment"), "{a}");
+    assert!(a.contains("readonly name?:"), "{a}");
+}
+
+#[test]
+fn test_output_pluck_config_overrides_project() {
+    let dir = PathBuf::from(env!("CARGO_TARGET_TMPDIR")).join("output_pluck_config");
+    let _ = fs::remove_dir_all(&dir);
+    fs::create_dir_all(&dir).unwrap();
+    fs::write(dir.join("schema.graphql"), "type Query { id: ID }").unwrap();
+    fs::write(
+        dir.join("queries.ts"),
+        "const a = gql`query GetId { id }`;\nconst b = relayQuery`query GetOtherId { id }`;\n",
+    )
+    .unwrap();
+
+    let config: CodegenConfig = serde_json::from_value(serde_json::json!({
+        "schema": dir.join("schema.graphql").to_str().unwrap(),
+        "documents": dir.join("queries.ts").to_str().unwrap(),
+        "outputs": {
+            "default.ts": { "generators": ["operation-types"] },
+            "relay.ts": {
+                "generators": ["operation-types"],
+                "pluckConfig": { "tags": ["relayQuery"] }
+            }
+        }
+    }))
+    .unwrap();
+
+    let result = generate(&config).unwrap();
+
+    assert!(result.diagnostics.is_empty(), "{:?}", result.diagnostics);
+    let content = |path: &str| {
+        &result
+            .files
+            .iter()
+            .find(|file| file.path == path)
+            .unwrap()
+            .content
+    };
+    assert!(content("default.ts").contains("GetIdQuery"));
+    assert!(!content("default.ts").contains("GetOtherIdQuery"));
+    assert!(content("relay.ts").contains("GetIdQuery"));
+    assert!(content("relay.ts").contains("GetOtherIdQuery"));
+}
@@ -0,0 +1,114 @@
+//! Integration tests for validating documents against the schema
+
+use std::path::Path;
+
+use gql_codegen_core::test_utils::{fixtures_dir, source_cache};
+use gql_codegen_core::{
+    Diagnostics, ExtractConfig, Severity, collect_documents, load_schema, validate_documents,
+};
+
+fn validate(sources: &[(&str, &str)], severity: Severity) -> Diagnostics {
+    let schema = load_schema(&[fixtures_dir().join("schemas/basic.graphql")]).unwrap();
+    let cache = source_cache(sources);
+    let documents = collect_documents(&cache, &ExtractConfig::default());
+    validate_documents(&schema, &documents, &cache, severity)
+}
+
+#[test]
+fn test_valid_documents_with_fragment_in_other_file() {
+    let diagnostics = validate(
+        &[
+            (
+                "query.graphql",
+                "query GetUser { user(id: 1) { ...UserFields } }",
+            ),
+            (
+                "fragment.graphql",
+                "fragment UserFields on User { id name }",
+            ),
+        ],
+        Severity::Error,
+    );
+
+    assert!(diagnostics.is_empty(), "{diagnostics:?}");
+}
+
+#[test]
+fn test_unknown_field_has_source_location() {
+    let diagnostics = validate(
+        &[(
+            "queries.graphql",
+            "query GetUser {\n  user(id: 1) { id }\n}\n\nquery GetUsers {\n  users { id nmae }\n}\n",
+        )],
+        Severity::Error,
+    );
+
+    let error = diagnostics
+        .errors()
+        .next()
+        .expect("unknown field should error");
+    assert!(error.message.contains("nmae"), "{}", error.message);
+
+    // Line/column are relative to the file, not the definition
+    let location = error.location.as_ref().unwrap();
+    assert_eq!(location.file, Path::new("queries.graphql"));
+    assert_eq!(location.line, 6);
+    assert_eq!(location.column, 14);
+}
+
+#[test]
+fn test_location_inside_embedded_document() {
+    let source = "import { gql } from 'graphql-tag';\n\nexport const QUERY = gql`\n  query GetUser {\n    user(id: 1) { phone }\n  }\n`;\n";
+    let diagnostics = validate(&[("component.tsx", source)], Severity::Error);
+
+    let error = diagnostics
+        .errors()
+        .find(|d| d.message.contains("phone"))
+        .expect("unknown field should error");
+    let location = error.location.as_ref().unwrap();
+    assert_eq!(location.file, Path::new("component.tsx"));
+    assert_eq!(location.line, 5);
+    assert_eq!(location.column, 19);
+}
+
+#[test]
+fn test_bad_argument_and_variable_type() {
+    let diagnostics = validate(
+        &[(
+            "queries.graphql",
+            "query GetUser($id: String) { user(id: $id, offset: 1) { id } }",
+        )],
+        Severity::Error,
+    );
+
+    let messages: Vec<_> = diagnostics.iter().map(|d| d.message.as_str()).collect();
+    assert!(
+        messages.iter().any(|m| m.contains("offset")),
+        "{messages:?}"
+    );
+    assert!(messages.iter().any(|m| m.contains("$id")), "{messages:?}");
+}
+
+#[test]
+fn test_multiple_anonymous_operations_validate_independently() {
+    let diagnostics = validate(
+        &[
+            ("a.graphql", "{ users { id } }"),
+            ("b.graphql", "{ users { name } }"),
+        ],
+        Severity::Error,
+    );
+
+    assert!(diagnostics.is_empty(), "{diagnostics:?}");
+}
+
+#[test]
+fn test_warning_severity() {
+    let diagnostics = validate(
+        &[("query.graphql", "query GetUser { user(id: 1) { nope } }")],
+        Severity::Warning,
+    );
+
+    assert!(!diagnostics.has_errors());
+    assert!(diagnostics.warnings().any(|d| d.message.contains("nope")));
+}
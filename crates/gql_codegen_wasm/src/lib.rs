@@ -8,8 +8,8 @@ use std::path::PathBuf;
 use gql_codegen_core::diagnostic::{render_diagnostic_string, render_diagnostics_string};
 use gql_codegen_core::{
     CollectedDocuments, ExtractConfig, GenerateInput, GeneratorConfig, GeneratorOptions,
-    OutputConfig, SourceCache, StringOrArray, collect_documents, config_json_schema,
-    generate_from_input, load_schema_from_contents,
+    OutputConfig, Severity, SourceCache, StringOrArray, analyze_documents, collect_documents,
+    config_json_schema, generate_from_input, load_schema_from_contents, validate_documents,
 };
 use serde::{Deserialize, Serialize};
 use wasm_bindgen::prelude::*;
@@ -129,7 +129,13 @@ fn generate_internal(
 
     // Collect documents (operations and fragments)
     let extract_config = ExtractConfig::default();
-    let documents: CollectedDocuments = collect_documents(&source_cache, &extract_config);
+    let mut documents: CollectedDocuments = collect_documents(&source_cache, &extract_config);
+
+    // Report invalid operations as warnings so the playground still shows output
+    let analysis = analyze_documents(&documents, &source_cache, Severity::Warning);
+    documents.diagnostics.extend(analysis);
+    let validation = validate_documents(&schema, &documents, &source_cache, Severity::Warning);
+    documents.diagnostics.extend(validation);
 
     // Collect warnings from document parsing
     let doc_warnings: Vec<String> = documents
@@ -150,6 +156,11 @@ fn generate_internal(
                         .map(|gs| gs.into_iter().map(GeneratorConfig::Name).collect()),
                     prelude: None,
                     config: out.config,
+                    import_types_from: None,
+                    documents: None,
+                    include: None,
+                    exclude: None,
+                    pluck_config: None,
                 };
                 (path, output_config)
             })
@@ -166,6 +177,11 @@ fn generate_internal(
                     ]),
                     prelude: None,
                     config: None,
+                    import_types_from: None,
+                    documents: None,
+                    include: None,
+                    exclude: None,
+                    pluck_config: None,
                 },
             );
             map
@@ -177,6 +193,8 @@ fn generate_internal(
         schema: &schema,
         documents: &documents,
         outputs: &outputs,
+        output_documents: None,
+        federation: None,
     };
 
     match generate_from_input(&input) {
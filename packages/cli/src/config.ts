@@ -113,7 +113,7 @@ export function resolveConfigPaths(
     baseDir: string,
 ): CodegenConfig {
     const resolvePath = (p: string) =>
-        p.startsWith("/") ? p : resolve(baseDir, p);
+        p.startsWith("/") || /^https?:\/\//i.test(p) ? p : resolve(baseDir, p);
     const resolvePaths = (paths: string | string[]): string | string[] => {
         if (Array.isArray(paths)) {
             return paths.map(resolvePath);
@@ -99,6 +99,11 @@ const TS_EXTENSIONS = ['.ts', '.tsx', '.mts', '.cts']
 export async function loadSchema(
     schemaPath: string,
 ): Promise<SchemaResult | null> {
+    // Rust core fetches URLs
+    if (/^https?:\/\//i.test(schemaPath)) {
+        return null
+    }
+
     const p = path.parse(schemaPath)
 
     if (!JS_EXTENSIONS.includes(p.ext) && !TS_EXTENSIONS.includes(p.ext)) {
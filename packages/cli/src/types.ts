@@ -5,10 +5,18 @@
  */
 
 export interface CodegenConfig {
-    /** Path to GraphQL schema file(s) */
+    /**
+     * Paths or globs of GraphQL schema file(s), `!` to exclude. `.json` files
+     * are read as introspection results, SDL is extracted from `gql` tagged
+     * templates in globbed code files, and `http(s)://` URLs are introspected.
+     */
     schema: string | string[]
 
-    /** Glob patterns for document files */
+    /**
+     * Glob patterns for document files. Type definitions and extensions in
+     * documents (client-side schema for `@client` fields) are merged into
+     * the schema, except in files that are also schema sources.
+     */
     documents: string | string[]
 
     /** Output configurations keyed by output path */
@@ -17,6 +25,63 @@ export interface CodegenConfig {
     /** Lifecycle hooks — shell commands run after generation */
     hooks?: HooksConfig
 
+    /**
+     * How operations and fragments that don't validate against the schema
+     * are reported. `warning` (default) reports and continues, `error`
+     * fails generation, `off` skips validation.
+     */
+    documentValidation?: 'warning' | 'error' | 'off'
+
+    /**
+     * How selections of deprecated fields, arguments, input fields and enum
+     * values in documents are reported. `warning` (default) reports and
+     * continues, `error` fails generation, `off` skips the check.
+     */
+    deprecatedUsage?: 'warning' | 'error' | 'off'
+
+    /**
+     * Depth, alias and cost limits for operations, with fragments expanded.
+     * Operations exceeding a limit fail generation.
+     */
+    complexity?: ComplexityConfig
+
+    /**
+     * How schema validation errors are handled. `strict` (default) fails
+     * generation; `lenient` reports errors that leave the schema usable,
+     * such as an undefined directive, as warnings and generates anyway.
+     * Syntax errors always fail.
+     */
+    schemaValidation?: 'strict' | 'lenient'
+
+    /**
+     * How operations without a name are handled. `allow` (default) names
+     * them `Unknown_1_`, `error` fails generation, `fileName` names them
+     * after their source file, prefixed with parent directories when
+     * several files share a name.
+     */
+    anonymousOperations?: 'allow' | 'error' | 'fileName'
+
+    /**
+     * How GraphQL is extracted per document file extension (without the
+     * dot), merged over the defaults: `graphql` for .graphql/.gql, `script`
+     * for JS/TS, `html` for .vue/.svelte, `astro` for .astro and `markdown`
+     * for .md/.mdx.
+     */
+    documentExtensions?: Record<string, 'graphql' | 'script' | 'html' | 'astro' | 'markdown'>
+
+    /**
+     * How GraphQL is found in JavaScript/TypeScript documents. Outputs can
+     * replace it with their own `pluckConfig`.
+     */
+    pluckConfig?: PluckConfig
+
+    /**
+     * File and directory names (or globs on names) skipped when expanding
+     * document and schema globs, in addition to `.gitignore`d paths.
+     * Replaces the default `['node_modules', '.git', 'target', '__generated__']`.
+     */
+    ignore?: string[]
+
     /**
      * Base directory for resolving paths.
      * @internal Set automatically by the CLI - do not set manually.
@@ -30,6 +95,70 @@ export interface CodegenConfig {
      * @internal Set automatically by the CLI - do not set manually.
      */
     schemaContent?: string[]
+
+    /** Request options for `schema` URLs */
+    remoteSchema?: RemoteSchemaConfig
+
+    /**
+     * Apollo Federation version of a subgraph schema. Adds the federation
+     * directive and scalar definitions, allows `extend type` of types
+     * defined in other subgraphs, and hides federation types from output.
+     */
+    federation?: 1 | 2
+}
+
+export interface RemoteSchemaConfig {
+    /**
+     * Request headers. `${VAR}` and `$VAR` in values expand to environment
+     * variables, e.g. `{ Authorization: "Bearer ${API_TOKEN}" }`
+     */
+    headers?: Record<string, string>
+
+    /** HTTP method for the introspection query (default: POST) */
+    method?: "GET" | "POST"
+
+    /** Request timeout in milliseconds (default: 10000) */
+    timeout?: number
+}
+
+export interface ComplexityConfig {
+    /** Maximum nesting of fields */
+    maxDepth?: number
+
+    /** Maximum number of aliased fields */
+    maxAliases?: number
+
+    /**
+     * Maximum cost. Selecting a field costs its weight plus the cost of its
+     * selections times the `first`/`last` argument of list fields.
+     */
+    maxCost?: number
+
+    /** Field weights other than 1 by coordinate, e.g. `{ "Query.search": 10 }` */
+    fieldWeights?: Record<string, number>
+
+    /**
+     * Assumed size of lists without a `first` or `last` argument, or where
+     * it is a variable without a default (default: 1)
+     */
+    defaultListSize?: number
+}
+
+export interface PluckConfig {
+    /** Tag names recognized in addition to `gql` and `graphql` */
+    tags?: string[]
+
+    /** Magic comments recognized in addition to `/* GraphQL *\/` */
+    magicComments?: string[]
+
+    /** Identifiers that are tags without being imported, e.g. a global `gql` */
+    globalIdentifiers?: string[]
+
+    /**
+     * Only treat tags as GraphQL when imported from one of these modules
+     * (e.g. `@apollo/client`, `graphql-tag`). Renamed imports are followed.
+     */
+    modules?: string[]
 }
 
 export interface OutputConfig {
@@ -41,13 +170,62 @@ export interface OutputConfig {
 
     /** Shared config for all generators */
     config?: GeneratorOptions
+
+    /**
+     * Path of another output that generates the schema types. Operation types
+     * in this output import the types they reference from it instead of
+     * duplicating them.
+     */
+    importTypesFrom?: string
+
+    /**
+     * Documents for this output, replacing the top-level `documents`.
+     * They're collected and validated on their own, so operation names only
+     * need to be unique within them and spread fragments must be among them.
+     */
+    documents?: string | string[]
+
+    /** Only generate operations matching this filter */
+    include?: DocumentFilter
+
+    /** Skip operations matching this filter */
+    exclude?: DocumentFilter
+
+    /**
+     * How GraphQL is found in the JavaScript/TypeScript documents of this
+     * output, replacing the top-level `pluckConfig`
+     */
+    pluckConfig?: PluckConfig
 }
 
+/**
+ * Operation filter for an output. An operation matches when it satisfies
+ * every criterion that is set, and any entry within a criterion.
+ */
+export interface DocumentFilter {
+    /** Operation name globs, e.g. `Admin*` */
+    names?: string[]
+
+    /** Operation types */
+    types?: Array<'query' | 'mutation' | 'subscription'>
+
+    /** Globs on the path of the file defining the operation */
+    paths?: string[]
+}
+
+export type ScalarConfig =
+    | string
+    | { input: string; output: string }
+    | { module: string; export: string; input?: string; output?: string }
+
 export type GeneratorConfig = string | Record<string, GeneratorOptions>
 
 export interface GeneratorOptions {
-    /** Custom scalar type mappings */
-    scalars?: Record<string, string | { input: string; output: string }>
+    /**
+     * Custom scalar type mappings. Use `module#Export` (e.g. `dayjs#Dayjs`) or
+     * the `module` form to import the type into each generated file.
+     */
+    scalars?: Record<string, ScalarConfig>
     /** Whether to generate readonly types */
     immutableTypes?: boolean
     /** Use string union types instead of TS enums */
@@ -66,6 +244,15 @@ export interface GeneratorOptions {
     strictScalars?: boolean
 
     onlyReferencedTypes?: boolean
+
+    /**
+     * Emit a `<Name>Document` constant with each operation's source (and its
+     * fragments) in `operation-registry` output, referenced from its entry
+     */
+    registryDocuments?: boolean
+
+    /** Drop deprecated fields, arguments, input fields and enum values from schema types */
+    omitDeprecated?: boolean
 }
 
 export interface HooksConfig {